@@ -10,6 +10,7 @@ pub enum BuiltinFn {
     Assert,
     CProverAssume,
     CProverCover,
+    CProverOutput,
     Calloc,
     Ceil,
     Ceilf,
@@ -69,6 +70,7 @@ fn to_string(&self) -> String {
             Assert => "assert",
             CProverAssume => "__CPROVER_assume",
             CProverCover => "__CPROVER_cover",
+            CProverOutput => "__CPROVER_output",
             Calloc => "calloc",
             Ceil => "ceil",
             Ceilf => "ceilf",
@@ -132,6 +134,7 @@ pub fn param_types(&self) -> Vec<Type> {
             Assert => vec![Type::bool()],
             CProverAssume => vec![Type::bool()],
             CProverCover => vec![Type::bool()],
+            CProverOutput => vec![Type::c_char().to_pointer(), Type::c_int()],
             Calloc => vec![Type::size_t(), Type::size_t()],
             Ceil => vec![Type::double()],
             Ceilf => vec![Type::float()],
@@ -188,6 +191,7 @@ pub fn return_type(&self) -> Type {
             Assert => Type::empty(),
             CProverAssume => Type::empty(),
             CProverCover => Type::empty(),
+            CProverOutput => Type::empty(),
             Calloc => Type::void_pointer(),
             Ceil => Type::double(),
             Ceilf => Type::float(),
@@ -247,6 +251,7 @@ pub fn list_all() -> Vec<BuiltinFn> {
             Assert,
             CProverAssume,
             CProverCover,
+            CProverOutput,
             Calloc,
             Ceil,
             Ceilf,
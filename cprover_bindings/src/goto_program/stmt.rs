@@ -64,6 +64,8 @@ pub enum StmtBody {
     },
     /// Marks the target place as uninitialized.
     Deinit(Expr),
+    /// Marks the lifetime of the variable as ended: pointers to it become dangling.
+    Dead(Expr), // SymbolExpr
     /// `e;`
     Expression(Expr),
     // `for (init; cond; update) {body}`
@@ -244,6 +246,12 @@ pub fn deinit(place: Expr, loc: Location) -> Self {
         stmt!(Deinit(place), loc)
     }
 
+    /// `Dead(var)`, see `StmtBody::Dead`.
+    pub fn dead(var: Expr, loc: Location) -> Self {
+        assert!(var.is_symbol());
+        stmt!(Dead(var), loc)
+    }
+
     /// `e;`
     pub fn code_expression(e: Expr, loc: Location) -> Self {
         stmt!(Expression(e), loc)
@@ -435,6 +435,7 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
                     code_irep(IrepId::Decl, vec![lhs.to_irep(mm)])
                 }
             }
+            StmtBody::Dead(var) => code_irep(IrepId::Dead, vec![var.to_irep(mm)]),
             StmtBody::Deinit(place) => {
                 // CBMC doesn't yet have a notion of poison (https://github.com/diffblue/cbmc/issues/7014)
                 // So we translate identically to `nondet` here, but add a comment noting we wish it were poison
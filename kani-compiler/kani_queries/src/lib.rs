@@ -36,6 +36,21 @@ fn default() -> Self {
     }
 }
 
+/// The kinds of lossy numeric `as` conversions that can be checked.
+#[derive(Debug, Clone, Copy, AsRefStr, EnumString, EnumVariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ConversionCheck {
+    /// An integer is cast to a narrower integer type that can't represent its value.
+    Truncation,
+    /// An integer is cast to an integer type, and the result has a different sign.
+    SignChange,
+    /// A float is cast to an integer type that can't represent its integer part (or it's NaN),
+    /// so the result saturates.
+    FloatToInt,
+    /// An integer is cast to a float type that can't represent it exactly, so it's rounded.
+    IntToFloat,
+}
+
 pub trait UserInput {
     fn set_emit_vtable_restrictions(&mut self, restrictions: bool);
     fn get_emit_vtable_restrictions(&self) -> bool;
@@ -55,6 +70,48 @@ pub trait UserInput {
     fn set_stubbing_enabled(&mut self, stubbing_enabled: bool);
     fn get_stubbing_enabled(&self) -> bool;
 
+    fn set_harness_filter(&mut self, harnesses: Option<Vec<String>>);
+    fn get_harness_filter(&self) -> Option<&[String]>;
+
+    fn set_model_suffix(&mut self, suffix: Option<String>);
+    fn get_model_suffix(&self) -> Option<&str>;
+
+    fn set_default_models(&mut self, default_models: bool);
+    fn get_default_models(&self) -> bool;
+
+    fn set_uninit_checks(&mut self, uninit_checks: bool);
+    fn get_uninit_checks(&self) -> bool;
+
+    fn set_aliasing_checks(&mut self, aliasing_checks: bool);
+    fn get_aliasing_checks(&self) -> bool;
+
+    fn set_alignment_checks(&mut self, alignment_checks: bool);
+    fn get_alignment_checks(&self) -> bool;
+
+    fn set_validity_checks(&mut self, validity_checks: bool);
+    fn get_validity_checks(&self) -> bool;
+
+    fn set_conversion_checks(&mut self, conversion_checks: Vec<ConversionCheck>);
+    fn get_conversion_checks(&self) -> &[ConversionCheck];
+
+    fn set_nondet_alloc_failure(&mut self, nondet_alloc_failure: bool);
+    fn get_nondet_alloc_failure(&self) -> bool;
+
+    fn set_nondet_alloc_failure_min_size(&mut self, min_size: u64);
+    fn get_nondet_alloc_failure_min_size(&self) -> u64;
+
+    fn set_nondet_alloc_failure_crates(&mut self, crates: Vec<String>);
+    fn get_nondet_alloc_failure_crates(&self) -> &[String];
+
+    fn set_leak_check(&mut self, leak_check: bool);
+    fn get_leak_check(&self) -> bool;
+
+    fn set_record_nondet(&mut self, record_nondet: bool);
+    fn get_record_nondet(&self) -> bool;
+
+    fn set_vacuity_checks(&mut self, vacuity_checks: bool);
+    fn get_vacuity_checks(&self) -> bool;
+
     #[cfg(feature = "unsound_experiments")]
     fn get_unsound_experiments(&self) -> Arc<Mutex<UnsoundExperiments>>;
 }
@@ -67,6 +124,34 @@ pub struct QueryDb {
     ignore_global_asm: AtomicBool,
     reachability_analysis: Mutex<ReachabilityType>,
     stubbing_enabled: bool,
+    /// The (fully qualified) names of the harnesses to codegen, if not all of them.
+    harness_filter: Option<Vec<String>>,
+    /// A suffix to distinguish the model of this compilation from the other models of the crate.
+    model_suffix: Option<String>,
+    /// Whether the default model of the `getrandom` crate is enabled.
+    default_models: bool,
+    /// Whether reads of uninitialized memory are checked.
+    uninit_checks: bool,
+    /// Whether the aliasing rules of references and raw pointers are checked.
+    aliasing_checks: bool,
+    /// Whether the alignment of the raw pointers that are accessed is checked.
+    alignment_checks: bool,
+    /// Whether the values produced by unsafe operations in user code are checked to be valid.
+    validity_checks: bool,
+    /// The kinds of lossy `as` conversions that are checked in user code.
+    conversion_checks: Vec<ConversionCheck>,
+    /// Whether the allocations of every harness may nondeterministically fail.
+    nondet_alloc_failure: bool,
+    /// The minimum size of the allocations that may fail.
+    nondet_alloc_failure_min_size: u64,
+    /// The crates whose allocations may fail, or empty if the allocations of all crates may fail.
+    nondet_alloc_failure_crates: Vec<String>,
+    /// Whether the harnesses check that the memory they allocate is freed.
+    leak_check: bool,
+    /// Whether every value produced by `kani::any()` is passed to `__KANI_record_nondet`.
+    record_nondet: bool,
+    /// Whether the reachability of the assumptions and assertions of user code is checked.
+    vacuity_checks: bool,
     #[cfg(feature = "unsound_experiments")]
     unsound_experiments: Arc<Mutex<UnsoundExperiments>>,
 }
@@ -120,6 +205,118 @@ fn get_stubbing_enabled(&self) -> bool {
         self.stubbing_enabled
     }
 
+    fn set_harness_filter(&mut self, harnesses: Option<Vec<String>>) {
+        self.harness_filter = harnesses;
+    }
+
+    fn get_harness_filter(&self) -> Option<&[String]> {
+        self.harness_filter.as_deref()
+    }
+
+    fn set_model_suffix(&mut self, suffix: Option<String>) {
+        self.model_suffix = suffix;
+    }
+
+    fn get_model_suffix(&self) -> Option<&str> {
+        self.model_suffix.as_deref()
+    }
+
+    fn set_default_models(&mut self, default_models: bool) {
+        self.default_models = default_models;
+    }
+
+    fn get_default_models(&self) -> bool {
+        self.default_models
+    }
+
+    fn set_uninit_checks(&mut self, uninit_checks: bool) {
+        self.uninit_checks = uninit_checks;
+    }
+
+    fn get_uninit_checks(&self) -> bool {
+        self.uninit_checks
+    }
+
+    fn set_aliasing_checks(&mut self, aliasing_checks: bool) {
+        self.aliasing_checks = aliasing_checks;
+    }
+
+    fn get_aliasing_checks(&self) -> bool {
+        self.aliasing_checks
+    }
+
+    fn set_alignment_checks(&mut self, alignment_checks: bool) {
+        self.alignment_checks = alignment_checks;
+    }
+
+    fn get_alignment_checks(&self) -> bool {
+        self.alignment_checks
+    }
+
+    fn set_validity_checks(&mut self, validity_checks: bool) {
+        self.validity_checks = validity_checks;
+    }
+
+    fn get_validity_checks(&self) -> bool {
+        self.validity_checks
+    }
+
+    fn set_conversion_checks(&mut self, conversion_checks: Vec<ConversionCheck>) {
+        self.conversion_checks = conversion_checks;
+    }
+
+    fn get_conversion_checks(&self) -> &[ConversionCheck] {
+        &self.conversion_checks
+    }
+
+    fn set_nondet_alloc_failure(&mut self, nondet_alloc_failure: bool) {
+        self.nondet_alloc_failure = nondet_alloc_failure;
+    }
+
+    fn get_nondet_alloc_failure(&self) -> bool {
+        self.nondet_alloc_failure
+    }
+
+    fn set_nondet_alloc_failure_min_size(&mut self, min_size: u64) {
+        self.nondet_alloc_failure_min_size = min_size;
+    }
+
+    fn get_nondet_alloc_failure_min_size(&self) -> u64 {
+        self.nondet_alloc_failure_min_size
+    }
+
+    fn set_nondet_alloc_failure_crates(&mut self, crates: Vec<String>) {
+        self.nondet_alloc_failure_crates = crates;
+    }
+
+    fn get_nondet_alloc_failure_crates(&self) -> &[String] {
+        &self.nondet_alloc_failure_crates
+    }
+
+    fn set_leak_check(&mut self, leak_check: bool) {
+        self.leak_check = leak_check;
+    }
+
+    fn get_leak_check(&self) -> bool {
+        self.leak_check
+    }
+
+    fn set_record_nondet(&mut self, record_nondet: bool) {
+        self.record_nondet = record_nondet;
+    }
+
+    fn get_record_nondet(&self) -> bool {
+        self.record_nondet
+    }
+
+    fn set_vacuity_checks(&mut self, vacuity_checks: bool) {
+        self.vacuity_checks = vacuity_checks;
+    }
+
+    fn get_vacuity_checks(&self) -> bool {
+        self.vacuity_checks
+    }
+
     #[cfg(feature = "unsound_experiments")]
     fn get_unsound_experiments(&self) -> Arc<Mutex<UnsoundExperiments>> {
         self.unsound_experiments.clone()
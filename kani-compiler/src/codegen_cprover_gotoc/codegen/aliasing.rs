@@ -0,0 +1,336 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module instruments the program for `--aliasing-checks`, which detect violations of the
+//! aliasing rules by unsafe code, following the model of Stacked Borrows.
+//!
+//! The borrow stack is kept by the `__KANI_sb_*` functions of `kani_lib.c`, which track the stack
+//! of a single byte chosen nondeterministically. Here we keep the tag of every thin reference or
+//! raw pointer local in a shadow variable, and we insert the calls that update the stack:
+//!  * A `Retag` statement (which the compiler emits with `-Zmir-emit-retag`) gives a fresh tag to
+//!    a pointer, derived from the tag that it had. This accesses the memory through the parent tag.
+//!  * Assigning a pointer (or creating one with `&` or `&raw`) copies the tag of the pointer it
+//!    comes from, or the base tag of the owner of the memory. Other pointers, e.g., the ones
+//!    returned by calls, get the wildcard tag.
+//!  * Reads and writes of places through a pointer, or of locals whose address is taken, access
+//!    the memory through the corresponding tag.
+//!
+//! Every borrow and access is an event, whose ID we record at its location. A violation records
+//! the IDs of the events that created and invalidated the borrow, so that the driver can report
+//! where they happened (see `kani_metadata::checks`).
+//!
+//! We only instrument the functions of the crate under verification. The tags don't cross
+//! function boundaries, and we don't model protectors or interior mutability within a value.
+
+use super::uninit::OperandPlaces;
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
+use kani_metadata::checks::{
+    ALIASING_BORROW_VAR, ALIASING_CONFLICT_VAR, ALIASING_EVENT_VAR, ALIASING_VIOLATION_DESC,
+};
+use kani_queries::UserInput;
+use rustc_middle::mir;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{
+    Local, Operand, Place, ProjectionElem, RetagKind, Rvalue, Statement, StatementKind, Terminator,
+    TerminatorKind,
+};
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::{self, ParamEnv, Ty, TypeAndMut};
+
+/// The tag of the owner of the memory.
+const BASE_TAG: u64 = 0;
+/// The tag of pointers whose provenance is not tracked.
+const WILDCARD_TAG: u64 = 0xFFFF_FFFF;
+
+/// The permissions of the items of the borrow stack (see `kani_lib.c`).
+const UNIQUE: u64 = 0;
+const SHARED_RW: u64 = 1;
+const SHARED_RO: u64 = 2;
+
+/// The maximum depth of the borrow stack (see `kani_lib.c`).
+const STACK_DEPTH: usize = 8;
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Whether the current function is instrumented with the aliasing checks.
+    pub fn aliasing_checks_enabled(&self) -> bool {
+        self.queries.get_aliasing_checks() && self.current_fn().instance().def_id().is_local()
+    }
+
+    /// Declares the shadow tags of the pointer locals of the current function, and resets the
+    /// borrow stack of the arguments whose address is taken.
+    pub fn codegen_aliasing_prelude(&mut self) -> Stmt {
+        let mir = self.current_fn().mir();
+        let loc = self.codegen_span(&mir.span);
+        let mut stmts = vec![];
+        for local in mir.local_decls.indices() {
+            if self.aliasing_tracks(local) {
+                let tag = self.aliasing_tag(local);
+                stmts.push(Stmt::decl(tag, Some(Self::aliasing_tag_constant(WILDCARD_TAG)), loc));
+            }
+        }
+        for arg in mir.args_iter() {
+            if self.current_fn().is_borrowed(arg) {
+                stmts.push(self.codegen_aliasing_reset(arg, loc));
+            }
+        }
+        Stmt::block(stmts, loc)
+    }
+
+    /// Instruments the code of a statement: the places it reads and writes are accessed before it
+    /// runs, and the tag of the pointer it assigns, or retags, is updated after it.
+    pub fn codegen_aliasing_statement(&mut self, stmt: &Statement<'tcx>, code: Stmt) -> Stmt {
+        let loc = *code.location();
+        let mut visitor = OperandPlaces { places: vec![] };
+        visitor.visit_statement(stmt, mir::Location::START);
+        let mut stmts: Vec<Stmt> = visitor
+            .places
+            .iter()
+            .map(|place| self.codegen_aliasing_access(place, false, loc))
+            .collect();
+        match &stmt.kind {
+            StatementKind::Assign(box (place, rvalue)) => {
+                stmts.push(self.codegen_aliasing_access(place, true, loc));
+                stmts.push(code);
+                if let Some(local) = place.as_local().filter(|local| self.aliasing_tracks(*local)) {
+                    let tag = self.aliasing_derived_tag(rvalue);
+                    stmts.push(self.aliasing_tag(local).assign(tag, loc));
+                }
+            }
+            StatementKind::Retag(kind, box place) => {
+                stmts.push(code);
+                stmts.push(self.codegen_aliasing_retag(*kind, place, loc));
+            }
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local)
+                if self.current_fn().is_borrowed(*local) =>
+            {
+                stmts.push(code);
+                stmts.push(self.codegen_aliasing_reset(*local, loc));
+            }
+            _ => stmts.push(code),
+        }
+        Stmt::block(stmts, loc)
+    }
+
+    /// Instruments the code of a terminator: the places it reads, and the destination of a call,
+    /// are accessed before it runs. The pointer returned by a call gets the wildcard tag.
+    pub fn codegen_aliasing_terminator(&mut self, term: &Terminator<'tcx>, code: Stmt) -> Stmt {
+        let loc = *code.location();
+        let mut visitor = OperandPlaces { places: vec![] };
+        visitor.visit_terminator(term, mir::Location::START);
+        let mut stmts: Vec<Stmt> = visitor
+            .places
+            .iter()
+            .map(|place| self.codegen_aliasing_access(place, false, loc))
+            .collect();
+        if let TerminatorKind::Call { destination, .. } = &term.kind {
+            stmts.push(self.codegen_aliasing_access(destination, true, loc));
+            if let Some(local) = destination.as_local().filter(|local| self.aliasing_tracks(*local))
+            {
+                let wildcard = Self::aliasing_tag_constant(WILDCARD_TAG);
+                stmts.push(self.aliasing_tag(local).assign(wildcard, loc));
+            }
+        }
+        stmts.push(code);
+        Stmt::block(stmts, loc)
+    }
+
+    /// Whether we keep the tag of `local`, i.e., it's a thin reference or raw pointer.
+    fn aliasing_tracks(&self, local: Local) -> bool {
+        match self.local_ty(local).kind() {
+            ty::Ref(_, pointee, _) | ty::RawPtr(TypeAndMut { ty: pointee, .. }) => {
+                pointee.is_sized(self.tcx, ParamEnv::reveal_all())
+            }
+            _ => false,
+        }
+    }
+
+    /// The shadow variable that holds the tag of `local`.
+    fn aliasing_tag(&mut self, local: Local) -> Expr {
+        let base_name = format!("__kani_sb_tag_{}", local.index());
+        let name = format!("{}::{base_name}", self.current_fn().name());
+        self.ensure(name, |_, name| {
+            Symbol::variable(name, base_name, Type::unsigned_int(32), Location::none())
+                .with_is_hidden(true)
+        })
+        .to_expr()
+    }
+
+    fn aliasing_tag_constant(tag: u64) -> Expr {
+        Expr::int_constant(tag, Type::unsigned_int(32))
+    }
+
+    /// The tag of the pointer used to access `place`: the tag of the pointer local it
+    /// dereferences, or the base tag if it's a local. Returns `None` if the pointer is not tracked.
+    fn aliasing_place_tag(&mut self, place: &Place<'tcx>) -> Option<Expr> {
+        match place.projection.iter().rposition(|elem| elem == ProjectionElem::Deref) {
+            None => Some(Self::aliasing_tag_constant(BASE_TAG)),
+            Some(0) if self.aliasing_tracks(place.local) => Some(self.aliasing_tag(place.local)),
+            Some(_) => None,
+        }
+    }
+
+    /// The tag of a pointer assigned the value of `rvalue`.
+    fn aliasing_derived_tag(&mut self, rvalue: &Rvalue<'tcx>) -> Expr {
+        let tag = match rvalue {
+            Rvalue::Use(Operand::Copy(src) | Operand::Move(src))
+            | Rvalue::Cast(_, Operand::Copy(src) | Operand::Move(src), _) => match src.as_local() {
+                Some(local) if self.aliasing_tracks(local) => Some(self.aliasing_tag(local)),
+                _ => None,
+            },
+            Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) => {
+                self.aliasing_place_tag(place)
+            }
+            _ => None,
+        };
+        tag.unwrap_or_else(|| Self::aliasing_tag_constant(WILDCARD_TAG))
+    }
+
+    /// Reads or writes `place` through the pointer it dereferences, or through the base tag if
+    /// it's a local whose address is taken.
+    fn codegen_aliasing_access(&mut self, place: &Place<'tcx>, write: bool, loc: Location) -> Stmt {
+        if !place.has_deref() && !self.current_fn().is_borrowed(place.local) {
+            return Stmt::skip(loc);
+        }
+        let (Some(tag), Some((ptr, size))) =
+            (self.aliasing_place_tag(place), self.aliasing_place_bytes(place)) else {
+            return Stmt::skip(loc);
+        };
+        let args = vec![tag, ptr, size, Expr::bool_constant(write)];
+        let access = self.codegen_aliasing_fn("__KANI_sb_access", args, Type::unsigned_int(32));
+        self.codegen_aliasing_check(access, loc)
+    }
+
+    /// Gives a fresh tag to the pointer stored in `place`.
+    fn codegen_aliasing_retag(
+        &mut self,
+        kind: RetagKind,
+        place: &Place<'tcx>,
+        loc: Location,
+    ) -> Stmt {
+        let Some(local) = place.as_local() else { return Stmt::skip(loc) };
+        if !self.aliasing_tracks(local) {
+            return Stmt::skip(loc);
+        }
+        let (pointee, permission) = match self.local_ty(local).kind() {
+            ty::Ref(_, pointee, mir::Mutability::Mut)
+            | ty::RawPtr(TypeAndMut { ty: pointee, mutbl: mir::Mutability::Mut }) => {
+                // Two-phase borrows allow reads through other pointers until they are activated.
+                let unique = kind != RetagKind::TwoPhase && self.local_ty(local).is_ref();
+                (*pointee, if unique { UNIQUE } else { SHARED_RW })
+            }
+            ty::Ref(_, pointee, mir::Mutability::Not)
+            | ty::RawPtr(TypeAndMut { ty: pointee, mutbl: mir::Mutability::Not }) => {
+                (*pointee, if self.aliasing_is_freeze(*pointee) { SHARED_RO } else { SHARED_RW })
+            }
+            _ => return Stmt::skip(loc),
+        };
+        let layout = self.layout_of(pointee);
+        if layout.is_zst() {
+            return Stmt::skip(loc);
+        }
+        let Ok(pointer) = self.codegen_place(place) else { return Stmt::skip(loc) };
+        let args = vec![
+            self.aliasing_tag(local).address_of(),
+            pointer.goto_expr.cast_to(Type::unsigned_int(8).to_pointer()),
+            Expr::int_constant(layout.size.bytes(), Type::size_t()),
+            Expr::int_constant(permission, Type::unsigned_int(8)),
+        ];
+        let retag = self.codegen_aliasing_fn("__KANI_sb_retag", args, Type::unsigned_int(32));
+        self.codegen_aliasing_check(retag, loc)
+    }
+
+    /// Resets the borrow stack of `local`, whose storage is (re)allocated.
+    fn codegen_aliasing_reset(&mut self, local: Local, loc: Location) -> Stmt {
+        let Some((ptr, size)) = self.aliasing_place_bytes(&Place::from(local)) else {
+            return Stmt::skip(loc);
+        };
+        self.codegen_aliasing_fn("__KANI_sb_reset", vec![ptr, size], Type::empty()).as_stmt(loc)
+    }
+
+    /// Records the ID of the event `op` at its location, and asserts that it didn't violate the
+    /// aliasing rules. On a violation, the IDs of the events that created and invalidated the
+    /// borrow are recorded for the message of the failed check.
+    ///
+    /// We also assert that the model didn't run out of room, since the later uses of the memory
+    /// aren't checked after that. This is not an assumption, so that a violation found at the same
+    /// time is still reported.
+    fn codegen_aliasing_check(&mut self, op: Expr, loc: Location) -> Stmt {
+        let ok = self.codegen_aliasing_fn("__KANI_sb_ok", vec![], Type::bool());
+        let borrow = self.codegen_aliasing_fn(
+            "__KANI_sb_violation_borrow_event",
+            vec![],
+            Type::unsigned_int(32),
+        );
+        let conflict = self.codegen_aliasing_fn(
+            "__KANI_sb_violation_conflict_event",
+            vec![],
+            Type::unsigned_int(32),
+        );
+        let record = Stmt::block(
+            vec![
+                self.aliasing_event_var(ALIASING_BORROW_VAR, loc).assign(borrow, loc),
+                self.aliasing_event_var(ALIASING_CONFLICT_VAR, loc).assign(conflict, loc),
+            ],
+            loc,
+        );
+        let overflow = self.codegen_aliasing_fn("__KANI_sb_stack_overflow", vec![], Type::bool());
+        // The driver reports the other checks as undetermined when this fails.
+        let msg = GotocCtx::unsupported_msg(
+            &format!(
+                "Aliasing model incomplete: tracking more than {STACK_DEPTH} borrows of the same \
+                 memory"
+            ),
+            None,
+        );
+        Stmt::block(
+            vec![
+                self.aliasing_event_var(ALIASING_EVENT_VAR, loc).assign(op, loc),
+                ok.clone().not().if_then_else(record, None, loc),
+                self.codegen_assert(overflow.not(), PropertyClass::UnsupportedConstruct, &msg, loc),
+                self.codegen_assert_assume(
+                    ok,
+                    PropertyClass::SafetyCheck,
+                    ALIASING_VIOLATION_DESC,
+                    loc,
+                ),
+            ],
+            loc,
+        )
+    }
+
+    /// The global variable that holds the ID of an event.
+    fn aliasing_event_var(&mut self, name: &str, loc: Location) -> Expr {
+        self.ensure_global_var(name, false, Type::unsigned_int(32), loc, |_, _| None)
+    }
+
+    fn aliasing_is_freeze(&self, ty: Ty<'tcx>) -> bool {
+        ty.is_freeze(self.tcx, ParamEnv::reveal_all())
+    }
+
+    /// The address (as a byte pointer) and the size of `place`, or `None` if it has no bytes
+    /// (i.e., it is a ZST or unsized) or if its codegen is not supported.
+    fn aliasing_place_bytes(&mut self, place: &Place<'tcx>) -> Option<(Expr, Expr)> {
+        let layout = self.layout_of(self.place_ty(place));
+        if layout.is_zst() || layout.is_unsized() {
+            return None;
+        }
+        let ptr = self.codegen_place(place).ok()?.goto_expr.address_of();
+        Some((
+            ptr.cast_to(Type::unsigned_int(8).to_pointer()),
+            Expr::int_constant(layout.size.bytes(), Type::size_t()),
+        ))
+    }
+
+    /// Calls one of the `__KANI_sb_*` functions of `kani_lib.c`, which is declared (without a
+    /// body) the first time it is used.
+    fn codegen_aliasing_fn(&mut self, function: &str, args: Vec<Expr>, ret: Type) -> Expr {
+        let param_types = args.iter().map(|arg| arg.typ().clone()).collect();
+        let typ = Type::code_with_unnamed_parameters(param_types, ret);
+        let func = self
+            .ensure(function, |_, name| Symbol::function(name, typ, None, name, Location::none()))
+            .to_expr();
+        func.call(args)
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module generates the checks that the raw pointers that are accessed are properly aligned,
+//! which CBMC's `--pointer-check` doesn't detect. We check:
+//!  * The dereferences of raw pointers, unless they are only used to take an address (e.g., in
+//!    `addr_of!((*ptr).field)`).
+//!  * The pointer arguments of `ptr::read`, `ptr::write` (and the equivalent methods of raw
+//!    pointers) and `slice::from_raw_parts`.
+//!
+//! These checks are enabled by default and can be disabled with `--no-alignment-checks`. The
+//! intrinsics that access memory (e.g., `copy_nonoverlapping`) always check the alignment of
+//! their pointer arguments, in `intrinsic.rs`.
+
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt};
+use kani_queries::UserInput;
+use rustc_middle::mir;
+use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::{Operand, Place, ProjectionElem, Statement, Terminator};
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::{Instance, ParamEnv, Ty};
+
+/// Collects the places accessed by a statement or terminator, i.e., all the places except those
+/// whose address is taken.
+pub(super) struct AccessedPlaces<'tcx> {
+    pub(super) places: Vec<Place<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for AccessedPlaces<'tcx> {
+    fn visit_place(
+        &mut self,
+        place: &Place<'tcx>,
+        context: PlaceContext,
+        _location: mir::Location,
+    ) {
+        if !matches!(
+            context,
+            PlaceContext::NonUse(_)
+                | PlaceContext::NonMutatingUse(NonMutatingUseContext::AddressOf)
+                | PlaceContext::MutatingUse(MutatingUseContext::AddressOf)
+        ) {
+            self.places.push(*place);
+        }
+    }
+}
+
+impl<'tcx> GotocCtx<'tcx> {
+    pub fn alignment_checks_enabled(&self) -> bool {
+        self.queries.get_alignment_checks()
+    }
+
+    /// Checks the alignment of the raw pointers dereferenced by a statement before it runs.
+    pub fn codegen_alignment_statement(&mut self, stmt: &Statement<'tcx>, code: Stmt) -> Stmt {
+        let mut visitor = AccessedPlaces { places: vec![] };
+        visitor.visit_statement(stmt, mir::Location::START);
+        self.codegen_alignment_derefs(visitor.places, code)
+    }
+
+    /// Checks the alignment of the raw pointers dereferenced by a terminator before it runs.
+    pub fn codegen_alignment_terminator(&mut self, term: &Terminator<'tcx>, code: Stmt) -> Stmt {
+        let mut visitor = AccessedPlaces { places: vec![] };
+        visitor.visit_terminator(term, mir::Location::START);
+        self.codegen_alignment_derefs(visitor.places, code)
+    }
+
+    /// Checks the pointer argument of a call to `ptr::read` or `ptr::write`, or to the
+    /// equivalent methods of raw pointers (e.g., `<*mut T>::write`).
+    pub fn codegen_alignment_call_args(
+        &mut self,
+        instance: Instance<'tcx>,
+        fargs: &[Expr],
+        args: &[Operand<'tcx>],
+        loc: Location,
+    ) -> Stmt {
+        if !self.alignment_checks_enabled() {
+            return Stmt::skip(loc);
+        }
+        let Some(name) = self.ptr_fns.aligned_access_name(instance.def_id()) else {
+            return Stmt::skip(loc);
+        };
+        let msg = format!("`{name}` requires that the pointer argument is aligned");
+        let ptr_ty = self.operand_ty(&args[0]);
+        self.codegen_alignment_check(ptr_ty, fargs[0].clone(), &msg, loc)
+    }
+
+    /// Asserts that `ptr`, a thin pointer of type `ptr_ty`, is aligned for its pointee type.
+    pub fn codegen_alignment_check(
+        &mut self,
+        ptr_ty: Ty<'tcx>,
+        ptr: Expr,
+        msg: &str,
+        loc: Location,
+    ) -> Stmt {
+        if !self.alignment_checks_enabled() {
+            return Stmt::skip(loc);
+        }
+        let aligned = self.is_ptr_aligned(ptr_ty, ptr);
+        self.codegen_assert_assume(aligned, PropertyClass::SafetyCheck, msg, loc)
+    }
+
+    fn codegen_alignment_derefs(&mut self, places: Vec<Place<'tcx>>, code: Stmt) -> Stmt {
+        if !self.alignment_checks_enabled() {
+            return code;
+        }
+        let loc = *code.location();
+        let mut stmts = vec![];
+        for place in places {
+            for (base, elem) in place.iter_projections() {
+                if elem != ProjectionElem::Deref {
+                    continue;
+                }
+                let base = Place {
+                    local: base.local,
+                    projection: self.tcx.intern_place_elems(base.projection),
+                };
+                if let Some(check) = self.codegen_alignment_deref(&base, loc) {
+                    stmts.push(check);
+                }
+            }
+        }
+        if stmts.is_empty() {
+            return code;
+        }
+        stmts.push(code);
+        Stmt::block(stmts, loc)
+    }
+
+    /// Asserts that the pointer stored in `ptr_place` is aligned, if it's a raw pointer to a
+    /// sized type with an alignment greater than one.
+    fn codegen_alignment_deref(&mut self, ptr_place: &Place<'tcx>, loc: Location) -> Option<Stmt> {
+        let ptr_ty = self.place_ty(ptr_place);
+        if !ptr_ty.is_unsafe_ptr() {
+            return None;
+        }
+        let pointee_ty = ptr_ty.builtin_deref(true).unwrap().ty;
+        if !pointee_ty.is_sized(self.tcx, ParamEnv::reveal_all()) {
+            return None;
+        }
+        let align = self.layout_of(pointee_ty).align.abi.bytes();
+        if align == 1 {
+            return None;
+        }
+        let ptr = self.codegen_place(ptr_place).ok()?.goto_expr;
+        let msg = format!("misaligned pointer dereference: address must be a multiple of {align}");
+        Some(self.codegen_alignment_check(ptr_ty, ptr, &msg, loc))
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module instruments the program for nondeterministic allocation failure, which is enabled
+//! for all harnesses by `--nondet-alloc-failure`, or for a harness by
+//! `#[kani::nondet_alloc_failure]`.
+//!
+//! The allocation functions of `kani_lib.c` decide whether an allocation fails with
+//! `__KANI_alloc_fails`. Here we insert the calls that configure it:
+//!  * Harnesses that enable it call `__KANI_alloc_failure_enable` on entry, with the minimum size
+//!    of the allocations that may fail (`--nondet-alloc-failure-min-size`).
+//!  * If it's restricted to some crates (`--nondet-alloc-failure-crate`), only the allocations
+//!    performed during the calls from the functions of these crates into the standard library
+//!    may fail. These calls are enclosed between `__KANI_alloc_failure_enter` and
+//!    `__KANI_alloc_failure_exit`.
+
+use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::has_kanitool_attribute;
+use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
+use kani_queries::{QueryDb, UserInput};
+use rustc_middle::ty::{Instance, TyCtxt};
+
+/// Whether allocations may fail in some harness of the crate. This is computed once, when the
+/// codegen context is created (see `GotocCtx::nondet_alloc_failure`).
+pub fn nondet_alloc_failure_in_crate(tcx: TyCtxt, queries: &QueryDb) -> bool {
+    queries.get_nondet_alloc_failure()
+        || tcx.hir().body_owners().any(|owner| {
+            has_kanitool_attribute(
+                tcx.get_attrs_unchecked(owner.to_def_id()),
+                "nondet_alloc_failure",
+            )
+        })
+}
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Whether allocations may fail in some harness of the crate.
+    pub fn nondet_alloc_failure_enabled(&self) -> bool {
+        self.nondet_alloc_failure
+    }
+
+    /// Enables allocation failure on entry of the current function, if it's a harness that
+    /// enables it.
+    pub fn codegen_alloc_failure_prelude(&mut self) -> Option<Stmt> {
+        let def_id = self.current_fn().instance().def_id();
+        let attributes = self.tcx.get_attrs_unchecked(def_id);
+        if !has_kanitool_attribute(attributes, "proof")
+            || !(self.queries.get_nondet_alloc_failure()
+                || has_kanitool_attribute(attributes, "nondet_alloc_failure"))
+        {
+            return None;
+        }
+        let loc = self.codegen_span(&self.current_fn().mir().span);
+        let min_size =
+            Expr::int_constant(self.queries.get_nondet_alloc_failure_min_size(), Type::size_t());
+        let all_crates =
+            Expr::bool_constant(self.queries.get_nondet_alloc_failure_crates().is_empty());
+        let enable = self.codegen_alloc_failure_fn(
+            "__KANI_alloc_failure_enable",
+            vec![min_size, all_crates],
+            Type::empty(),
+        );
+        Some(enable.as_stmt(loc))
+    }
+
+    /// Encloses the code of a call to `callee` in the allocation failure scope, if the current
+    /// function belongs to one of the crates whose allocations may fail and `callee` belongs to
+    /// the standard library.
+    pub fn codegen_alloc_failure_scope(
+        &mut self,
+        callee: Instance<'tcx>,
+        call: Vec<Stmt>,
+        loc: Location,
+    ) -> Vec<Stmt> {
+        let crates = self.queries.get_nondet_alloc_failure_crates();
+        let caller_crate = self.tcx.crate_name(self.current_fn().instance().def_id().krate);
+        let callee_crate = self.tcx.crate_name(callee.def_id().krate);
+        if !crates.iter().any(|krate| krate.as_str() == caller_crate.as_str())
+            || !matches!(callee_crate.as_str(), "core" | "alloc" | "std")
+        {
+            return call;
+        }
+        let enter =
+            self.codegen_alloc_failure_fn("__KANI_alloc_failure_enter", vec![], Type::empty());
+        let exit =
+            self.codegen_alloc_failure_fn("__KANI_alloc_failure_exit", vec![], Type::empty());
+        let mut stmts = vec![enter.as_stmt(loc)];
+        stmts.extend(call);
+        stmts.push(exit.as_stmt(loc));
+        stmts
+    }
+
+    /// Whether an allocation of `size` bytes fails.
+    pub fn codegen_alloc_fails(&mut self, size: Expr) -> Expr {
+        self.codegen_alloc_failure_fn("__KANI_alloc_fails", vec![size], Type::bool())
+    }
+
+    fn codegen_alloc_failure_fn(&mut self, function: &str, args: Vec<Expr>, ret: Type) -> Expr {
+        let param_types = args.iter().map(|arg| arg.typ().clone()).collect();
+        let typ = Type::code_with_unnamed_parameters(param_types, ret);
+        let func = self
+            .ensure(function, |_, name| Symbol::function(name, typ, None, name, Location::none()))
+            .to_expr();
+        func.call(args)
+    }
+}
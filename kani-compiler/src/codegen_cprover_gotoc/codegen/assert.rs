@@ -84,6 +84,30 @@ pub enum PropertyClass {
     /// `std::intrinsics::unreachable()` and can't tell the difference between that case
     /// and other cases where the Rust compiler thinks things should be unreachable.
     Unreachable,
+    /// Checks that a value produced by an unsafe operation in user code (e.g., a `transmute` or a
+    /// read through a raw pointer) satisfies the validity invariant of its type.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    ValidityCheck,
+    /// Checks that a numeric `as` conversion in user code doesn't lose information
+    /// (`--conversion-checks`).
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    ConversionCheck,
+    /// Checks that a dereferenced raw pointer doesn't point to a local whose storage is dead.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    DanglingStackPointer,
+    /// Checks that the memory allocated by a harness is freed when it returns (`--leak-check`).
+    /// Unlike CBMC's `memory-leak` checks, these are located at the allocation site.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    LeakCheck,
+    /// A cover of a location that a non-vacuous harness must reach, e.g., its end.
+    ///
+    /// SPECIAL BEHAVIOR: Not reported as a property. The driver warns about the ones that are
+    /// unsatisfiable instead.
+    VacuityCheck,
 }
 
 #[allow(dead_code)]
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::codegen_cprover_gotoc::GotocCtx;
-use rustc_middle::mir::{BasicBlock, BasicBlockData};
+use cbmc::goto_program::Stmt;
+use rustc_middle::mir::{BasicBlock, BasicBlockData, Statement, Terminator};
 
 impl<'tcx> GotocCtx<'tcx> {
     /// Generates Goto-C for a basic block.
@@ -19,22 +20,51 @@ pub fn codegen_block(&mut self, bb: BasicBlock, bbd: &BasicBlockData<'tcx>) {
         match bbd.statements.len() {
             0 => {
                 let term = bbd.terminator();
-                let tcode = self.codegen_terminator(term);
+                let tcode = self.codegen_instrumented_terminator(term);
                 self.current_fn_mut().push_onto_block(tcode.with_label(label));
             }
             _ => {
                 let stmt = &bbd.statements[0];
-                let scode = self.codegen_statement(stmt);
+                let scode = self.codegen_instrumented_statement(stmt);
                 self.current_fn_mut().push_onto_block(scode.with_label(label));
 
                 for s in &bbd.statements[1..] {
-                    let stmt = self.codegen_statement(s);
+                    let stmt = self.codegen_instrumented_statement(s);
                     self.current_fn_mut().push_onto_block(stmt);
                 }
-                let term = self.codegen_terminator(bbd.terminator());
+                let term = self.codegen_instrumented_terminator(bbd.terminator());
                 self.current_fn_mut().push_onto_block(term);
             }
         }
         self.current_fn_mut().reset_current_bb();
     }
+
+    /// Generates Goto-C for a statement, with the instrumentation of the optional checks.
+    fn codegen_instrumented_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
+        let code = self.codegen_statement(stmt);
+        let code = self.codegen_conversion_statement(stmt, code);
+        let code = self.codegen_alignment_statement(stmt, code);
+        let code = self.codegen_dangling_statement(stmt, code);
+        let code = self.codegen_validity_statement(stmt, code);
+        let code = if self.aliasing_checks_enabled() {
+            self.codegen_aliasing_statement(stmt, code)
+        } else {
+            code
+        };
+        if self.uninit_checks_enabled() { self.codegen_uninit_statement(stmt, code) } else { code }
+    }
+
+    /// Generates Goto-C for a terminator, with the instrumentation of the optional checks.
+    fn codegen_instrumented_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
+        let code = self.codegen_terminator(term);
+        let code = self.codegen_alignment_terminator(term, code);
+        let code = self.codegen_dangling_terminator(term, code);
+        let code = self.codegen_validity_terminator(term, code);
+        let code = if self.aliasing_checks_enabled() {
+            self.codegen_aliasing_terminator(term, code)
+        } else {
+            code
+        };
+        if self.uninit_checks_enabled() { self.codegen_uninit_terminator(term, code) } else { code }
+    }
 }
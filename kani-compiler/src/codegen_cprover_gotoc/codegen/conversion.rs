@@ -0,0 +1,173 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module instruments the numeric `as` conversions of user code (i.e., the local crate) for
+//! `--conversion-checks`, which detect conversions that don't preserve the value. Each kind of
+//! check can be enabled separately:
+//!  * `truncation`: an integer is cast to a narrower integer type that can't represent it.
+//!  * `sign-change`: an integer is cast to an integer type and the result has a different sign.
+//!  * `float-to-int`: a float is cast to an integer type that can't represent its integer part,
+//!    or it's NaN, so the result saturates.
+//!  * `int-to-float`: an integer is cast to a float type that can't represent it exactly.
+//!
+//! Conversions are well defined in Rust, so failing these checks doesn't stop the execution.
+//! We don't use CBMC's `--conversion-check` since the code generated by Kani relies on lossy
+//! conversions. The checks are disabled in the functions annotated with
+//! `#[kani::no_conversion_checks]` and in their closures.
+
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::has_kanitool_attribute;
+use cbmc::goto_program::{Expr, Stmt};
+use kani_queries::{ConversionCheck, UserInput};
+use rustc_middle::mir::{CastKind, Rvalue, Statement, StatementKind};
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::{self, FloatTy, Ty};
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Checks the numeric conversion performed by a statement, if any, before it runs.
+    pub fn codegen_conversion_statement(&mut self, stmt: &Statement<'tcx>, code: Stmt) -> Stmt {
+        let StatementKind::Assign(box (_, Rvalue::Cast(kind, operand, dst_ty))) = &stmt.kind else {
+            return code;
+        };
+        if !matches!(kind, CastKind::IntToInt | CastKind::FloatToInt | CastKind::IntToFloat)
+            || !self.conversion_checks_apply()
+        {
+            return code;
+        }
+        let src_ty = self.operand_ty(operand);
+        let dst_ty = self.monomorphize(*dst_ty);
+        let value = self.codegen_operand(operand);
+        let loc = *code.location();
+        let mut stmts: Vec<Stmt> = self
+            .conversion_conditions(value, src_ty, dst_ty)
+            .into_iter()
+            .map(|(msg, cond)| {
+                let msg = format!("`{src_ty} as {dst_ty}` conversion {msg}");
+                self.codegen_assert(cond, PropertyClass::ConversionCheck, &msg, loc)
+            })
+            .collect();
+        if stmts.is_empty() {
+            return code;
+        }
+        stmts.push(code);
+        Stmt::block(stmts, loc)
+    }
+
+    fn conversion_checks_apply(&self) -> bool {
+        if self.queries.get_conversion_checks().is_empty() {
+            return false;
+        }
+        let def_id = self.current_fn().instance().def_id();
+        def_id.is_local()
+            && !has_kanitool_attribute(
+                self.tcx.get_attrs_unchecked(self.tcx.typeck_root_def_id(def_id)),
+                "no_conversion_checks",
+            )
+    }
+
+    fn conversion_check_enabled(&self, check: ConversionCheck) -> bool {
+        self.queries.get_conversion_checks().contains(&check)
+    }
+
+    /// The conditions under which converting `value` from `src_ty` to `dst_ty` preserves it,
+    /// for the enabled checks, with the description of their violation.
+    fn conversion_conditions(
+        &mut self,
+        value: Expr,
+        src_ty: Ty<'tcx>,
+        dst_ty: Ty<'tcx>,
+    ) -> Vec<(&'static str, Expr)> {
+        let src_t = self.codegen_ty(src_ty);
+        let dst_t = self.codegen_ty(dst_ty);
+        let src_bits = self.layout_of(src_ty).size.bits();
+        let dst_bits = self.layout_of(dst_ty).size.bits();
+        let src_signed = src_ty.is_signed();
+        let dst_signed = dst_ty.is_signed();
+        let mut conditions = vec![];
+        if src_ty.is_integral() && dst_ty.is_integral() {
+            if self.conversion_check_enabled(ConversionCheck::Truncation) && dst_bits < src_bits {
+                // The value must be in the range of the destination type.
+                let (dst_min, dst_max) = int_range(dst_bits, dst_signed);
+                let mut in_range = value.clone().le(Expr::int_constant(dst_max, src_t.clone()));
+                if src_signed {
+                    in_range =
+                        in_range.and(value.clone().ge(Expr::int_constant(dst_min, src_t.clone())));
+                }
+                conditions.push(("truncates the value", in_range));
+            }
+            if self.conversion_check_enabled(ConversionCheck::SignChange) {
+                let result = value.clone().cast_to(dst_t.clone());
+                let same_sign = match (src_signed, dst_signed) {
+                    (true, true) => Some(value.lt(src_t.zero()).eq(result.lt(dst_t.zero()))),
+                    (true, false) => Some(value.ge(src_t.zero())),
+                    (false, true) => Some(result.ge(dst_t.zero())),
+                    (false, false) => None,
+                };
+                if let Some(same_sign) = same_sign {
+                    conditions.push(("changes the sign of the value", same_sign));
+                }
+            }
+        } else if src_ty.is_floating_point() && dst_ty.is_integral() {
+            if self.conversion_check_enabled(ConversionCheck::FloatToInt) {
+                // The integer part of the value must be in the range of the destination type,
+                // i.e., `MIN - 1 < value < MAX + 1`. Both comparisons fail if it's NaN.
+                let (dst_min, dst_max) = int_range(dst_bits, dst_signed);
+                let above_min = if !dst_signed {
+                    value.clone().gt(float_constant(src_ty, -1.0))
+                } else if dst_bits <= mantissa_digits(src_ty) {
+                    value.clone().gt(float_constant(src_ty, dst_min as f64 - 1.0))
+                } else {
+                    // `MIN - 1` isn't representable, and neither is any value between it and
+                    // `MIN`, which is a power of two.
+                    value.clone().ge(float_constant(src_ty, dst_min as f64))
+                };
+                let below_max = value.lt(float_constant(src_ty, dst_max as f64 + 1.0));
+                conditions.push(("saturates the value", above_min.and(below_max)));
+            }
+        } else if src_ty.is_integral() && dst_ty.is_floating_point() {
+            let magnitude_bits = if src_signed { src_bits - 1 } else { src_bits };
+            if self.conversion_check_enabled(ConversionCheck::IntToFloat)
+                && magnitude_bits > mantissa_digits(dst_ty)
+            {
+                // The value must be converted back to itself. The result can only be out of the
+                // range of the source type if it was rounded up to `MAX + 1`.
+                let (_, src_max) = int_range(src_bits, src_signed);
+                let result = value.clone().cast_to(dst_t);
+                let exact = result
+                    .clone()
+                    .lt(float_constant(dst_ty, src_max as f64 + 1.0))
+                    .and(result.cast_to(src_t).eq(value));
+                conditions.push(("rounds the value", exact));
+            }
+        }
+        conditions
+    }
+}
+
+/// The minimum and maximum values of an integer type with the given width and signedness.
+fn int_range(bits: u64, signed: bool) -> (i128, u128) {
+    if signed {
+        (-1i128 << (bits - 1), (1u128 << (bits - 1)) - 1)
+    } else {
+        (0, u128::MAX >> (128 - bits))
+    }
+}
+
+/// The number of significant binary digits of a float type.
+fn mantissa_digits(float_ty: Ty) -> u64 {
+    match float_ty.kind() {
+        ty::Float(FloatTy::F32) => f32::MANTISSA_DIGITS.into(),
+        ty::Float(FloatTy::F64) => f64::MANTISSA_DIGITS.into(),
+        _ => unreachable!("expected a float type, found {float_ty:?}"),
+    }
+}
+
+/// A constant of the given float type, rounded to the nearest representable value.
+fn float_constant(float_ty: Ty, value: f64) -> Expr {
+    match float_ty.kind() {
+        ty::Float(FloatTy::F32) => Expr::float_constant(value as f32),
+        ty::Float(FloatTy::F64) => Expr::double_constant(value),
+        _ => unreachable!("expected a float type, found {float_ty:?}"),
+    }
+}
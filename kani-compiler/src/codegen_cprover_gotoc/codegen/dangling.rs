@@ -0,0 +1,115 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module generates the checks that a raw pointer that is dereferenced doesn't point to a
+//! local whose storage is dead, i.e., to a local that went out of scope.
+//!
+//! `StorageDead` is lowered to a CBMC `DEAD`, so CBMC's pointer checks already fail on such a
+//! dereference, but with the same "dead object" description as for other invalid pointers. To
+//! report it as a dangling stack pointer, we track a single dead local in the ghost variable
+//! `__kani_dead_local`:
+//!  * When the storage of a local whose address is taken becomes dead, we nondeterministically
+//!    choose whether its address is the one we track.
+//!  * When the storage of the tracked local becomes live again, we stop tracking it.
+//!  * Before dereferencing a raw pointer, we assert that it doesn't point to the tracked local.
+//!
+//! Since the choice is nondeterministic, the check fails if any dead local can be dereferenced.
+
+use super::alignment::AccessedPlaces;
+use super::typ::TypeExt;
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use rustc_middle::mir;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{Place, ProjectionElem, Statement, Terminator};
+
+/// The ghost variable with the address of the dead local that we track, or null.
+const DEAD_LOCAL_VAR: &str = "__kani_dead_local";
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Checks the raw pointers dereferenced by a statement before it runs.
+    pub fn codegen_dangling_statement(&mut self, stmt: &Statement<'tcx>, code: Stmt) -> Stmt {
+        let mut visitor = AccessedPlaces { places: vec![] };
+        visitor.visit_statement(stmt, mir::Location::START);
+        self.codegen_dangling_derefs(visitor.places, code)
+    }
+
+    /// Checks the raw pointers dereferenced by a terminator before it runs.
+    pub fn codegen_dangling_terminator(&mut self, term: &Terminator<'tcx>, code: Stmt) -> Stmt {
+        let mut visitor = AccessedPlaces { places: vec![] };
+        visitor.visit_terminator(term, mir::Location::START);
+        self.codegen_dangling_derefs(visitor.places, code)
+    }
+
+    /// Nondeterministically tracks `var`, a local whose storage becomes dead.
+    pub fn codegen_mark_dead(&mut self, var: Expr, loc: Location) -> Stmt {
+        let dead_local = self.dead_local_var(loc);
+        let addr = var.address_of().cast_to(Type::void_pointer());
+        let value = Type::bool().nondet().ternary(addr, dead_local.clone());
+        dead_local.assign(value, loc)
+    }
+
+    /// Stops tracking `var`, a local whose storage becomes live, if it's the tracked local.
+    pub fn codegen_mark_live(&mut self, var: Expr, loc: Location) -> Stmt {
+        let dead_local = self.dead_local_var(loc);
+        let addr = var.address_of().cast_to(Type::void_pointer());
+        let value = dead_local
+            .clone()
+            .same_object(addr)
+            .ternary(Type::void_pointer().null(), dead_local.clone());
+        dead_local.assign(value, loc)
+    }
+
+    fn dead_local_var(&mut self, loc: Location) -> Expr {
+        self.ensure_global_var(DEAD_LOCAL_VAR, false, Type::void_pointer(), loc, |_, _| None)
+    }
+
+    fn codegen_dangling_derefs(&mut self, places: Vec<Place<'tcx>>, code: Stmt) -> Stmt {
+        let loc = *code.location();
+        let mut stmts = vec![];
+        for place in places {
+            for (base, elem) in place.iter_projections() {
+                if elem != ProjectionElem::Deref {
+                    continue;
+                }
+                let base = Place {
+                    local: base.local,
+                    projection: self.tcx.intern_place_elems(base.projection),
+                };
+                if let Some(check) = self.codegen_dangling_deref(&base, loc) {
+                    stmts.push(check);
+                }
+            }
+        }
+        if stmts.is_empty() {
+            return code;
+        }
+        stmts.push(code);
+        Stmt::block(stmts, loc)
+    }
+
+    /// Asserts that the pointer stored in `ptr_place`, if it's a raw pointer, doesn't point to
+    /// the tracked dead local.
+    fn codegen_dangling_deref(&mut self, ptr_place: &Place<'tcx>, loc: Location) -> Option<Stmt> {
+        if !self.place_ty(ptr_place).is_unsafe_ptr() {
+            return None;
+        }
+        let ptr = self.codegen_place(ptr_place).ok()?.goto_expr;
+        let data = if ptr.typ().is_rust_fat_ptr(&self.symbol_table) {
+            ptr.member("data", &self.symbol_table)
+        } else {
+            ptr
+        };
+        let dead_local = self.dead_local_var(loc);
+        // `same_object` holds for two null pointers, and no local is tracked in that case.
+        let untracked = dead_local.clone().eq(Type::void_pointer().null());
+        let cond = untracked.or(data.cast_to(Type::void_pointer()).same_object(dead_local).not());
+        Some(self.codegen_assert_assume(
+            cond,
+            PropertyClass::DanglingStackPointer,
+            "dereference failure: dangling stack pointer",
+            loc,
+        ))
+    }
+}
@@ -81,6 +81,20 @@ pub fn codegen_function(&mut self, instance: Instance<'tcx>) {
             self.print_instance(instance, mir);
             self.codegen_function_prelude();
             self.codegen_declare_variables();
+            if self.aliasing_checks_enabled() {
+                let prelude = self.codegen_aliasing_prelude();
+                self.current_fn_mut().push_onto_block(prelude);
+            }
+            if self.uninit_checks_enabled() {
+                let prelude = self.codegen_uninit_prelude();
+                self.current_fn_mut().push_onto_block(prelude);
+            }
+            if let Some(prelude) = self.codegen_alloc_failure_prelude() {
+                self.current_fn_mut().push_onto_block(prelude);
+            }
+            if let Some(prelude) = self.codegen_leak_check_prelude() {
+                self.current_fn_mut().push_onto_block(prelude);
+            }
 
             mir.basic_blocks.iter_enumerated().for_each(|(bb, bbd)| self.codegen_block(bb, bbd));
 
@@ -359,15 +373,22 @@ fn create_proof_harness(&mut self, other_attributes: Vec<(String, &Attribute)>)
         let mut harness = self.default_kanitool_proof();
         for attr in other_attributes.iter() {
             match attr.0.as_str() {
-                "stub" => {
+                "stub" | "unstub" => {
                     if !self.queries.get_stubbing_enabled() {
                         self.tcx.sess.span_warn(
                             attr.1.span,
-                            "Stubbing is not enabled; attribute `kani::stub` will be ignored",
+                            format!(
+                                "Stubbing is not enabled; attribute `kani::{}` will be ignored",
+                                attr.0
+                            ),
                         )
                     }
                 }
                 "unwind" => self.handle_kanitool_unwind(attr.1, &mut harness),
+                // Handled when the conversions of the function are instrumented.
+                "no_conversion_checks" => {}
+                // Handled when the harness is codegen'd.
+                "nondet_alloc_failure" => {}
                 _ => {
                     self.tcx.sess.span_err(
                         attr.1.span,
@@ -629,7 +629,7 @@ macro_rules! unstable_codegen {
             "sqrtf32" => unstable_codegen!(codegen_simple_intrinsic!(Sqrtf)),
             "sqrtf64" => unstable_codegen!(codegen_simple_intrinsic!(Sqrt)),
             "sub_with_overflow" => codegen_op_with_overflow!(sub_overflow_result),
-            "transmute" => self.codegen_intrinsic_transmute(fargs, ret_ty, p),
+            "transmute" => self.codegen_intrinsic_transmute(fargs, ret_ty, p, loc),
             "truncf32" => codegen_simple_intrinsic!(Truncf),
             "truncf64" => codegen_simple_intrinsic!(Trunc),
             "try" => self.codegen_unimplemented_stmt(
@@ -971,6 +971,8 @@ pub fn codegen_copy(
 
         // Build the call to the copy built-in (`memmove` or `memcpy`)
         let copy_builtin = if is_non_overlapping { BuiltinFn::Memcpy } else { BuiltinFn::Memmove };
+        let uninit_copy =
+            self.codegen_uninit_copy(dst.clone(), src.clone(), count_bytes.clone(), loc);
         let copy_call = copy_builtin.call(vec![dst.clone(), src, count_bytes.clone()], loc);
 
         // The C implementations of `memmove` and `memcpy` do not allow an
@@ -986,7 +988,10 @@ pub fn codegen_copy(
         } else {
             copy_if_nontrivial.as_stmt(loc)
         };
-        Stmt::block(vec![src_align_check, dst_align_check, overflow_check, copy_expr], loc)
+        Stmt::block(
+            vec![src_align_check, dst_align_check, overflow_check, copy_expr, uninit_copy],
+            loc,
+        )
     }
 
     // In some contexts (e.g., compilation-time evaluation),
@@ -1154,19 +1159,23 @@ fn codegen_ptr_offset_from_expr(
     /// }
     ///
     /// Note(std): An earlier attempt to add alignment checks for both the argument and result types
-    /// had catastrophic results in the regression. Hence, we don't perform any additional checks
-    /// and only encode the transmute operation here.
+    /// had catastrophic results in the regression. Hence, we don't perform any alignment checks.
+    /// We only check the validity invariant of the result when transmuting in user code.
     fn codegen_intrinsic_transmute(
         &mut self,
         mut fargs: Vec<Expr>,
         ret_ty: Ty<'tcx>,
         p: &Place<'tcx>,
+        loc: Location,
     ) -> Stmt {
         assert!(fargs.len() == 1, "transmute had unexpected arguments {fargs:?}");
         let arg = fargs.remove(0);
         let cbmc_ret_ty = self.codegen_ty(ret_ty);
         let expr = arg.transmute_to(cbmc_ret_ty, &self.symbol_table);
-        self.codegen_expr_to_place(p, expr)
+        Stmt::block(
+            vec![self.codegen_expr_to_place(p, expr), self.codegen_validity_transmute(p, loc)],
+            loc,
+        )
     }
 
     // `raw_eq` determines whether the raw bytes of two values are equal.
@@ -1721,8 +1730,9 @@ fn codegen_write_bytes(
             loc,
         );
 
+        let uninit_mark = self.codegen_uninit_mark_init(dst.clone(), count_bytes.clone(), loc);
         let memset_call = BuiltinFn::Memset.call(vec![dst, val, count_bytes], loc);
-        Stmt::block(vec![align_check, overflow_check, memset_call.as_stmt(loc)], loc)
+        Stmt::block(vec![align_check, overflow_check, memset_call.as_stmt(loc), uninit_mark], loc)
     }
 
     /// Computes (multiplies) the equivalent of a memory-related number (e.g., an offset) in bytes.
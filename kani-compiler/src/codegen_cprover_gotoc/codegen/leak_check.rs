@@ -0,0 +1,145 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module instruments the program for `--leak-check`, which checks that the memory allocated
+//! during the execution of a harness is freed when it returns (e.g., it detects the memory leaked
+//! by `Box::leak`, `mem::forget` or `Rc` cycles).
+//!
+//! The allocation functions of `kani_lib.c` track a nondeterministically chosen allocation, along
+//! with the current allocation site. Here we insert the calls that drive it:
+//!  * Harnesses call `__KANI_leak_check_enable` on entry, and check that the tracked allocation
+//!    was freed before they return.
+//!  * Every call from user code (i.e., the local crate) into the standard library is numbered and
+//!    sets the current allocation site while it runs. So the memory allocated by, e.g.,
+//!    `Box::new` or `Vec::with_capacity` is attributed to that call, not to `__rust_alloc`.
+//!  * Once the crate has been codegen'd, we define `__KANI_leak_check`, which has one check per
+//!    allocation site, located at that site.
+//!
+//! We don't use CBMC's `--memory-leak-check`: it checks for leaks once `__CPROVER__start` returns,
+//! and reports them at that point, so there would be no way to tell which Rust allocation leaked.
+
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::has_kanitool_attribute;
+use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
+use kani_queries::UserInput;
+use rustc_middle::ty::Instance;
+use rustc_span::Span;
+
+/// The function that checks the allocation sites of user code for leaks.
+const LEAK_CHECK_FN: &str = "__KANI_leak_check";
+
+/// The allocation site of the memory that isn't allocated during a call from user code.
+const UNKNOWN_SITE: usize = 0;
+
+impl<'tcx> GotocCtx<'tcx> {
+    pub fn leak_check_enabled(&self) -> bool {
+        self.queries.get_leak_check()
+    }
+
+    /// Enables the tracking of allocations on entry of the current function, if it's a harness.
+    pub fn codegen_leak_check_prelude(&mut self) -> Option<Stmt> {
+        if !self.leak_check_enabled() || !self.is_current_fn_harness() {
+            return None;
+        }
+        let loc = self.codegen_span(&self.current_fn().mir().span);
+        Some(self.codegen_leak_fn("__KANI_leak_check_enable", vec![], Type::empty()).as_stmt(loc))
+    }
+
+    /// Checks that the memory allocated by the current function, if it's a harness, was freed
+    /// before it returns with `ret`.
+    pub fn codegen_leak_check_exit(&mut self, ret: Stmt, loc: Location) -> Stmt {
+        if !self.leak_check_enabled() || !self.is_current_fn_harness() {
+            return ret;
+        }
+        let unknown_site = Expr::int_constant(UNKNOWN_SITE, Type::size_t());
+        let leaked = self.codegen_leak_fn("__KANI_leaked", vec![unknown_site], Type::bool());
+        let check = self.codegen_assert(
+            leaked.not(),
+            PropertyClass::LeakCheck,
+            "dynamically allocated memory never freed",
+            loc,
+        );
+        let check_sites = self.codegen_leak_fn(LEAK_CHECK_FN, vec![], Type::empty());
+        Stmt::block(vec![check, check_sites.as_stmt(loc), ret], loc)
+    }
+
+    /// Sets the allocation site during the code of a call to `callee`, if the current function
+    /// is in user code and `callee` belongs to the standard library. Calls without a span are
+    /// attributed to the current function.
+    pub fn codegen_leak_site(
+        &mut self,
+        callee: Instance<'tcx>,
+        call: Vec<Stmt>,
+        span: Option<Span>,
+        loc: Location,
+    ) -> Vec<Stmt> {
+        let callee_crate = self.tcx.crate_name(callee.def_id().krate);
+        if !self.leak_check_enabled()
+            || !self.current_fn().instance().def_id().is_local()
+            || !matches!(callee_crate.as_str(), "core" | "alloc" | "std")
+        {
+            return call;
+        }
+        // Leaks are reported at the call site, or at the invocation of the macro that expands to
+        // it (e.g., `vec!`).
+        let span = span.unwrap_or(self.current_fn().mir().span);
+        let site_loc = self.codegen_caller_span(&Some(span));
+        let (index, _) = self.leak_sites.insert_full(span, site_loc);
+        let site = Expr::int_constant(index + 1, Type::size_t());
+        let enter = self.codegen_leak_fn("__KANI_leak_site_enter", vec![site], Type::size_t());
+        let (previous, decl) = self.decl_temp_variable(Type::size_t(), Some(enter), loc);
+        let exit = self.codegen_leak_fn("__KANI_leak_site_exit", vec![previous], Type::empty());
+        let mut stmts = vec![decl];
+        stmts.extend(call);
+        stmts.push(exit.as_stmt(loc));
+        stmts
+    }
+
+    /// Tracks an allocation that isn't performed by the allocation functions of `kani_lib.c`.
+    pub fn codegen_leak_track(&mut self, ptr: Expr, loc: Location) -> Stmt {
+        let ptr = ptr.cast_to(Type::void_pointer());
+        self.codegen_leak_fn("__KANI_leak_track", vec![ptr], Type::empty()).as_stmt(loc)
+    }
+
+    /// Defines `__KANI_leak_check`, which checks that the tracked allocation wasn't allocated
+    /// at any of the allocation sites of the crate. This must run after all the functions have
+    /// been codegen'd.
+    pub fn codegen_leak_check_fn(&mut self) {
+        if !self.symbol_table.contains(LEAK_CHECK_FN.into()) {
+            return;
+        }
+        let checks = (0..self.leak_sites.len())
+            .map(|index| {
+                let (_, loc) = self.leak_sites.get_index(index).unwrap();
+                let loc = *loc;
+                let site = Expr::int_constant(index + 1, Type::size_t());
+                let leaked = self.codegen_leak_fn("__KANI_leaked", vec![site], Type::bool());
+                self.codegen_assert(
+                    leaked.not(),
+                    PropertyClass::LeakCheck,
+                    "dynamically allocated memory never freed",
+                    loc,
+                )
+            })
+            .collect();
+        self.symbol_table.update_fn_declaration_with_definition(
+            LEAK_CHECK_FN,
+            Stmt::block(checks, Location::none()),
+        );
+    }
+
+    pub(super) fn is_current_fn_harness(&self) -> bool {
+        let def_id = self.current_fn().instance().def_id();
+        has_kanitool_attribute(self.tcx.get_attrs_unchecked(def_id), "proof")
+    }
+
+    fn codegen_leak_fn(&mut self, function: &str, args: Vec<Expr>, ret: Type) -> Expr {
+        let param_types = args.iter().map(|arg| arg.typ().clone()).collect();
+        let typ = Type::code_with_unnamed_parameters(param_types, ret);
+        let func = self
+            .ensure(function, |_, name| Symbol::function(name, typ, None, name, Location::none()))
+            .to_expr();
+        func.call(args)
+    }
+}
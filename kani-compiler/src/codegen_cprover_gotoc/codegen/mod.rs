@@ -4,19 +4,29 @@
 //! This module does that actual translation of MIR constructs to goto constructs.
 //! Each subfile is named for the MIR construct it translates.
 
+mod aliasing;
+mod alignment;
+mod alloc_failure;
 mod assert;
 mod block;
+mod conversion;
+mod dangling;
 mod function;
 mod intrinsic;
+mod leak_check;
 mod operand;
 mod place;
 mod rvalue;
 mod span;
 mod statement;
 mod static_var;
+mod uninit;
+mod vacuity;
+mod validity;
 
 // Visible for all codegen module.
 pub(super) mod typ;
 
+pub use alloc_failure::nondet_alloc_failure_in_crate;
 pub use assert::PropertyClass;
 pub use typ::TypeExt;
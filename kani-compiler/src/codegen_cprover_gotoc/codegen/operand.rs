@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use crate::codegen_cprover_gotoc::utils::slice_fat_ptr;
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::stubbing::stub_instance;
 use crate::unwrap_or_return_codegen_unimplemented;
 use cbmc::btree_string_map;
 use cbmc::goto_program::{DatatypeComponent, Expr, ExprValue, Location, Stmt, Symbol, Type};
@@ -726,6 +727,7 @@ pub fn codegen_fndef(
     ) -> Expr {
         let instance =
             Instance::resolve(self.tcx, ty::ParamEnv::reveal_all(), d, substs).unwrap().unwrap();
+        let instance = stub_instance(self.tcx, instance, None);
         self.codegen_fn_item(instance, span)
     }
 
@@ -336,7 +336,7 @@ fn codegen_local_fndef(&mut self, ty: ty::Ty<'tcx>) -> Option<Expr> {
     }
 
     /// Codegen for a local
-    fn codegen_local(&mut self, l: Local) -> Expr {
+    pub fn codegen_local(&mut self, l: Local) -> Expr {
         // Check if the local is a function definition (see comment above)
         if let Some(fn_def) = self.codegen_local_fndef(self.local_ty(l)) {
             return fn_def;
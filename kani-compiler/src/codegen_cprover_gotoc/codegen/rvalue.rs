@@ -8,6 +8,7 @@
 use crate::kani_middle::coercion::{
     extract_unsize_casting, CoerceUnsizedInfo, CoerceUnsizedIterator, CoercionBase,
 };
+use crate::kani_middle::stubbing::stub_instance;
 use crate::{emit_concurrency_warning, unwrap_or_return_codegen_unimplemented};
 use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
 use cbmc::MachineModel;
@@ -652,6 +653,7 @@ fn codegen_pointer_cast(
                         Instance::resolve(self.tcx, ty::ParamEnv::reveal_all(), *def_id, substs)
                             .unwrap()
                             .unwrap();
+                    let instance = stub_instance(self.tcx, instance, None);
                     // We need to handle this case in a special way because `codegen_operand` compiles FnDefs to dummy structs.
                     // (cf. the function documentation)
                     self.codegen_func_expr(instance, None).address_of()
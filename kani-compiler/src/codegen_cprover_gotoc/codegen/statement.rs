@@ -4,20 +4,21 @@
 use super::typ::FN_RETURN_VOID_VAR_NAME;
 use super::PropertyClass;
 use crate::codegen_cprover_gotoc::{GotocCtx, VtableCtx};
+use crate::kani_middle::stubbing::stub_instance;
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
 use cbmc::goto_program::{Expr, Location, Stmt, Type};
 use kani_queries::UserInput;
 use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
 use rustc_middle::mir::{
-    AssertKind, BasicBlock, NonDivergingIntrinsic, Operand, Place, Statement, StatementKind,
+    AssertKind, BasicBlock, Local, NonDivergingIntrinsic, Operand, Place, Statement, StatementKind,
     SwitchTargets, Terminator, TerminatorKind,
 };
 use rustc_middle::ty;
 use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::{Instance, InstanceDef, Ty};
 use rustc_span::Span;
-use rustc_target::abi::{FieldsShape, Primitive, TagEncoding, Variants};
+use rustc_target::abi::{FieldsShape, Primitive, TagEncoding, VariantIdx, Variants};
 use tracing::{debug, info_span, trace};
 
 impl<'tcx> GotocCtx<'tcx> {
@@ -59,68 +60,13 @@ pub fn codegen_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
             StatementKind::SetDiscriminant { place, variant_index } => {
                 // this requires place points to an enum type.
                 let pt = self.place_ty(place);
-                let layout = self.layout_of(pt);
-                match &layout.variants {
-                    Variants::Single { .. } => Stmt::skip(location),
-                    Variants::Multiple { tag, tag_encoding, .. } => match tag_encoding {
-                        TagEncoding::Direct => {
-                            let discr =
-                                pt.discriminant_for_variant(self.tcx, *variant_index).unwrap();
-                            let discr_t = self.codegen_enum_discr_typ(pt);
-                            // The constant created below may not fit into the type.
-                            // https://github.com/model-checking/kani/issues/996
-                            //
-                            // It doesn't matter if the type comes from `self.codegen_enum_discr_typ(pt)`
-                            // or `discr.ty`. It looks like something is wrong with `discriminat_for_variant`
-                            // because when it tries to codegen `std::cmp::Ordering` (which should produce
-                            // discriminant values -1, 0 and 1) it produces values 255, 0 and 1 with i8 types:
-                            //
-                            // debug!("DISCRIMINANT - val:{:?} ty:{:?}", discr.val, discr.ty);
-                            // DISCRIMINANT - val:255 ty:i8
-                            // DISCRIMINANT - val:0 ty:i8
-                            // DISCRIMINANT - val:1 ty:i8
-                            let discr = Expr::int_constant(discr.val, self.codegen_ty(discr_t));
-                            let place_goto_expr = unwrap_or_return_codegen_unimplemented_stmt!(
-                                self,
-                                self.codegen_place(place)
-                            )
-                            .goto_expr;
-                            self.codegen_discriminant_field(place_goto_expr, pt)
-                                .assign(discr, location)
-                        }
-                        TagEncoding::Niche { untagged_variant, niche_variants, niche_start } => {
-                            if untagged_variant != variant_index {
-                                let offset = match &layout.fields {
-                                    FieldsShape::Arbitrary { offsets, .. } => offsets[0],
-                                    _ => unreachable!("niche encoding must have arbitrary fields"),
-                                };
-                                let discr_ty = self.codegen_enum_discr_typ(pt);
-                                let discr_ty = self.codegen_ty(discr_ty);
-                                let niche_value =
-                                    variant_index.as_u32() - niche_variants.start().as_u32();
-                                let niche_value = (niche_value as u128).wrapping_add(*niche_start);
-                                let value =
-                                    if niche_value == 0 && tag.primitive() == Primitive::Pointer {
-                                        discr_ty.null()
-                                    } else {
-                                        Expr::int_constant(niche_value, discr_ty.clone())
-                                    };
-                                let place = unwrap_or_return_codegen_unimplemented_stmt!(
-                                    self,
-                                    self.codegen_place(place)
-                                )
-                                .goto_expr;
-                                self.codegen_get_niche(place, offset, discr_ty)
-                                    .assign(value, location)
-                            } else {
-                                Stmt::skip(location)
-                            }
-                        }
-                    },
-                }
+                let place_goto_expr =
+                    unwrap_or_return_codegen_unimplemented_stmt!(self, self.codegen_place(place))
+                        .goto_expr;
+                self.codegen_set_discriminant(pt, place_goto_expr, *variant_index, location)
             }
-            StatementKind::StorageLive(_) => Stmt::skip(location), // TODO: fix me
-            StatementKind::StorageDead(_) => Stmt::skip(location), // TODO: fix me
+            StatementKind::StorageLive(local) => self.codegen_storage_live(*local, location),
+            StatementKind::StorageDead(local) => self.codegen_storage_dead(*local, location),
             StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(
                 mir::CopyNonOverlapping { ref src, ref dst, ref count },
             )) => {
@@ -152,6 +98,61 @@ pub fn codegen_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
         .with_location(location)
     }
 
+    /// Sets the discriminant of `dest_expr`, a value of the enum type `pt`, to the one of
+    /// `variant_index`.
+    pub fn codegen_set_discriminant(
+        &mut self,
+        pt: Ty<'tcx>,
+        dest_expr: Expr,
+        variant_index: VariantIdx,
+        location: Location,
+    ) -> Stmt {
+        let layout = self.layout_of(pt);
+        match &layout.variants {
+            Variants::Single { .. } => Stmt::skip(location),
+            Variants::Multiple { tag, tag_encoding, .. } => match tag_encoding {
+                TagEncoding::Direct => {
+                    let discr = pt.discriminant_for_variant(self.tcx, variant_index).unwrap();
+                    let discr_t = self.codegen_enum_discr_typ(pt);
+                    // The constant created below may not fit into the type.
+                    // https://github.com/model-checking/kani/issues/996
+                    //
+                    // It doesn't matter if the type comes from `self.codegen_enum_discr_typ(pt)`
+                    // or `discr.ty`. It looks like something is wrong with `discriminat_for_variant`
+                    // because when it tries to codegen `std::cmp::Ordering` (which should produce
+                    // discriminant values -1, 0 and 1) it produces values 255, 0 and 1 with i8 types:
+                    //
+                    // debug!("DISCRIMINANT - val:{:?} ty:{:?}", discr.val, discr.ty);
+                    // DISCRIMINANT - val:255 ty:i8
+                    // DISCRIMINANT - val:0 ty:i8
+                    // DISCRIMINANT - val:1 ty:i8
+                    let discr = Expr::int_constant(discr.val, self.codegen_ty(discr_t));
+                    self.codegen_discriminant_field(dest_expr, pt).assign(discr, location)
+                }
+                TagEncoding::Niche { untagged_variant, niche_variants, niche_start } => {
+                    if *untagged_variant != variant_index {
+                        let offset = match &layout.fields {
+                            FieldsShape::Arbitrary { offsets, .. } => offsets[0],
+                            _ => unreachable!("niche encoding must have arbitrary fields"),
+                        };
+                        let discr_ty = self.codegen_enum_discr_typ(pt);
+                        let discr_ty = self.codegen_ty(discr_ty);
+                        let niche_value = variant_index.as_u32() - niche_variants.start().as_u32();
+                        let niche_value = (niche_value as u128).wrapping_add(*niche_start);
+                        let value = if niche_value == 0 && tag.primitive() == Primitive::Pointer {
+                            discr_ty.null()
+                        } else {
+                            Expr::int_constant(niche_value, discr_ty.clone())
+                        };
+                        self.codegen_get_niche(dest_expr, offset, discr_ty).assign(value, location)
+                    } else {
+                        Stmt::skip(location)
+                    }
+                }
+            },
+        }
+    }
+
     /// Generate Goto-c for MIR [Terminator] statements.
     /// Many kinds of seemingly ordinary statements in Rust are "terminators" (i.e. the sort of statement that _ends_ a basic block)
     /// because of the need for unwinding/drop. For instance, function calls.
@@ -186,7 +187,9 @@ pub fn codegen_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
             TerminatorKind::Return => {
                 let rty = self.current_fn().sig().skip_binder().output();
                 if rty.is_unit() {
-                    self.codegen_ret_unit()
+                    let ret = self.codegen_ret_unit();
+                    let ret = self.codegen_leak_check_exit(ret, loc);
+                    self.codegen_vacuity_exit(ret, loc)
                 } else {
                     let p = Place::from(mir::RETURN_PLACE);
                     let v =
@@ -267,6 +270,37 @@ pub fn codegen_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
         }
     }
 
+    /// A local gets a fresh (uninitialized) object when its storage becomes live. This is
+    /// lowered to a CBMC `DECL`.
+    fn codegen_storage_live(&mut self, local: Local, location: Location) -> Stmt {
+        let var = self.codegen_local(local);
+        if !var.is_symbol() {
+            // Function definitions are constants, not objects.
+            return Stmt::skip(location);
+        }
+        let init = self.codegen_default_initializer(&var);
+        let decl = Stmt::decl(var.clone(), init, location);
+        if !self.current_fn().is_borrowed(local) {
+            return decl;
+        }
+        Stmt::block(vec![self.codegen_mark_live(var, location), decl], location)
+    }
+
+    /// The object of a local is deallocated when its storage becomes dead. This is lowered to a
+    /// CBMC `DEAD`, so dereferencing a pointer to the local afterwards fails CBMC's pointer
+    /// checks, and Kani's dangling stack pointer check (see `dangling.rs`).
+    fn codegen_storage_dead(&mut self, local: Local, location: Location) -> Stmt {
+        let var = self.codegen_local(local);
+        if !var.is_symbol() {
+            return Stmt::skip(location);
+        }
+        let dead = Stmt::dead(var.clone(), location);
+        if !self.current_fn().is_borrowed(local) {
+            return dead;
+        }
+        Stmt::block(vec![self.codegen_mark_dead(var, location), dead], location)
+    }
+
     /// From rustc doc: "This writes `uninit` bytes to the entire place."
     /// Our model of GotoC has a similar statement, which is later lowered
     /// to assigning a Nondet in CBMC, with a comment specifying that it
@@ -514,6 +548,7 @@ fn codegen_funcall(
                     Instance::resolve(self.tcx, ty::ParamEnv::reveal_all(), *defid, subst)
                         .unwrap()
                         .unwrap();
+                let instance = stub_instance(self.tcx, instance, None);
 
                 if self.ty_needs_closure_untupled(funct) {
                     self.codegen_untuple_closure_args(instance, &mut fargs, args.last());
@@ -523,6 +558,8 @@ fn codegen_funcall(
                     return hk.handle(self, instance, fargs, *destination, *target, Some(span));
                 }
 
+                let precondition = self.codegen_safety_preconditions(instance, &fargs, args, loc);
+                let align_check = self.codegen_alignment_call_args(instance, &fargs, args, loc);
                 let mut stmts: Vec<Stmt> = match instance.def {
                     // Here an empty drop glue is invoked; we just ignore it.
                     InstanceDef::DropGlue(_, None) => {
@@ -552,12 +589,18 @@ fn codegen_funcall(
                         // We need to handle FnDef items in a special way because `codegen_operand` compiles them to dummy structs.
                         // (cf. the function documentation)
                         let func_exp = self.codegen_func_expr(instance, None);
-                        vec![
+                        let call = vec![
                             self.codegen_expr_to_place(destination, func_exp.call(fargs))
                                 .with_location(loc),
-                        ]
+                        ];
+                        let call = self.codegen_leak_site(instance, call, Some(span), loc);
+                        let mut stmts = vec![precondition, align_check];
+                        stmts.extend(self.codegen_alloc_failure_scope(instance, call, loc));
+                        stmts
                     }
                 };
+                stmts.push(self.codegen_validity_call_result(instance, destination, loc));
+                stmts.push(self.codegen_uninit_receive_return(destination, loc));
                 stmts.push(self.codegen_end_call(target.as_ref(), loc));
                 Stmt::block(stmts, loc)
             }
@@ -569,6 +612,7 @@ fn codegen_funcall(
                     vec![
                         self.codegen_expr_to_place(destination, func_expr.call(fargs))
                             .with_location(loc),
+                        self.codegen_uninit_receive_return(destination, loc),
                         Stmt::goto(self.current_fn().find_label(&target.unwrap()), loc),
                     ],
                     loc,
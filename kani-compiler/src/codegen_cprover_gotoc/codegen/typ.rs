@@ -1,6 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::stubbing::stub_instance;
 use cbmc::btree_map;
 use cbmc::goto_program::{DatatypeComponent, Expr, Location, Parameter, Symbol, SymbolTable, Type};
 use cbmc::utils::aggr_tag;
@@ -778,6 +779,7 @@ fn codegen_ty_inner(&mut self, ty: Ty<'tcx>) -> Type {
                     Instance::resolve(self.tcx, ty::ParamEnv::reveal_all(), *def_id, substs)
                         .unwrap()
                         .unwrap();
+                let instance = stub_instance(self.tcx, instance, None);
                 self.codegen_fndef_type(instance)
             }
             ty::FnPtr(sig) => self.codegen_function_sig(*sig).to_pointer(),
@@ -0,0 +1,384 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module instruments the program for `--uninit-checks`, which detect reads of
+//! uninitialized memory.
+//!
+//! The shadow initialization state of the memory is kept by the `__KANI_uninit_*` functions of
+//! `kani_lib.c`, which track the state of a single byte chosen nondeterministically. Here we
+//! insert the calls that update the state:
+//!  * Harnesses call `__KANI_uninit_enable` on entry.
+//!  * The bytes of a place become uninitialized on `Deinit` and `StorageLive`. Allocations are
+//!    handled by `kani_lib.c`, once the checks are enabled.
+//!  * The bytes of a place become initialized when it's assigned, unless it's assigned a copy of
+//!    another place, whose state is copied instead. The state of arguments and return values is
+//!    passed between caller and callee in the same way.
+//!  * Every typed read (i.e., `Copy` and `Move` operands, and `CopyForDeref`) asserts that the bytes that hold data are
+//!    initialized. We don't check the padding bytes, the bytes of unions and enums, or arrays of
+//!    non-primitive types.
+
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
+use kani_queries::UserInput;
+use rustc_middle::mir;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{
+    Local, Operand, Place, ProjectionElem, Rvalue, Statement, StatementKind, Terminator,
+    TerminatorKind,
+};
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::{self, ParamEnv, Ty};
+
+/// The slot used to pass the state of a return value (arguments use the index of their local).
+const RETURN_SLOT: u64 = 0;
+
+/// Collects the places read by the `Copy` and `Move` operands (and `CopyForDeref` rvalues) of a
+/// statement or terminator.
+pub(super) struct OperandPlaces<'tcx> {
+    pub(super) places: Vec<Place<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for OperandPlaces<'tcx> {
+    fn visit_operand(&mut self, operand: &Operand<'tcx>, _location: mir::Location) {
+        if let Operand::Copy(place) | Operand::Move(place) = operand {
+            self.places.push(*place);
+        }
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: mir::Location) {
+        if let Rvalue::CopyForDeref(place) = rvalue {
+            self.places.push(*place);
+        }
+        self.super_rvalue(rvalue, location);
+    }
+}
+
+impl<'tcx> GotocCtx<'tcx> {
+    pub fn uninit_checks_enabled(&self) -> bool {
+        self.queries.get_uninit_checks()
+    }
+
+    /// Instruments the code of a statement: the places it reads are checked before it runs, and
+    /// the place it writes is updated after it.
+    pub fn codegen_uninit_statement(&mut self, stmt: &Statement<'tcx>, code: Stmt) -> Stmt {
+        let loc = *code.location();
+        let mut stmts = self.codegen_uninit_read_checks(loc, |visitor| {
+            visitor.visit_statement(stmt, mir::Location::START)
+        });
+        stmts.push(code);
+        match &stmt.kind {
+            StatementKind::Assign(box (
+                place,
+                Rvalue::Use(Operand::Copy(src) | Operand::Move(src)) | Rvalue::CopyForDeref(src),
+            )) => stmts.push(self.codegen_uninit_copy_place(place, src, loc)),
+            StatementKind::Assign(box (place, _)) => {
+                stmts.push(self.codegen_uninit_mark_place(place, true, loc))
+            }
+            StatementKind::Deinit(box place) => {
+                stmts.push(self.codegen_uninit_mark_place(place, false, loc))
+            }
+            StatementKind::StorageLive(local) => {
+                stmts.push(self.codegen_uninit_mark_place(&Place::from(*local), false, loc))
+            }
+            _ => {}
+        }
+        Stmt::block(stmts, loc)
+    }
+
+    /// Instruments the code of a terminator: the places it reads are checked, and the state of
+    /// the arguments of a call, or of the return value, is passed to the callee or the caller.
+    /// The state of the destination of a call is received by `codegen_uninit_receive_return`.
+    pub fn codegen_uninit_terminator(&mut self, term: &Terminator<'tcx>, code: Stmt) -> Stmt {
+        let loc = *code.location();
+        let mut stmts = self.codegen_uninit_read_checks(loc, |visitor| {
+            visitor.visit_terminator(term, mir::Location::START)
+        });
+        match &term.kind {
+            TerminatorKind::Call { func, args, destination, .. } => {
+                stmts.push(self.codegen_uninit_call("__KANI_uninit_clear_transit", vec![], loc));
+                // The destination is written by the call, even if the callee is not instrumented
+                // (e.g., an intrinsic or a hook).
+                stmts.push(self.codegen_uninit_mark_place(destination, true, loc));
+                // The last argument of closure calls is untupled, so it doesn't match the
+                // parameters of the callee.
+                let untupled = self.ty_needs_closure_untupled(self.operand_ty(func));
+                let passed = if untupled { &args[..args.len().min(1)] } else { &args[..] };
+                for (idx, arg) in passed.iter().enumerate() {
+                    if let Operand::Copy(place) | Operand::Move(place) = arg {
+                        stmts.push(self.codegen_uninit_transfer(
+                            "__KANI_uninit_pass",
+                            idx as u64 + 1,
+                            place,
+                            loc,
+                        ));
+                    }
+                }
+            }
+            TerminatorKind::Return => stmts.push(self.codegen_uninit_transfer(
+                "__KANI_uninit_pass",
+                RETURN_SLOT,
+                &Place::from(mir::RETURN_PLACE),
+                loc,
+            )),
+            _ => {}
+        }
+        stmts.push(code);
+        Stmt::block(stmts, loc)
+    }
+
+    /// Enables the tracking of allocations on entry of the current function, if it's a harness,
+    /// and receives the state of its arguments from the caller.
+    pub fn codegen_uninit_prelude(&mut self) -> Stmt {
+        let mir = self.current_fn().mir();
+        let loc = self.codegen_span(&mir.span);
+        let enable = if self.is_current_fn_harness() {
+            self.codegen_uninit_call("__KANI_uninit_enable", vec![], loc)
+        } else {
+            Stmt::skip(loc)
+        };
+        Stmt::block(vec![enable, self.codegen_uninit_receive_args()], loc)
+    }
+
+    /// Receives the state of the arguments of the current function from the caller.
+    fn codegen_uninit_receive_args(&mut self) -> Stmt {
+        let mir = self.current_fn().mir();
+        let loc = self.codegen_span(&mir.span);
+        let stmts = mir
+            .args_iter()
+            .filter(|arg| Some(*arg) != mir.spread_arg)
+            .map(|arg| {
+                self.codegen_uninit_transfer(
+                    "__KANI_uninit_receive",
+                    arg.as_u32().into(),
+                    &Place::from(arg),
+                    loc,
+                )
+            })
+            .collect();
+        Stmt::block(stmts, loc)
+    }
+
+    /// Receives the state of the value returned by a call into its destination.
+    pub fn codegen_uninit_receive_return(
+        &mut self,
+        destination: &Place<'tcx>,
+        loc: Location,
+    ) -> Stmt {
+        if !self.uninit_checks_enabled() {
+            return Stmt::skip(loc);
+        }
+        self.codegen_uninit_transfer("__KANI_uninit_receive", RETURN_SLOT, destination, loc)
+    }
+
+    /// Copies the state of the `size` bytes at `src` to the bytes at `dst` (e.g., for the copy
+    /// intrinsics).
+    pub fn codegen_uninit_copy(&mut self, dst: Expr, src: Expr, size: Expr, loc: Location) -> Stmt {
+        if !self.uninit_checks_enabled() {
+            return Stmt::skip(loc);
+        }
+        let args = vec![Self::uninit_ptr(dst), Self::uninit_ptr(src), size];
+        self.codegen_uninit_call("__KANI_uninit_copy", args, loc)
+    }
+
+    /// Marks the `size` bytes at `ptr` as initialized (e.g., for the `write_bytes` intrinsic).
+    pub fn codegen_uninit_mark_init(&mut self, ptr: Expr, size: Expr, loc: Location) -> Stmt {
+        if !self.uninit_checks_enabled() {
+            return Stmt::skip(loc);
+        }
+        self.codegen_uninit_call("__KANI_uninit_mark_init", vec![Self::uninit_ptr(ptr), size], loc)
+    }
+
+    /// Checks the places read by a statement or terminator, visited by `visit`.
+    fn codegen_uninit_read_checks<F: FnOnce(&mut OperandPlaces<'tcx>)>(
+        &mut self,
+        loc: Location,
+        visit: F,
+    ) -> Vec<Stmt> {
+        let mut visitor = OperandPlaces { places: vec![] };
+        visit(&mut visitor);
+        visitor.places.iter().map(|place| self.codegen_uninit_read_check(place, loc)).collect()
+    }
+
+    /// Asserts that the bytes that hold the data of `place` are initialized.
+    fn codegen_uninit_read_check(&mut self, place: &Place<'tcx>, loc: Location) -> Stmt {
+        let Some((ptr, _)) = self.uninit_place_bytes(place) else { return Stmt::skip(loc) };
+        let mut ranges = vec![];
+        self.uninit_data_ranges(self.place_ty(place), 0, &mut ranges);
+        let msg = format!("read of uninitialized memory: `{}`", self.readable_place(place));
+        let checks = ranges
+            .into_iter()
+            .map(|(offset, size)| {
+                let start = ptr.clone().plus(Expr::int_constant(offset, Type::size_t()));
+                let size = Expr::int_constant(size, Type::size_t());
+                let is_init = self.codegen_uninit_fn(
+                    "__KANI_uninit_is_init",
+                    vec![start, size],
+                    Type::bool(),
+                );
+                self.codegen_assert_assume(is_init, PropertyClass::SafetyCheck, &msg, loc)
+            })
+            .collect();
+        Stmt::block(checks, loc)
+    }
+
+    /// Marks the bytes of `place` as initialized or uninitialized.
+    fn codegen_uninit_mark_place(
+        &mut self,
+        place: &Place<'tcx>,
+        init: bool,
+        loc: Location,
+    ) -> Stmt {
+        let Some((ptr, size)) = self.uninit_place_bytes(place) else { return Stmt::skip(loc) };
+        let name = if init { "__KANI_uninit_mark_init" } else { "__KANI_uninit_mark_uninit" };
+        self.codegen_uninit_call(name, vec![ptr, size], loc)
+    }
+
+    /// Copies the state of the bytes of `src` to the bytes of `dst`.
+    fn codegen_uninit_copy_place(
+        &mut self,
+        dst: &Place<'tcx>,
+        src: &Place<'tcx>,
+        loc: Location,
+    ) -> Stmt {
+        let (Some((dst, size)), Some((src, _))) =
+            (self.uninit_place_bytes(dst), self.uninit_place_bytes(src)) else {
+            return Stmt::skip(loc);
+        };
+        self.codegen_uninit_call("__KANI_uninit_copy", vec![dst, src, size], loc)
+    }
+
+    /// Passes the state of the bytes of `place` to, or receives it from, the given slot.
+    fn codegen_uninit_transfer(
+        &mut self,
+        function: &str,
+        slot: u64,
+        place: &Place<'tcx>,
+        loc: Location,
+    ) -> Stmt {
+        let Some((ptr, size)) = self.uninit_place_bytes(place) else { return Stmt::skip(loc) };
+        let slot = Expr::int_constant(slot, Type::size_t());
+        self.codegen_uninit_call(function, vec![slot, ptr, size], loc)
+    }
+
+    /// The address (as a byte pointer) and the size of `place`, or `None` if it has no bytes to
+    /// track (i.e., it is a ZST or unsized) or if its codegen is not supported (which is reported
+    /// by the codegen of the statement itself).
+    fn uninit_place_bytes(&mut self, place: &Place<'tcx>) -> Option<(Expr, Expr)> {
+        let layout = self.layout_of(self.place_ty(place));
+        if layout.is_zst() || layout.is_unsized() {
+            return None;
+        }
+        let ptr = Self::uninit_ptr(self.codegen_place(place).ok()?.goto_expr.address_of());
+        Some((ptr, Expr::int_constant(layout.size.bytes(), Type::size_t())))
+    }
+
+    /// Collects the (offset, size) ranges of the bytes that hold the data of a value of type `ty`
+    /// stored at `offset`.
+    fn uninit_data_ranges(&self, ty: Ty<'tcx>, offset: u64, ranges: &mut Vec<(u64, u64)>) {
+        let layout = self.layout_of(ty);
+        if layout.is_zst() {
+            return;
+        }
+        let is_primitive = |ty: Ty<'tcx>| {
+            matches!(
+                ty.kind(),
+                ty::Bool
+                    | ty::Char
+                    | ty::Int(_)
+                    | ty::Uint(_)
+                    | ty::Float(_)
+                    | ty::RawPtr(_)
+                    | ty::Ref(..)
+                    | ty::FnPtr(_)
+            )
+        };
+        let fields: Vec<Ty<'tcx>> = match ty.kind() {
+            _ if is_primitive(ty) => {
+                Self::push_range(ranges, offset, layout.size.bytes());
+                return;
+            }
+            ty::Array(elem, _) if is_primitive(*elem) => {
+                Self::push_range(ranges, offset, layout.size.bytes());
+                return;
+            }
+            ty::Adt(def, substs) if def.is_struct() => def
+                .non_enum_variant()
+                .fields
+                .iter()
+                .map(|field| {
+                    self.tcx.normalize_erasing_regions(
+                        ParamEnv::reveal_all(),
+                        field.ty(self.tcx, substs),
+                    )
+                })
+                .collect(),
+            ty::Tuple(tys) => tys.iter().collect(),
+            _ => return,
+        };
+        for (idx, field_ty) in fields.into_iter().enumerate() {
+            let field_offset = offset + layout.fields.offset(idx).bytes();
+            self.uninit_data_ranges(field_ty, field_offset, ranges);
+        }
+    }
+
+    /// Adds a range to `ranges`, merging it with the last one if they are contiguous.
+    fn push_range(ranges: &mut Vec<(u64, u64)>, offset: u64, size: u64) {
+        match ranges.last_mut() {
+            Some((last_offset, last_size)) if *last_offset + *last_size == offset => {
+                *last_size += size
+            }
+            _ => ranges.push((offset, size)),
+        }
+    }
+
+    /// A description of `place` in terms of the names of the user variables.
+    fn readable_place(&self, place: &Place<'tcx>) -> String {
+        let base = match self.find_debug_info(&place.local) {
+            Some(info) => info.name.to_string(),
+            None => format!("{:?}", place.local),
+        };
+        place.projection.iter().fold(base, |desc, elem| match elem {
+            ProjectionElem::Deref => format!("(*{desc})"),
+            ProjectionElem::Field(field, _) => format!("{desc}.{}", field.index()),
+            ProjectionElem::Index(local) => format!("{desc}[{}]", self.readable_local(local)),
+            ProjectionElem::ConstantIndex { offset, from_end: false, .. } => {
+                format!("{desc}[{offset}]")
+            }
+            ProjectionElem::ConstantIndex { offset, from_end: true, .. } => {
+                format!("{desc}[-{offset}]")
+            }
+            ProjectionElem::Subslice { .. } => format!("{desc}[..]"),
+            ProjectionElem::Downcast(Some(variant), _) => format!("({desc} as {variant})"),
+            ProjectionElem::Downcast(None, idx) => format!("({desc} as {})", idx.index()),
+            ProjectionElem::OpaqueCast(_) => desc,
+        })
+    }
+
+    fn readable_local(&self, local: Local) -> String {
+        match self.find_debug_info(&local) {
+            Some(info) => info.name.to_string(),
+            None => format!("{local:?}"),
+        }
+    }
+
+    fn uninit_ptr(ptr: Expr) -> Expr {
+        ptr.cast_to(Type::unsigned_int(8).to_pointer())
+    }
+
+    /// Calls one of the `__KANI_uninit_*` functions of `kani_lib.c` that return nothing.
+    fn codegen_uninit_call(&mut self, function: &str, args: Vec<Expr>, loc: Location) -> Stmt {
+        self.codegen_uninit_fn(function, args, Type::empty()).as_stmt(loc)
+    }
+
+    /// Calls one of the `__KANI_uninit_*` functions of `kani_lib.c`, which is declared (without
+    /// a body) the first time it is used.
+    fn codegen_uninit_fn(&mut self, function: &str, args: Vec<Expr>, ret: Type) -> Expr {
+        let param_types = args.iter().map(|arg| arg.typ().clone()).collect();
+        let typ = Type::code_with_unnamed_parameters(param_types, ret);
+        let func = self
+            .ensure(function, |_, name| Symbol::function(name, typ, None, name, Location::none()))
+            .to_expr();
+        func.call(args)
+    }
+}
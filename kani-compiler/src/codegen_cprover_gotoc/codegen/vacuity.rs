@@ -0,0 +1,76 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module generates the checks that detect vacuous harnesses, i.e., harnesses that verify
+//! successfully because their assumptions rule out (some of) the executions they should check.
+//!
+//! Each check is a cover of the location where it's inserted, with the `vacuity_check` property
+//! class. The driver doesn't report them as properties: it warns about the ones that are
+//! unsatisfiable instead. We check that:
+//!  * The end of every harness is reachable. Like the assertion reachability checks, this is
+//!    enabled by default and disabled with `--no-assertion-reach-checks`.
+//!  * With `--vacuity-checks`, every `kani::assume` in user code (i.e., the local crate) is
+//!    reachable, and so is the code right after it (otherwise the assumption makes the remaining
+//!    path infeasible).
+//!  * With `--vacuity-checks`, every `kani::assert` (and `assert!`) in user code is reachable.
+//!    We don't add a cover for that: the reachability check of the assertion gets the
+//!    `vacuity_check` property class instead, so that the driver can tell it's from user code.
+//!
+//! The driver also warns when none of the assertions of a harness is reachable, which only needs
+//! the assertion reachability checks.
+
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::has_kanitool_attribute;
+use cbmc::goto_program::{Expr, Location, Stmt};
+use kani_metadata::checks::{
+    VACUITY_ASSUMPTION_DESC, VACUITY_ASSUMPTION_HOLDS_DESC, VACUITY_HARNESS_END_DESC,
+};
+use kani_queries::UserInput;
+use rustc_span::Span;
+
+impl<'tcx> GotocCtx<'tcx> {
+    fn vacuity_checks_enabled(&self) -> bool {
+        self.queries.get_vacuity_checks() && self.current_fn().instance().def_id().is_local()
+    }
+
+    /// Checks that the code following `ret` is reachable, if the current function is a harness.
+    pub fn codegen_vacuity_exit(&self, ret: Stmt, loc: Location) -> Stmt {
+        let def_id = self.current_fn().instance().def_id();
+        if !self.queries.get_check_assertion_reachability()
+            || !has_kanitool_attribute(self.tcx.get_attrs_unchecked(def_id), "proof")
+        {
+            return ret;
+        }
+        Stmt::block(vec![self.codegen_vacuity_check(VACUITY_HARNESS_END_DESC, loc), ret], loc)
+    }
+
+    /// Checks that `assume` is reachable and satisfiable, if it's in user code.
+    pub fn codegen_vacuity_assume(&self, assume: Stmt, loc: Location) -> Stmt {
+        if !self.vacuity_checks_enabled() {
+            return assume;
+        }
+        Stmt::block(
+            vec![
+                self.codegen_vacuity_check(VACUITY_ASSUMPTION_DESC, loc),
+                assume,
+                self.codegen_vacuity_check(VACUITY_ASSUMPTION_HOLDS_DESC, loc),
+            ],
+            loc,
+        )
+    }
+
+    /// The reachability check of an assertion, which is also a vacuity check if it's in user
+    /// code.
+    pub fn codegen_assertion_reach_check(&self, reach_msg: &str, span: Option<Span>) -> Stmt {
+        if !self.vacuity_checks_enabled() {
+            return self.codegen_cover_loc(reach_msg, span);
+        }
+        self.codegen_vacuity_check(reach_msg, self.codegen_caller_span(&span))
+    }
+
+    /// A cover of `loc`, i.e., an assertion that fails if `loc` is reachable.
+    fn codegen_vacuity_check(&self, description: &str, loc: Location) -> Stmt {
+        self.codegen_assert(Expr::bool_false(), PropertyClass::VacuityCheck, description, loc)
+    }
+}
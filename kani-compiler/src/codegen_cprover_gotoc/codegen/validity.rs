@@ -0,0 +1,202 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module generates the checks of the validity invariants of the values produced by unsafe
+//! operations in user code (i.e., the local crate): values read through raw pointers, results of
+//! `transmute`, and values returned by the `read` functions of `core::ptr`.
+//!
+//! The validity invariant is derived from the layout of the type: every scalar that is not a
+//! union must be in its valid range (which encodes the values of `bool` and `char`, the
+//! discriminants and niches of enums, `NonZero` integers, and non-null references and function
+//! pointers). We check the tag of enums, but not the fields of their variants, and we don't check
+//! the elements of arrays.
+//!
+//! These checks are enabled by default, and can be disabled with `--no-validity-checks`.
+
+use super::uninit::OperandPlaces;
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use kani_queries::UserInput;
+use rustc_middle::mir;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{HasLocalDecls, Place, ProjectionElem, Statement, Terminator};
+use rustc_middle::ty::layout::{LayoutOf, TyAndLayout};
+use rustc_middle::ty::{Instance, Ty};
+use rustc_target::abi::{Abi, FieldsShape, Scalar, Size, Variants};
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Checks the values read through raw pointers by a statement before it runs.
+    pub fn codegen_validity_statement(&mut self, stmt: &Statement<'tcx>, code: Stmt) -> Stmt {
+        let mut visitor = OperandPlaces { places: vec![] };
+        visitor.visit_statement(stmt, mir::Location::START);
+        self.codegen_validity_raw_reads(visitor.places, code)
+    }
+
+    /// Checks the values read through raw pointers by a terminator before it runs.
+    pub fn codegen_validity_terminator(&mut self, term: &Terminator<'tcx>, code: Stmt) -> Stmt {
+        let mut visitor = OperandPlaces { places: vec![] };
+        visitor.visit_terminator(term, mir::Location::START);
+        self.codegen_validity_raw_reads(visitor.places, code)
+    }
+
+    /// Checks the value written to `place` by `transmute`.
+    pub fn codegen_validity_transmute(&mut self, place: &Place<'tcx>, loc: Location) -> Stmt {
+        if !self.validity_checks_apply() {
+            return Stmt::skip(loc);
+        }
+        self.codegen_validity_check(place, "produced by `transmute`", loc)
+    }
+
+    /// Checks the value returned into `destination` by a call to `instance`, if it is one of the
+    /// `read` functions of `core::ptr` (e.g., `ptr::read` or `<*const T>::read_unaligned`).
+    pub fn codegen_validity_call_result(
+        &mut self,
+        instance: Instance<'tcx>,
+        destination: &Place<'tcx>,
+        loc: Location,
+    ) -> Stmt {
+        if !self.validity_checks_apply() {
+            return Stmt::skip(loc);
+        }
+        let Some(name) = self.ptr_fns.read_name(instance.def_id()) else {
+            return Stmt::skip(loc);
+        };
+        let origin = format!("read by `{name}`");
+        self.codegen_validity_check(destination, &origin, loc)
+    }
+
+    /// Assumes that the value of type `ty` stored at `ptr` satisfies the validity invariant of
+    /// its type (e.g., after `kani::havoc` makes it nondeterministic).
+    pub fn codegen_assume_valid(&mut self, ty: Ty<'tcx>, ptr: Expr, loc: Location) -> Stmt {
+        let mut scalars = vec![];
+        self.validity_scalars(self.layout_of(ty), Size::ZERO, &mut scalars);
+        let ptr = ptr.cast_to(Type::unsigned_int(8).to_pointer());
+        let assumptions = scalars
+            .into_iter()
+            .map(|(offset, scalar)| {
+                self.codegen_assume(self.scalar_is_valid(ptr.clone(), offset, scalar), loc)
+            })
+            .collect();
+        Stmt::block(assumptions, loc)
+    }
+
+    /// Whether some values of type `ty` don't satisfy its validity invariant.
+    pub fn has_validity_invariant(&self, ty: Ty<'tcx>) -> bool {
+        let mut scalars = vec![];
+        self.validity_scalars(self.layout_of(ty), Size::ZERO, &mut scalars);
+        !scalars.is_empty()
+    }
+
+    /// We only check the values produced in user code, unless `--no-validity-checks` is set.
+    fn validity_checks_apply(&self) -> bool {
+        self.queries.get_validity_checks() && self.current_fn().instance().def_id().is_local()
+    }
+
+    fn codegen_validity_raw_reads(&mut self, places: Vec<Place<'tcx>>, code: Stmt) -> Stmt {
+        if !self.validity_checks_apply() {
+            return code;
+        }
+        let loc = *code.location();
+        let raw_reads: Vec<_> =
+            places.into_iter().filter(|place| self.is_raw_ptr_read(place)).collect();
+        let mut stmts: Vec<Stmt> = raw_reads
+            .iter()
+            .map(|place| self.codegen_validity_check(place, "read through a raw pointer", loc))
+            .collect();
+        if stmts.is_empty() {
+            return code;
+        }
+        stmts.push(code);
+        Stmt::block(stmts, loc)
+    }
+
+    /// Whether reading `place` dereferences a raw pointer.
+    fn is_raw_ptr_read(&self, place: &Place<'tcx>) -> bool {
+        let local_decls = self.current_fn().mir().local_decls();
+        place.iter_projections().any(|(base, elem)| {
+            elem == ProjectionElem::Deref
+                && self.monomorphize(base.ty(local_decls, self.tcx).ty).is_unsafe_ptr()
+        })
+    }
+
+    /// Asserts that the value stored in `place` satisfies the validity invariant of its type.
+    fn codegen_validity_check(&mut self, place: &Place<'tcx>, origin: &str, loc: Location) -> Stmt {
+        let ty = self.place_ty(place);
+        let layout = self.layout_of(ty);
+        if layout.is_unsized() {
+            return Stmt::skip(loc);
+        }
+        let mut scalars = vec![];
+        self.validity_scalars(layout, Size::ZERO, &mut scalars);
+        if scalars.is_empty() {
+            return Stmt::skip(loc);
+        }
+        let Ok(place_expr) = self.codegen_place(place) else { return Stmt::skip(loc) };
+        let ptr = place_expr.goto_expr.address_of().cast_to(Type::unsigned_int(8).to_pointer());
+        let msg = format!("invalid value of type `{ty}` {origin}");
+        let checks = scalars
+            .into_iter()
+            .map(|(offset, scalar)| {
+                let is_valid = self.scalar_is_valid(ptr.clone(), offset, scalar);
+                self.codegen_assert_assume(is_valid, PropertyClass::ValidityCheck, &msg, loc)
+            })
+            .collect();
+        Stmt::block(checks, loc)
+    }
+
+    /// Whether the bits of the scalar at `offset` bytes from `ptr` are in its valid range.
+    fn scalar_is_valid(&self, ptr: Expr, offset: Size, scalar: Scalar) -> Expr {
+        let range = scalar.valid_range(self);
+        let int_ty = Type::unsigned_int(scalar.size(self).bits());
+        let value = ptr
+            .plus(Expr::int_constant(offset.bytes(), Type::size_t()))
+            .cast_to(int_ty.clone().to_pointer())
+            .dereference();
+        let start = Expr::int_constant(range.start, int_ty.clone());
+        let end = Expr::int_constant(range.end, int_ty);
+        if range.start <= range.end {
+            value.clone().ge(start).and(value.le(end))
+        } else {
+            // The range wraps around.
+            value.clone().ge(start).or(value.le(end))
+        }
+    }
+
+    /// Collects the scalars with a restricted valid range of a value with the given layout,
+    /// stored at `offset`.
+    fn validity_scalars(
+        &self,
+        layout: TyAndLayout<'tcx>,
+        offset: Size,
+        scalars: &mut Vec<(Size, Scalar)>,
+    ) {
+        let mut push = |offset: Size, scalar: Scalar| {
+            if !scalar.is_always_valid(self) {
+                scalars.push((offset, scalar))
+            }
+        };
+        match layout.abi {
+            Abi::Scalar(scalar) => push(offset, scalar),
+            Abi::ScalarPair(first, second) => {
+                push(offset, first);
+                push(offset + first.size(self).align_to(second.align(self).abi), second);
+            }
+            Abi::Uninhabited | Abi::Vector { .. } => {}
+            Abi::Aggregate { .. } => match &layout.variants {
+                Variants::Multiple { tag, tag_field, .. } => {
+                    push(offset + layout.fields.offset(*tag_field), *tag)
+                }
+                Variants::Single { .. } => {
+                    if let FieldsShape::Arbitrary { .. } = layout.fields {
+                        for idx in 0..layout.fields.count() {
+                            let field = layout.field(self, idx);
+                            let field_offset = offset + layout.fields.offset(idx);
+                            self.validity_scalars(field, field_offset, scalars);
+                        }
+                    }
+                }
+            },
+        }
+    }
+}
@@ -140,6 +140,11 @@ fn codegen_crate(
                         MonoItem::GlobalAsm(_) => {} // We have already warned above
                     }
                 }
+
+                // finally, we define the functions that depend on the whole crate
+                if gcx.leak_check_enabled() {
+                    gcx.codegen_leak_check_fn();
+                }
             },
             "codegen",
         );
@@ -158,12 +163,11 @@ fn codegen_crate(
             None
         };
 
-        let metadata = generate_metadata(&gcx, tcx);
+        let base_filename = model_base_filename(&gcx);
+        let metadata = generate_metadata(&gcx, tcx, &base_filename);
 
         // No output should be generated if user selected no_codegen.
         if !tcx.sess.opts.unstable_opts.no_codegen && tcx.sess.opts.output_types.should_codegen() {
-            let outputs = tcx.output_filenames(());
-            let base_filename = outputs.output_path(OutputType::Object);
             let pretty = self.queries.get_output_pretty_json();
             write_file(&base_filename, ArtifactType::SymTab, &gcx.symbol_table, pretty);
             write_file(&base_filename, ArtifactType::TypeMap, &type_map, pretty);
@@ -376,7 +380,13 @@ fn collect_codegen_items<'tcx>(gcx: &GotocCtx<'tcx>) -> Vec<MonoItem<'tcx>> {
         }
         ReachabilityType::Harnesses => {
             // Cross-crate collecting of all items that are reachable from the crate harnesses.
-            let harnesses = filter_crate_items(tcx, |_, def_id| gcx.is_proof_harness(def_id));
+            // If a filter is given (e.g., when each stub set gets its own model), only start from
+            // the harnesses in the filter.
+            let filter = gcx.queries.get_harness_filter();
+            let harnesses = filter_crate_items(tcx, |_, def_id| {
+                gcx.is_proof_harness(def_id)
+                    && filter.map_or(true, |names| names.contains(&tcx.def_path_str(def_id)))
+            });
             collect_reachable_items(tcx, &harnesses).into_iter().collect()
         }
         ReachabilityType::Tests => {
@@ -468,13 +478,26 @@ fn visible_item<'tcx>(item: &MonoItem<'tcx>) -> Option<(MonoItem<'tcx>, DefId)>
     }
 }
 
+/// Return the path used as a base for the names of all the artifacts of the model.
+///
+/// When a crate has more than one model (e.g., one per stub set), the models other than the
+/// first one are distinguished by a suffix: `<crate>.<suffix>.symtab.out`.
+fn model_base_filename(gcx: &GotocCtx) -> PathBuf {
+    let base_filename = gcx.tcx.output_filenames(()).output_path(OutputType::Object);
+    match gcx.queries.get_model_suffix() {
+        Some(suffix) => {
+            let extension = base_filename.extension().unwrap_or_default().to_string_lossy();
+            base_filename.with_extension(format!("{suffix}.{extension}"))
+        }
+        None => base_filename,
+    }
+}
+
 /// Method that generates `KaniMetadata` from the given compilation context.
 /// This is a temporary method used until we generate a model per-harness.
 /// See <https://github.com/model-checking/kani/issues/1855> for more details.
-fn generate_metadata(gcx: &GotocCtx, tcx: TyCtxt) -> KaniMetadata {
-    let outputs = tcx.output_filenames(());
-    let model_file =
-        outputs.output_path(OutputType::Object).with_extension(ArtifactType::SymTabGoto);
+fn generate_metadata(gcx: &GotocCtx, tcx: TyCtxt, base_filename: &Path) -> KaniMetadata {
+    let model_file = base_filename.with_extension(ArtifactType::SymTabGoto);
     let extend_harnesses = |mut harnesses: Vec<HarnessMetadata>| {
         for harness in harnesses.iter_mut() {
             harness.goto_file = Some(model_file.clone());
@@ -3,16 +3,21 @@
 
 use crate::codegen_cprover_gotoc::GotocCtx;
 use cbmc::goto_program::Stmt;
+use rustc_index::bit_set::BitSet;
 use rustc_middle::mir::BasicBlock;
 use rustc_middle::mir::Body;
+use rustc_middle::mir::Local;
 use rustc_middle::ty::Instance;
 use rustc_middle::ty::PolyFnSig;
+use rustc_mir_dataflow::impls::borrowed_locals;
 
 /// This structure represents useful data about the function we are currently compiling.
 #[derive(Debug)]
 pub struct CurrentFnCtx<'tcx> {
     /// The GOTO block we are compiling into
     block: Vec<Stmt>,
+    /// The locals whose address is taken
+    borrowed_locals: BitSet<Local>,
     /// The current MIR basic block
     current_bb: Option<BasicBlock>,
     /// The codegen instance for the current function
@@ -36,13 +41,15 @@ pub struct CurrentFnCtx<'tcx> {
 /// Constructor
 impl<'tcx> CurrentFnCtx<'tcx> {
     pub fn new(instance: Instance<'tcx>, gcx: &GotocCtx<'tcx>, labels: Vec<String>) -> Self {
+        let mir = gcx.tcx.instance_mir(instance.def);
         Self {
             block: vec![],
+            borrowed_locals: borrowed_locals(mir),
             current_bb: None,
             instance,
             krate: gcx.get_crate(instance),
             labels,
-            mir: gcx.tcx.instance_mir(instance.def),
+            mir,
             name: gcx.symbol_name(instance),
             readable_name: gcx.readable_instance_name(instance),
             sig: gcx.fn_sig_of_instance(instance),
@@ -84,6 +91,11 @@ pub fn instance(&self) -> Instance<'tcx> {
         self.instance
     }
 
+    /// Whether the address of `local` is taken in the current function.
+    pub fn is_borrowed(&self, local: Local) -> bool {
+        self.borrowed_locals.contains(local)
+    }
+
     /// The crate that function came from
     pub fn krate(&self) -> String {
         self.krate.to_string()
@@ -15,15 +15,17 @@
 //! this structure as input.
 use super::current_fn::CurrentFnCtx;
 use super::vtable_ctx::VtableCtx;
+use crate::codegen_cprover_gotoc::codegen::nondet_alloc_failure_in_crate;
 use crate::codegen_cprover_gotoc::overrides::{fn_hooks, GotocHooks};
 use crate::codegen_cprover_gotoc::utils::full_crate_name;
+use crate::kani_middle::ptr_fns::PtrFns;
 use cbmc::goto_program::{DatatypeComponent, Expr, Location, Stmt, Symbol, SymbolTable, Type};
 use cbmc::utils::aggr_tag;
 use cbmc::InternedString;
 use cbmc::{MachineModel, RoundingMode};
 use kani_metadata::{HarnessMetadata, UnsupportedFeature};
 use kani_queries::{QueryDb, UserInput};
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
 use rustc_data_structures::owning_ref::OwningRef;
 use rustc_data_structures::rustc_erase_owner;
 use rustc_data_structures::sync::MetadataRef;
@@ -67,15 +69,23 @@ pub struct GotocCtx<'tcx> {
     pub global_checks_count: u64,
     /// A map of unsupported constructs that were found while codegen
     pub unsupported_constructs: FxHashMap<InternedString, Vec<Location>>,
+    /// The locations of the calls from user code into the standard library, where the memory
+    /// leaks detected by `--leak-check` are reported
+    pub leak_sites: FxIndexMap<Span, Location>,
+    /// The functions of `core::ptr` that access the memory behind a raw pointer
+    pub ptr_fns: PtrFns,
+    /// Whether allocations may fail in some harness of the crate
+    pub nondet_alloc_failure: bool,
 }
 
 /// Constructor
 impl<'tcx> GotocCtx<'tcx> {
     pub fn new(tcx: TyCtxt<'tcx>, queries: Rc<QueryDb>) -> GotocCtx<'tcx> {
-        let fhks = fn_hooks();
+        let fhks = fn_hooks(&queries);
         let mm = machine_model_from_session(tcx.sess);
         let symbol_table = SymbolTable::new(mm);
         let emit_vtable_restrictions = queries.get_emit_vtable_restrictions();
+        let nondet_alloc_failure = nondet_alloc_failure_in_crate(tcx, &queries);
         GotocCtx {
             tcx,
             queries,
@@ -92,6 +102,9 @@ pub fn new(tcx: TyCtxt<'tcx>, queries: Rc<QueryDb>) -> GotocCtx<'tcx> {
             test_harnesses: vec![],
             global_checks_count: 0,
             unsupported_constructs: FxHashMap::default(),
+            leak_sites: FxIndexMap::default(),
+            ptr_fns: PtrFns::new(tcx),
+            nondet_alloc_failure,
         }
     }
 }
@@ -8,14 +8,18 @@
 //! It would be too nasty if we spread around these sort of undocumented hooks in place, so
 //! this module addresses this issue.
 
+use crate::codegen_cprover_gotoc::codegen::typ::pointee_type;
 use crate::codegen_cprover_gotoc::codegen::PropertyClass;
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
-use cbmc::goto_program::{BuiltinFn, Expr, Location, Stmt, Type};
-use kani_queries::UserInput;
+use cbmc::goto_program::{BuiltinFn, Expr, Location, Stmt, Symbol, Type};
+use kani_metadata::checks::MAX_RECORDED_ELEMENTS;
+use kani_queries::{QueryDb, UserInput};
+use rustc_hir::def_id::CRATE_DEF_INDEX;
 use rustc_middle::mir::{BasicBlock, Place};
+use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::print::with_no_trimmed_paths;
-use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_middle::ty::{self, Instance, ParamEnv, TyCtxt};
 use rustc_span::Span;
 use std::rc::Rc;
 use tracing::debug;
@@ -141,13 +145,8 @@ fn handle(
         let target = target.unwrap();
         let loc = tcx.codegen_span_option(span);
 
-        Stmt::block(
-            vec![
-                tcx.codegen_assume(cond, loc),
-                Stmt::goto(tcx.current_fn().find_label(&target), loc),
-            ],
-            loc,
-        )
+        let assume = tcx.codegen_vacuity_assume(tcx.codegen_assume(cond, loc), loc);
+        Stmt::block(vec![assume, Stmt::goto(tcx.current_fn().find_label(&target), loc)], loc)
     }
 }
 
@@ -183,7 +182,7 @@ fn handle(
             let msg = GotocCtx::add_prefix_to_msg(&msg, &assert_id);
             let reach_msg = GotocCtx::reachability_check_message(&assert_id);
             // inject a reachability (cover) check to the current location
-            (msg, tcx.codegen_cover_loc(&reach_msg, span))
+            (msg, tcx.codegen_assertion_reach_check(&reach_msg, span))
         } else {
             (msg, Stmt::skip(caller_loc))
         };
@@ -230,17 +229,207 @@ fn handle(
             let pe =
                 unwrap_or_return_codegen_unimplemented_stmt!(tcx, tcx.codegen_place(&assign_to))
                     .goto_expr;
-            Stmt::block(
-                vec![
-                    pe.assign(tcx.codegen_ty(pt).nondet(), loc),
-                    Stmt::goto(tcx.current_fn().find_label(&target), loc),
-                ],
-                loc,
-            )
+            let mut stmts = vec![pe.clone().assign(tcx.codegen_ty(pt).nondet(), loc)];
+            if tcx.queries.get_record_nondet() {
+                let size = tcx.layout_of(pt).size.bytes();
+                stmts.push(codegen_record_nondet(tcx, pe.address_of(), size, loc));
+            }
+            stmts.push(Stmt::goto(tcx.current_fn().find_label(&target), loc));
+            Stmt::block(stmts, loc)
+        }
+    }
+}
+
+/// Passes the `size` bytes of a value produced by `kani::any()` to `__KANI_record_nondet`, which
+/// the driver redefines to exclude the inputs of the counterexamples it already found.
+fn codegen_record_nondet(tcx: &mut GotocCtx, ptr: Expr, size: u64, loc: Location) -> Stmt {
+    let function = "__KANI_record_nondet";
+    let args = vec![
+        ptr.cast_to(Type::unsigned_int(8).to_pointer()),
+        Expr::int_constant(size, Type::size_t()),
+    ];
+    let param_types = args.iter().map(|arg| arg.typ().clone()).collect();
+    let typ = Type::code_with_unnamed_parameters(param_types, Type::empty());
+    let func = tcx
+        .ensure(function, |_, name| Symbol::function(name, typ, None, name, Location::none()))
+        .to_expr();
+    func.call(args).as_stmt(loc)
+}
+
+/// A hook for `kani::havoc(place)`, which assigns a nondeterministic value to the place and
+/// assumes that it satisfies the validity invariant of its type.
+///
+/// This compiles `havoc(place)` to:
+/// ```c
+/// place_var = place;
+/// *place_var = nondet();
+/// assume(valid(*place_var));
+/// ```
+struct Havoc;
+
+impl<'tcx> GotocHook<'tcx> for Havoc {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniHavoc")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let loc = tcx.codegen_span_option(span);
+        let target = target.unwrap();
+        let ty = tcx.monomorphize(instance.substs.type_at(0));
+        let size = tcx.layout_of(ty).size.bytes();
+        let mut stmts = vec![];
+        if size > 0 {
+            let place = fargs.remove(0);
+            let (place_var, place_decl) =
+                tcx.decl_temp_variable(place.typ().clone(), Some(place), loc);
+            let value = tcx.codegen_ty(ty).nondet();
+            let size = Expr::int_constant(size, Type::size_t());
+            stmts.push(place_decl);
+            stmts.push(place_var.clone().dereference().assign(value, loc));
+            stmts.push(tcx.codegen_uninit_mark_init(place_var.clone(), size, loc));
+            stmts.push(tcx.codegen_assume_valid(ty, place_var, loc));
+        }
+        stmts.push(Stmt::goto(tcx.current_fn().find_label(&target), loc));
+        Stmt::block(stmts, loc)
+    }
+}
+
+/// A hook for `kani::havoc_slice(slice)`, which makes the elements of the slice
+/// nondeterministic. If the elements accept any bit pattern, their bytes are havocked at once
+/// by `__KANI_havoc`. Otherwise, each element is assigned a nondeterministic value that is
+/// assumed to be valid, which requires unwinding the loop.
+///
+/// This compiles `havoc_slice(slice)` to:
+/// ```c
+/// slice_var = slice;
+/// __KANI_havoc(slice_var.data, slice_var.len * sizeof(T));
+/// ```
+/// or
+/// ```c
+/// slice_var = slice;
+/// for (idx = 0; idx < slice_var.len; idx++) {
+///     slice_var.data[idx] = nondet();
+///     assume(valid(slice_var.data[idx]));
+/// }
+/// ```
+struct HavocSlice;
+
+impl<'tcx> GotocHook<'tcx> for HavocSlice {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniHavocSlice")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let loc = tcx.codegen_span_option(span);
+        let target = target.unwrap();
+        let elem_ty = tcx.monomorphize(instance.substs.type_at(0));
+        let elem_size = tcx.layout_of(elem_ty).size.bytes();
+        let mut stmts = vec![];
+        if elem_size > 0 {
+            let slice = fargs.remove(0);
+            let (slice_var, slice_decl) =
+                tcx.decl_temp_variable(slice.typ().clone(), Some(slice), loc);
+            let data = slice_var.clone().member("data", &tcx.symbol_table);
+            let len = slice_var.member("len", &tcx.symbol_table);
+            let size = len.clone().mul(Expr::int_constant(elem_size, Type::size_t()));
+            stmts.push(slice_decl);
+            if tcx.has_validity_invariant(elem_ty) {
+                let (idx, idx_decl) =
+                    tcx.decl_temp_variable(len.typ().clone(), Some(len.typ().zero()), loc);
+                let elem = data.clone().plus(idx.clone());
+                let body = Stmt::block(
+                    vec![
+                        elem.clone().dereference().assign(tcx.codegen_ty(elem_ty).nondet(), loc),
+                        tcx.codegen_assume_valid(elem_ty, elem, loc),
+                    ],
+                    loc,
+                );
+                stmts.push(idx_decl);
+                stmts.push(Stmt::for_loop(
+                    Stmt::skip(loc),
+                    idx.clone().lt(len),
+                    idx.postincr().as_stmt(loc),
+                    body,
+                    loc,
+                ));
+            } else {
+                stmts.push(codegen_havoc_bytes(tcx, data.clone(), size.clone(), loc));
+            }
+            stmts.push(tcx.codegen_uninit_mark_init(data, size, loc));
         }
+        stmts.push(Stmt::goto(tcx.current_fn().find_label(&target), loc));
+        Stmt::block(stmts, loc)
+    }
+}
+
+/// A hook for `kani::havoc_raw(ptr, len)`, which makes the `len` bytes at `ptr`
+/// nondeterministic with `__KANI_havoc`.
+struct HavocRaw;
+
+impl<'tcx> GotocHook<'tcx> for HavocRaw {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniHavocRaw")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let loc = tcx.codegen_span_option(span);
+        let target = target.unwrap();
+        let ptr = fargs.remove(0);
+        let len = fargs.remove(0);
+        let (ptr_var, ptr_decl) = tcx.decl_temp_variable(ptr.typ().clone(), Some(ptr), loc);
+        let (len_var, len_decl) = tcx.decl_temp_variable(len.typ().clone(), Some(len), loc);
+        Stmt::block(
+            vec![
+                ptr_decl,
+                len_decl,
+                codegen_havoc_bytes(tcx, ptr_var.clone(), len_var.clone(), loc),
+                tcx.codegen_uninit_mark_init(ptr_var, len_var, loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), loc),
+            ],
+            loc,
+        )
     }
 }
 
+/// Makes the `len` bytes at `ptr` nondeterministic (see `__KANI_havoc` in `kani_lib.c`).
+fn codegen_havoc_bytes(tcx: &mut GotocCtx, ptr: Expr, len: Expr, loc: Location) -> Stmt {
+    let function = "__KANI_havoc";
+    let args = vec![ptr.cast_to(Type::unsigned_int(8).to_pointer()), len];
+    let param_types = args.iter().map(|arg| arg.typ().clone()).collect();
+    let typ = Type::code_with_unnamed_parameters(param_types, Type::empty());
+    let func = tcx
+        .ensure(function, |_, name| Symbol::function(name, typ, None, name, Location::none()))
+        .to_expr();
+    func.call(args).as_stmt(loc)
+}
+
 struct Panic;
 
 impl<'tcx> GotocHook<'tcx> for Panic {
@@ -288,20 +477,28 @@ fn handle(
         let loc = tcx.codegen_span_option(span);
         let target = target.unwrap();
         let size = fargs.remove(0);
-        Stmt::block(
-            vec![
-                unwrap_or_return_codegen_unimplemented_stmt!(tcx, tcx.codegen_place(&assign_to))
-                    .goto_expr
-                    .assign(
-                        BuiltinFn::Malloc
-                            .call(vec![size], loc)
-                            .cast_to(Type::unsigned_int(8).to_pointer()),
-                        loc,
-                    ),
-                Stmt::goto(tcx.current_fn().find_label(&target), Location::none()),
-            ],
-            Location::none(),
-        )
+        let mut stmts = vec![];
+        if tcx.nondet_alloc_failure_enabled() {
+            // `exchange_malloc` calls `handle_alloc_error` if the allocation fails.
+            let fails = tcx.codegen_alloc_fails(size.clone());
+            let error =
+                tcx.codegen_fatal_error(PropertyClass::Assertion, "memory allocation failed", span);
+            let check = Stmt::if_then_else(fails, error, None, loc);
+            stmts.extend(tcx.codegen_alloc_failure_scope(instance, vec![check], loc));
+        }
+        let ptr = unwrap_or_return_codegen_unimplemented_stmt!(tcx, tcx.codegen_place(&assign_to))
+            .goto_expr;
+        let mut alloc = vec![ptr.clone().assign(
+            BuiltinFn::Malloc.call(vec![size], loc).cast_to(Type::unsigned_int(8).to_pointer()),
+            loc,
+        )];
+        if tcx.leak_check_enabled() {
+            alloc.push(tcx.codegen_leak_track(ptr, loc));
+            alloc = tcx.codegen_leak_site(instance, alloc, span, loc);
+        }
+        stmts.extend(alloc);
+        stmts.push(Stmt::goto(tcx.current_fn().find_label(&target), Location::none()));
+        Stmt::block(stmts, Location::none())
     }
 }
 
@@ -327,14 +524,33 @@ fn handle(
     ) -> Stmt {
         let loc = tcx.codegen_span_option(span);
         let target = target.unwrap();
-        let pt = tcx.codegen_ty(tcx.place_ty(&assign_to));
+        let slice_ptr_ty = tcx.place_ty(&assign_to);
+        let pt = tcx.codegen_ty(slice_ptr_ty);
         let data = fargs.remove(0);
         let len = fargs.remove(0);
+        // `slice::from_raw_parts` creates its slice with this function, and requires `data` to
+        // be aligned.
+        let caller = tcx.current_fn().instance().def_id();
+        let align_check = if tcx.ptr_fns.is_slice_from_raw_parts(caller) {
+            let caller_name = tcx.tcx.item_name(caller);
+            let elem_ty = pointee_type(slice_ptr_ty).unwrap().sequence_element_type(tcx.tcx);
+            tcx.codegen_alignment_check(
+                tcx.tcx.mk_imm_ptr(elem_ty),
+                data.clone(),
+                &format!("`slice::{caller_name}` requires that the pointer argument is aligned"),
+                loc,
+            )
+        } else {
+            Stmt::skip(loc)
+        };
         let code = unwrap_or_return_codegen_unimplemented_stmt!(tcx, tcx.codegen_place(&assign_to))
             .goto_expr
             .assign(Expr::struct_expr_from_values(pt, vec![data, len], &tcx.symbol_table), loc)
             .with_location(loc);
-        Stmt::block(vec![code, Stmt::goto(tcx.current_fn().find_label(&target), loc)], loc)
+        Stmt::block(
+            vec![align_check, code, Stmt::goto(tcx.current_fn().find_label(&target), loc)],
+            loc,
+        )
     }
 }
 
@@ -404,20 +620,287 @@ fn handle(
     }
 }
 
-pub fn fn_hooks<'tcx>() -> GotocHooks<'tcx> {
-    GotocHooks {
-        hooks: vec![
-            Rc::new(Panic),
-            Rc::new(Assume),
-            Rc::new(Assert),
-            Rc::new(Cover),
-            Rc::new(ExpectFail),
-            Rc::new(Nondet),
-            Rc::new(RustAlloc),
-            Rc::new(SliceFromRawPart),
-            Rc::new(MemCmp),
-        ],
+/// A hook for the `getrandom` function of the `getrandom` crate, which is the source of
+/// randomness of `rand` (among others). The real function makes a system call that Kani cannot
+/// model, so the hook fills the buffer with nondeterministic bytes instead, and always succeeds.
+/// `--no-default-models` disables it.
+///
+/// This compiles `getrandom(dest)` to:
+/// ```c
+/// __KANI_havoc(dest.data, dest.len);
+/// ret = Ok(());
+/// ```
+struct GetRandom;
+
+impl<'tcx> GotocHook<'tcx> for GetRandom {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        // The function `getrandom` at the root of the `getrandom` crate.
+        let def_id = instance.def_id();
+        tcx.crate_name(def_id.krate).as_str() == "getrandom"
+            && tcx.def_key(def_id).parent == Some(CRATE_DEF_INDEX)
+            && tcx.opt_item_name(def_id).map_or(false, |name| name.as_str() == "getrandom")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let loc = tcx.codegen_span_option(span);
+        let target = target.unwrap();
+        let dest = fargs.remove(0);
+        let data = dest.clone().member("data", &tcx.symbol_table);
+        let len = dest.member("len", &tcx.symbol_table);
+        let fill = codegen_havoc_bytes(tcx, data, len, loc);
+        // The result is a `Result<(), getrandom::Error>`, so it's enough to set the discriminant
+        // of `Ok`.
+        let result_ty = tcx.place_ty(&assign_to);
+        let ty::Adt(result_def, _) = result_ty.kind() else {
+            unreachable!("`getrandom` returns a `Result`, not `{result_ty}`")
+        };
+        let ok = result_def
+            .variants()
+            .iter_enumerated()
+            .find_map(|(idx, variant)| (variant.name.as_str() == "Ok").then_some(idx))
+            .unwrap();
+        let place_expr =
+            unwrap_or_return_codegen_unimplemented_stmt!(tcx, tcx.codegen_place(&assign_to))
+                .goto_expr;
+        Stmt::block(
+            vec![
+                fill,
+                tcx.codegen_set_discriminant(result_ty, place_expr, ok, loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), loc),
+            ],
+            loc,
+        )
+    }
+}
+
+/// A hook for Kani's `record_fmt_arg` and `record_assert_operand` functions (declared in
+/// `library/kani/src/lib.rs`), which our standard library overrides use to record the arguments of
+/// a failing assertion or panic message. The driver looks up the recorded values in the trace of
+/// the failed check to render its message.
+///
+/// A value of a primitive type `T` (possibly behind references) recorded at index `I` is stored
+/// in a global variable named `__kani_<kind>_<I>_<T>`. This compiles `record(value)` to:
+/// ```c
+/// __kani_fmt_arg_0_u32 = nondet();
+/// assume(__kani_fmt_arg_0_u32 == *value);
+/// ```
+/// The assumption makes the failed check depend on the variable, so CBMC's `--slice-formula`
+/// doesn't remove the assignment from the trace.
+///
+/// For a string or a slice of a primitive type, the length is stored in `__kani_<kind>_<I>_str`
+/// or `__kani_<kind>_<I>_slice`, and each of its first [`MAX_RECORDED_ELEMENTS`] elements in
+/// `__kani_<kind>_<I>_<element>_<T>` (for strings, `T` is `u8`), the same way:
+/// ```c
+/// if (1 < value.len) {
+///     __kani_fmt_arg_0_1_u8 = nondet();
+///     assume(__kani_fmt_arg_0_1_u8 == value.data[1]);
+/// }
+/// ```
+/// Values of other types are not recorded.
+///
+/// The assignment gets the location of the outermost macro call (like the assertion or panic it
+/// belongs to), which is how the driver tells which recorded values belong to a failed check.
+struct RecordValue {
+    diagnostic_item: &'static str,
+    kind: &'static str,
+}
+
+impl RecordValue {
+    /// Assigns `value` to the global variable `name`, through an assumption.
+    fn record(tcx: &mut GotocCtx, name: String, value: Expr, loc: Location) -> Stmt {
+        let typ = value.typ().clone();
+        let var = tcx.ensure_global_var(name, false, typ.clone(), loc, |_, _| None);
+        Stmt::block(
+            vec![var.clone().assign(typ.nondet(), loc), tcx.codegen_assume(var.eq(value), loc)],
+            loc,
+        )
+    }
+}
+
+/// The name of a primitive type whose values can be recorded.
+fn recorded_type_name(ty: ty::Ty) -> Option<&'static str> {
+    match ty.kind() {
+        ty::Bool => Some("bool"),
+        ty::Char => Some("char"),
+        ty::Int(int_ty) => Some(int_ty.name_str()),
+        ty::Uint(uint_ty) => Some(uint_ty.name_str()),
+        _ => None,
+    }
+}
+
+impl<'tcx> GotocHook<'tcx> for RecordValue {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, self.diagnostic_item)
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let loc = tcx.codegen_caller_span(&span);
+        let goto_target = Stmt::goto(tcx.current_fn().find_label(&target.unwrap()), loc);
+        let index =
+            instance.substs.const_at(0).try_eval_usize(tcx.tcx, ParamEnv::reveal_all()).unwrap();
+        let prefix = format!("__kani_{}_{index}", self.kind);
+        // Peel off the references to find the type of the recorded value. `ptr` points to it.
+        let mut ty = instance.substs.type_at(1);
+        let mut ptr = fargs.remove(0);
+        while let ty::Ref(_, pointee, _) = ty.kind() {
+            ty = *pointee;
+            ptr = ptr.dereference();
+        }
+        let (kind, elem_ty) = match ty.kind() {
+            ty::Str => ("str", tcx.tcx.types.u8),
+            ty::Slice(elem_ty) => ("slice", *elem_ty),
+            _ => {
+                let Some(ty_name) = recorded_type_name(ty) else { return goto_target };
+                let record =
+                    Self::record(tcx, format!("{prefix}_{ty_name}"), ptr.dereference(), loc);
+                return Stmt::block(vec![record, goto_target], loc);
+            }
+        };
+        let Some(elem_name) = recorded_type_name(elem_ty) else { return goto_target };
+        let data = ptr.clone().member("data", &tcx.symbol_table);
+        let len = ptr.member("len", &tcx.symbol_table);
+        let mut stmts = vec![Self::record(tcx, format!("{prefix}_{kind}"), len.clone(), loc)];
+        for i in 0..MAX_RECORDED_ELEMENTS {
+            let i_expr = Expr::int_constant(i, len.typ().clone());
+            let elem = data.clone().plus(i_expr.clone()).dereference();
+            let record = Self::record(tcx, format!("{prefix}_{i}_{elem_name}"), elem, loc);
+            stmts.push(Stmt::if_then_else(i_expr.lt(len.clone()), record, None, loc));
+        }
+        stmts.push(goto_target);
+        Stmt::block(stmts, loc)
+    }
+}
+
+/// A hook for Kani's `record_fmt_message` function (declared in `library/kani/src/lib.rs`), which
+/// our standard library overrides call when the message of a failing assertion or panic is a
+/// stringified format string with arguments. The driver only renders such messages.
+///
+/// This compiles `record_fmt_message()` to an assignment at the location of the outermost macro
+/// call (see [`RecordValue`]):
+/// ```c
+/// __kani_fmt_message = nondet();
+/// assume(__kani_fmt_message);
+/// ```
+struct RecordFmtMessage;
+
+impl<'tcx> GotocHook<'tcx> for RecordFmtMessage {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniRecordFmtMessage")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert!(fargs.is_empty());
+        let loc = tcx.codegen_caller_span(&span);
+        let var =
+            tcx.ensure_global_var("__kani_fmt_message", false, Type::bool(), loc, |_, _| None);
+        Stmt::block(
+            vec![
+                var.clone().assign(Type::bool().nondet(), loc),
+                tcx.codegen_assume(var, loc),
+                Stmt::goto(tcx.current_fn().find_label(&target.unwrap()), loc),
+            ],
+            loc,
+        )
+    }
+}
+
+/// The prefix of the IDs of the output statements of `kani::log!`, which the driver looks for in
+/// the traces.
+const LOG_OUTPUT_PREFIX: &str = "[KANI_LOG] ";
+
+/// A hook for Kani's `log_message` function (declared in `library/kani/src/lib.rs`), which is
+/// called by the `kani::log!` macro after recording the arguments of the message.
+///
+/// This compiles `log_message(msg)` to an output statement, which CBMC includes in the traces
+/// with the given ID:
+/// ```c
+/// __CPROVER_output("[KANI_LOG] msg", 0);
+/// ```
+/// The driver prints the logged messages of the traces of failed checks and satisfied covers.
+struct Log;
+
+impl<'tcx> GotocHook<'tcx> for Log {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniLog")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let msg = tcx.extract_const_message(&fargs.remove(0)).unwrap();
+        let target = target.unwrap();
+        let caller_loc = tcx.codegen_caller_span(&span);
+        let output_id = Expr::string_constant(format!("{LOG_OUTPUT_PREFIX}{msg}"));
+        // CBMC requires at least one value to output.
+        let value = Expr::int_constant(0, Type::c_int());
+        Stmt::block(
+            vec![
+                BuiltinFn::CProverOutput
+                    .call(vec![output_id, value], caller_loc)
+                    .as_stmt(caller_loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
+pub fn fn_hooks<'tcx>(queries: &QueryDb) -> GotocHooks<'tcx> {
+    let mut hooks: Vec<Rc<dyn GotocHook<'tcx> + 'tcx>> = vec![
+        Rc::new(Panic),
+        Rc::new(Assume),
+        Rc::new(Assert),
+        Rc::new(Cover),
+        Rc::new(ExpectFail),
+        Rc::new(Nondet),
+        Rc::new(Havoc),
+        Rc::new(HavocSlice),
+        Rc::new(HavocRaw),
+        Rc::new(RustAlloc),
+        Rc::new(SliceFromRawPart),
+        Rc::new(MemCmp),
+        Rc::new(Log),
+        Rc::new(RecordValue { diagnostic_item: "KaniRecordFmtArg", kind: "fmt_arg" }),
+        Rc::new(RecordValue { diagnostic_item: "KaniRecordAssertOperand", kind: "assert_operand" }),
+        Rc::new(RecordFmtMessage),
+    ];
+    if queries.get_default_models() {
+        hooks.push(Rc::new(GetRandom));
     }
+    GotocHooks { hooks }
 }
 
 pub struct GotocHooks<'tcx> {
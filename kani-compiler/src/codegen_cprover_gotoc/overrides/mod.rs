@@ -5,7 +5,10 @@
 //! For example, we the Kani provides pseudo-functions, such as kani::assume().
 //! These functions should not be codegenned as MIR.
 //! Instead, we use a "hook" to generate the correct CBMC intrinsic.
+//! Calls to some `unsafe fn`s of the standard library are also preceded by checks of their safety
+//! preconditions.
 
 mod hooks;
+mod preconditions;
 
 pub use hooks::{fn_hooks, GotocHooks};
@@ -0,0 +1,157 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module checks the documented safety preconditions of commonly used `unsafe fn`s of the
+//! standard library when user code (i.e., the local crate) calls them. Without these checks, Kani
+//! only reports a violation if it eventually causes a memory safety error.
+//!
+//! The preconditions are asserted at the call site, before the call, which is then generated as
+//! usual. The functions are identified by the type (for methods) or module (for free functions)
+//! that owns them, e.g., `slice::get_unchecked` or `str::from_utf8_unchecked`.
+
+use crate::codegen_cprover_gotoc::codegen::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
+use rustc_hir::def::DefKind;
+use rustc_middle::mir::Operand;
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::{self, Instance};
+use rustc_span::def_id::DefId;
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Asserts the safety preconditions of a call to `instance` with the arguments `fargs`, if
+    /// it's one of the functions we check and the caller is in user code.
+    pub fn codegen_safety_preconditions(
+        &mut self,
+        instance: Instance<'tcx>,
+        fargs: &[Expr],
+        args: &[Operand<'tcx>],
+        loc: Location,
+    ) -> Stmt {
+        if !self.current_fn().instance().def_id().is_local() {
+            return Stmt::skip(loc);
+        }
+        let Some(name) = self.std_fn_name(instance.def_id()) else {
+            return Stmt::skip(loc);
+        };
+        let Some(precondition) = self.safety_precondition(&name, fargs, args) else {
+            return Stmt::skip(loc);
+        };
+        let msg = format!("safety precondition of `{name}` violated");
+        self.codegen_assert_assume(precondition, PropertyClass::SafetyCheck, &msg, loc)
+    }
+
+    /// The name of a function of the standard library, prefixed by the type or module that
+    /// owns it (e.g., `slice::get_unchecked` or `NonNull::new_unchecked`).
+    fn std_fn_name(&self, def_id: DefId) -> Option<String> {
+        if !matches!(self.tcx.crate_name(def_id.krate).as_str(), "core" | "alloc" | "std")
+            || !matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+        {
+            return None;
+        }
+        let owner = if let Some(impl_id) = self.tcx.impl_of_method(def_id) {
+            match self.tcx.type_of(impl_id).kind() {
+                ty::Slice(_) => "slice".to_string(),
+                ty::Str => "str".to_string(),
+                ty::Char => "char".to_string(),
+                ty::Adt(adt, _) => self.tcx.item_name(adt.did()).to_string(),
+                _ => return None,
+            }
+        } else {
+            // Free functions are exported by the top-level module of their crate.
+            self.tcx.def_path(def_id).data.first()?.data.get_opt_name()?.to_string()
+        };
+        Some(format!("{owner}::{}", self.tcx.item_name(def_id)))
+    }
+
+    /// The safety precondition of the function `name`, or `None` if we don't check it.
+    fn safety_precondition(
+        &mut self,
+        name: &str,
+        fargs: &[Expr],
+        args: &[Operand<'tcx>],
+    ) -> Option<Expr> {
+        match name {
+            "slice::get_unchecked" | "slice::get_unchecked_mut" => {
+                let len = fargs[0].clone().member("len", &self.symbol_table);
+                let index_ty = self.operand_ty(&args[1]);
+                let field = |name: &str| fargs[1].clone().member(name, &self.symbol_table);
+                let lang_items = self.tcx.lang_items();
+                match index_ty.kind() {
+                    ty::Uint(ty::UintTy::Usize) => Some(fargs[1].clone().lt(len)),
+                    ty::Adt(adt, _) if Some(adt.did()) == lang_items.range_struct() => {
+                        let end = field("end");
+                        Some(field("start").le(end.clone()).and(end.le(len)))
+                    }
+                    ty::Adt(adt, _) if Some(adt.did()) == lang_items.range_from_struct() => {
+                        Some(field("start").le(len))
+                    }
+                    ty::Adt(adt, _) if Some(adt.did()) == lang_items.range_to_struct() => {
+                        Some(field("end").le(len))
+                    }
+                    ty::Adt(adt, _) if Some(adt.did()) == lang_items.range_inclusive_struct() => {
+                        // `start..=end` is `start..end + 1`, or an empty range if it's exhausted.
+                        let end = field("end");
+                        let exclusive_end =
+                            end.clone().plus(Expr::int_constant(1, len.typ().clone()));
+                        let start_ok = field("exhausted")
+                            .cast_to(Type::bool())
+                            .or(field("start").le(exclusive_end));
+                        Some(end.lt(len).and(start_ok))
+                    }
+                    _ => None,
+                }
+            }
+            "slice::from_raw_parts" | "slice::from_raw_parts_mut" => {
+                // The pointer must be non-null, and the slice must not be larger than
+                // `isize::MAX` bytes. Its alignment is checked with the other alignment checks
+                // (see `alignment.rs`).
+                let data_ty = self.operand_ty(&args[0]);
+                let elem_size = self.layout_of(data_ty.builtin_deref(true)?.ty).size.bytes();
+                let mut precondition = fargs[0].clone().is_nonnull();
+                if elem_size > 0 {
+                    let isize_max = (1u64 << (self.tcx.data_layout.pointer_size.bits() - 1)) - 1;
+                    let max_len = Expr::int_constant(isize_max / elem_size, Type::size_t());
+                    precondition = precondition.and(fargs[1].clone().le(max_len));
+                }
+                Some(precondition)
+            }
+            "str::from_utf8_unchecked" | "str::from_utf8_unchecked_mut" => {
+                let data = fargs[0].clone().member("data", &self.symbol_table);
+                let len = fargs[0].clone().member("len", &self.symbol_table);
+                Some(self.codegen_is_utf8(data, len))
+            }
+            "char::from_u32_unchecked" => {
+                let value = fargs[0].clone();
+                let int = |i: u32| Expr::int_constant(i, Type::unsigned_int(32));
+                let is_surrogate = value.clone().ge(int(0xD800)).and(value.clone().le(int(0xDFFF)));
+                Some(value.le(int(0x10FFFF)).and(is_surrogate.not()))
+            }
+            "NonNull::new_unchecked" => {
+                let ptr_ty = self.operand_ty(&args[0]);
+                let ptr = if self.is_fat_pointer(ptr_ty) {
+                    fargs[0].clone().member("data", &self.symbol_table)
+                } else {
+                    fargs[0].clone()
+                };
+                Some(ptr.is_nonnull())
+            }
+            _ if name.starts_with("NonZero") && name.ends_with("::new_unchecked") => {
+                Some(fargs[0].clone().is_zero().not())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the `len` bytes at `data` are valid UTF-8 (see `__KANI_is_utf8` in `kani_lib.c`).
+    fn codegen_is_utf8(&mut self, data: Expr, len: Expr) -> Expr {
+        let function = "__KANI_is_utf8";
+        let args = vec![data.cast_to(Type::unsigned_int(8).to_pointer()), len];
+        let param_types = args.iter().map(|arg| arg.typ().clone()).collect();
+        let typ = Type::code_with_unnamed_parameters(param_types, Type::bool());
+        let func = self
+            .ensure(function, |_, name| Symbol::function(name, typ, None, name, Location::none()))
+            .to_expr();
+        func.call(args)
+    }
+}
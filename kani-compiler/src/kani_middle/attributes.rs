@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! This module contains code for processing Rust attributes (like `kani::proof`).
 
-use rustc_ast::{AttrKind, Attribute, LitKind, MetaItem};
+use rustc_ast::token::{BinOpToken, Token, TokenKind};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast::{AttrKind, Attribute, LitKind, MacArgs};
+use rustc_ast_pretty::pprust;
 
 /// Partition all the attributes into two buckets, proof_attributes and other_attributes
 pub fn partition_kanitool_attributes(
@@ -26,6 +29,11 @@ pub fn partition_kanitool_attributes(
     (proof_attributes, other_attributes)
 }
 
+/// Whether the attributes include `kanitool::<name>`
+pub fn has_kanitool_attribute(all_attributes: &[Attribute], name: &str) -> bool {
+    all_attributes.iter().any(|attr| kanitool_attr_name(attr).as_deref() == Some(name))
+}
+
 /// Extracts the integer value argument from the attribute provided
 /// For example, `unwind(8)` return `Some(8)`
 pub fn extract_integer_argument(attr: &Attribute) -> Option<u128> {
@@ -48,36 +56,63 @@ pub fn extract_integer_argument(attr: &Attribute) -> Option<u128> {
 /// Extracts a vector with the path arguments of an attribute.
 /// The length of the returned vector is equal to the number of arguments in the
 /// attribute; an entry is `None` if the argument is not syntactically a path,
-/// and `Some(<path>)` otherwise. Paths are returned as strings.
+/// and `Some(<path>)` otherwise. Paths are returned as strings, and may contain
+/// generic arguments and a qualified self type (e.g., `<T as Trait>::method`).
 ///
 /// For example, on `stub(foo::bar, 42, baz)`, this returns
 /// `vec![Some("foo::bar"), None, Some("baz")]`.
 pub fn extract_path_arguments(attr: &Attribute) -> Vec<Option<String>> {
-    let attr_args = attr.meta_item_list();
-    if attr_args.is_none() {
+    let MacArgs::Delimited(_, _, tokens) = &attr.get_normal_item().args else {
         return vec![];
+    };
+    split_arguments(tokens).into_iter().map(extract_path).collect()
+}
+
+/// Splits the tokens of the arguments of an attribute at the commas that are
+/// not nested in generic arguments (or in delimiters, which are single token
+/// trees).
+fn split_arguments(tokens: &TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut args = vec![];
+    let mut current = vec![];
+    let mut angle_depth: usize = 0;
+    for tree in tokens.trees() {
+        if let TokenTree::Token(token, _) = tree {
+            match token.kind {
+                TokenKind::Lt => angle_depth += 1,
+                TokenKind::BinOp(BinOpToken::Shl) => angle_depth += 2,
+                TokenKind::Gt => angle_depth = angle_depth.saturating_sub(1),
+                TokenKind::BinOp(BinOpToken::Shr) => angle_depth = angle_depth.saturating_sub(2),
+                TokenKind::Comma if angle_depth == 0 => {
+                    args.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        current.push(tree.clone());
     }
-    let mut paths = Vec::new();
-    for arg in attr_args.unwrap() {
-        let entry = arg.meta_item().and_then(extract_path);
-        paths.push(entry)
+    if !current.is_empty() {
+        args.push(current);
     }
-    paths
+    args
 }
 
-/// Extracts a path from an attribute item, returning `None` if the item is not
-/// syntactically a path.
-fn extract_path(meta_item: &MetaItem) -> Option<String> {
-    if meta_item.is_word() {
-        Some(
-            meta_item
-                .path
-                .segments
-                .iter()
-                .map(|seg| seg.ident.as_str())
-                .collect::<Vec<&str>>()
-                .join("::"),
-        )
+/// Extracts a path from the tokens of an attribute argument, returning `None`
+/// if the argument is not syntactically a path.
+fn extract_path(tokens: Vec<TokenTree>) -> Option<String> {
+    let starts_like_path = matches!(
+        tokens.first(),
+        Some(TokenTree::Token(
+            Token { kind: TokenKind::Ident(..) | TokenKind::ModSep | TokenKind::Lt, .. },
+            _
+        ))
+    );
+    let has_literal = tokens.iter().any(|tree| match tree {
+        TokenTree::Token(token, _) => matches!(token.kind, TokenKind::Literal(_)),
+        TokenTree::Delimited(..) => false,
+    });
+    if starts_like_path && !has_literal {
+        Some(pprust::tts_to_string(&TokenStream::new(tokens)))
     } else {
         None
     }
@@ -5,6 +5,7 @@
 pub mod attributes;
 pub mod coercion;
 pub mod provide;
+pub mod ptr_fns;
 pub mod reachability;
 pub mod resolve;
 pub mod stubbing;
@@ -0,0 +1,105 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module finds the functions of `core::ptr` and the methods of raw pointers that access
+//! the memory behind a pointer (e.g., `ptr::read` or `<*const T>::read_unaligned`), and the
+//! functions of `core::slice` that create a slice from a raw pointer, so that codegen can
+//! recognize calls to them without comparing paths.
+
+use rustc_ast::Mutability;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::fast_reject::SimplifiedTypeGen;
+use rustc_middle::ty::{DefIdTree, TyCtxt};
+use rustc_span::symbol::sym;
+
+/// The names of the functions (and methods of `*const T` and `*mut T`) that read a value.
+const READ_FNS: [&str; 3] = ["read", "read_unaligned", "read_volatile"];
+/// The names of the functions (and methods) that require an aligned pointer to read or write a
+/// value.
+const ALIGNED_ACCESS_FNS: [&str; 2] = ["read", "write"];
+/// The names of the functions of `core::slice` that create a slice from a raw pointer.
+const SLICE_FROM_RAW_PARTS_FNS: [&str; 2] = ["from_raw_parts", "from_raw_parts_mut"];
+
+/// The functions that access the memory behind a raw pointer, with their names as we print them
+/// in the messages of checks (e.g., `ptr::read` or `<*mut T>::write`).
+#[derive(Debug, Default)]
+pub struct PtrFns {
+    reads: FxHashMap<DefId, String>,
+    aligned_accesses: FxHashMap<DefId, String>,
+    slice_from_raw_parts: FxHashSet<DefId>,
+}
+
+impl PtrFns {
+    pub fn new(tcx: TyCtxt) -> PtrFns {
+        PtrFns {
+            reads: ptr_fns(tcx, &READ_FNS),
+            aligned_accesses: ptr_fns(tcx, &ALIGNED_ACCESS_FNS),
+            slice_from_raw_parts: slice_fns(tcx, &SLICE_FROM_RAW_PARTS_FNS),
+        }
+    }
+
+    /// The name of `def_id`, if it's one of the `read` functions of `core::ptr`, or a `read`
+    /// method of a raw pointer.
+    pub fn read_name(&self, def_id: DefId) -> Option<&str> {
+        self.reads.get(&def_id).map(String::as_str)
+    }
+
+    /// The name of `def_id`, if it's `ptr::read`, `ptr::write` or one of the equivalent methods
+    /// of a raw pointer, which require the pointer to be aligned.
+    pub fn aligned_access_name(&self, def_id: DefId) -> Option<&str> {
+        self.aligned_accesses.get(&def_id).map(String::as_str)
+    }
+
+    /// Whether `def_id` is `slice::from_raw_parts` or `slice::from_raw_parts_mut`.
+    pub fn is_slice_from_raw_parts(&self, def_id: DefId) -> bool {
+        self.slice_from_raw_parts.contains(&def_id)
+    }
+}
+
+/// Finds the functions of `core::ptr` and the methods of `*const T` and `*mut T` with one of the
+/// given names.
+fn ptr_fns(tcx: TyCtxt, names: &[&'static str]) -> FxHashMap<DefId, String> {
+    let mut fns = FxHashMap::default();
+    // `core::ptr` itself has no diagnostic item, so we find it through `ptr::null`.
+    if let Some(ptr_mod) = tcx.get_diagnostic_item(sym::ptr_null).map(|id| tcx.parent(id)) {
+        for (def_id, name) in module_fns(tcx, ptr_mod, names) {
+            fns.insert(def_id, format!("ptr::{name}"));
+        }
+    }
+    for (mutability, ptr_ty) in [(Mutability::Not, "*const T"), (Mutability::Mut, "*mut T")] {
+        for impl_ in tcx.incoherent_impls(SimplifiedTypeGen::PtrSimplifiedType(mutability)) {
+            for def_id in tcx.associated_item_def_ids(*impl_) {
+                let name = tcx.item_name(*def_id);
+                if names.contains(&name.as_str()) {
+                    fns.insert(*def_id, format!("<{ptr_ty}>::{name}"));
+                }
+            }
+        }
+    }
+    fns
+}
+
+/// Finds the functions of `core::slice` with one of the given names.
+fn slice_fns(tcx: TyCtxt, names: &[&'static str]) -> FxHashSet<DefId> {
+    // `core::slice` has no diagnostic item, so we find it through the `impl<T> [T]` block of the
+    // `len` method.
+    let Some(slice_mod) = tcx.lang_items().slice_len_fn().map(|id| tcx.parent(tcx.parent(id)))
+    else {
+        return FxHashSet::default();
+    };
+    module_fns(tcx, slice_mod, names).into_iter().map(|(def_id, _)| def_id).collect()
+}
+
+/// The functions (including the re-exported ones) of a module with one of the given names.
+fn module_fns(tcx: TyCtxt, module: DefId, names: &[&'static str]) -> Vec<(DefId, &'static str)> {
+    tcx.module_children(module)
+        .iter()
+        .filter_map(|child| match child.res {
+            Res::Def(DefKind::Fn, def_id) => {
+                names.iter().find(|name| child.ident.as_str() == **name).map(|name| (def_id, *name))
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -33,7 +33,7 @@
 };
 
 use crate::kani_middle::coercion;
-use crate::kani_middle::stubbing::get_stub;
+use crate::kani_middle::stubbing::{get_stub, stub_instance};
 
 /// Collect all reachable items starting from the given starting points.
 pub fn collect_reachable_items<'tcx>(
@@ -318,6 +318,8 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
                         substs,
                     )
                     .unwrap();
+                    let span = self.body.source_info(location).span;
+                    let instance = stub_instance(self.tcx, instance, Some(span));
                     self.collect_instance(instance, false);
                 } else {
                     unreachable!("Expected FnDef type, but got: {:?}", fn_ty);
@@ -438,7 +440,11 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                                 panic!("unable to resolve call to `{callee}` in `{caller}`")
                             }
                         }
-                        Some(instance) => self.collect_instance(instance, true),
+                        Some(instance) => {
+                            let span = terminator.source_info.span;
+                            let instance = stub_instance(tcx, instance, Some(span));
+                            self.collect_instance(instance, true)
+                        }
                     };
                 } else {
                     assert!(
@@ -1,31 +1,779 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-//! This module contains code for resolving strings representing simple paths to
-//! `DefId`s for functions and methods. For the definition of a simple path, see
-//! <https://doc.rust-lang.org/reference/paths.html#simple-paths>.
-//!
-//! TODO: Extend this logic to support resolving qualified paths.
-//! <https://github.com/model-checking/kani/issues/1997>
+//! This module contains code for resolving strings representing paths to
+//! `DefId`s for functions and methods. Besides simple paths (see
+//! <https://doc.rust-lang.org/reference/paths.html#simple-paths>), we support
+//! paths with a qualified self type, such as `<MyClock as Clock>::now`, and
+//! paths with generic arguments, such as `Vec::<u8>::push`. Generic arguments
+//! restrict the path to the instances of the function/method whose generic
+//! arguments match.
 
 use std::collections::VecDeque;
+use std::fmt;
 
+use rustc_ast::Mutability;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{DefId, LocalDefId, CRATE_DEF_INDEX};
-use rustc_hir::ItemKind;
-use rustc_middle::ty::TyCtxt;
-
-/// Attempts to resolve a simple path (in the form of a string) to a `DefId`.
-/// The current module is provided as an argument in order to resolve relative
-/// paths.
-///
-/// TODO: Extend this implementation to handle qualified paths and simple paths
-/// corresponding to trait methods.
-/// <https://github.com/model-checking/kani/issues/1997>
-pub fn resolve_path(tcx: TyCtxt, current_module: LocalDefId, path_str: &str) -> Option<DefId> {
+use rustc_hir::{ItemKind, UseKind};
+use rustc_middle::ty::fast_reject::SimplifiedTypeGen;
+use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
+use rustc_middle::ty::{GenericParamDefKind, Ty, TyCtxt, TyKind};
+use serde::{Deserialize, Serialize};
+
+/// The function/method that a path resolves to.
+#[derive(Debug)]
+pub struct ResolvedFn {
+    pub def_id: DefId,
+    /// A pattern for each generic argument of `def_id` (including the ones of its parent, e.g.
+    /// the `impl` block of a method), if the path only refers to some of its instances.
+    pub generic_args: Option<Vec<TyPattern>>,
+}
+
+/// Attempts to resolve a path (in the form of a string) to a function or
+/// method. The current module is provided as an argument in order to resolve
+/// relative paths. Returns an error message if the path cannot be resolved.
+pub fn resolve_path(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    path_str: &str,
+) -> Result<ResolvedFn, String> {
     let span = tracing::span!(tracing::Level::DEBUG, "path_resolution");
     let _enter = span.enter();
 
-    let path = to_path(tcx, current_module, path_str)?;
+    let path = FnPath::parse(path_str).ok_or_else(|| format!("invalid path: {path_str}"))?;
+    let resolved = match &path {
+        FnPath::Path(segments) => resolve_fn(tcx, current_module, segments),
+        FnPath::Qualified { self_ty: TyPath::Path(type_segments), trait_: None, method } => {
+            let mut segments = type_segments.clone();
+            segments.push(method.clone());
+            resolve_fn(tcx, current_module, &segments)
+        }
+        FnPath::Qualified { self_ty, trait_: None, .. } => {
+            Err(Some(format!("methods of `{self_ty}` can only be stubbed through a trait")))
+        }
+        FnPath::Qualified { self_ty, trait_: Some(trait_), method } => {
+            resolve_trait_impl_method(tcx, current_module, self_ty, trait_, method)
+        }
+    };
+    resolved.map_err(|reason| {
+        reason.unwrap_or_else(|| format!("unable to resolve function/method: {path}"))
+    })
+}
+
+/// The error of a resolution step: either a message that explains why a path is invalid, or
+/// `None` if the path simply doesn't refer to anything.
+type ResolveResult<T> = Result<T, Option<String>>;
+
+/// A pattern for a type, used to select the instances of a function/method by their generic
+/// arguments. Types are identified by their `DefPathHash` (as a pair of `u64`s), so that
+/// patterns can be passed to another compiler session.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TyPattern {
+    /// Matches any type; written as `_`, and used for the generic arguments that are omitted.
+    Any,
+    /// A primitive type, such as `u8` or `str`.
+    Primitive(String),
+    /// A struct, enum or union, with patterns for (a prefix of) its generic type arguments.
+    Adt((u64, u64), Vec<TyPattern>),
+    Ref(bool, Box<TyPattern>),
+    Slice(Box<TyPattern>),
+    Tuple(Vec<TyPattern>),
+}
+
+impl TyPattern {
+    /// Does `ty` match this pattern? A generic parameter in `ty` matches any pattern.
+    pub fn matches<'tcx>(&self, tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+        match (self, ty.kind()) {
+            (TyPattern::Any, _) | (_, TyKind::Param(_)) => true,
+            (TyPattern::Primitive(name), _) => {
+                (ty.is_primitive() || ty.is_str()) && ty.to_string() == *name
+            }
+            (TyPattern::Adt(hash, patterns), TyKind::Adt(adt_def, substs)) => {
+                def_path_hash_parts(tcx, adt_def.did()) == *hash
+                    && patterns.iter().zip(substs.types()).all(|(p, ty)| p.matches(tcx, ty))
+            }
+            (TyPattern::Ref(mutable, pattern), TyKind::Ref(_, ty, mutability)) => {
+                *mutable == (*mutability == Mutability::Mut) && pattern.matches(tcx, *ty)
+            }
+            (TyPattern::Slice(pattern), TyKind::Slice(ty)) => pattern.matches(tcx, *ty),
+            (TyPattern::Tuple(patterns), TyKind::Tuple(tys)) => {
+                patterns.len() == tys.len()
+                    && patterns.iter().zip(tys.iter()).all(|(p, ty)| p.matches(tcx, ty))
+            }
+            _ => false,
+        }
+    }
+
+    /// Do the generic arguments `substs` of an instance match the patterns?
+    pub fn matches_substs<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        patterns: &[TyPattern],
+        substs: SubstsRef<'tcx>,
+    ) -> bool {
+        patterns.len() == substs.len()
+            && patterns.iter().zip(substs.iter()).all(|(pattern, arg)| match arg.unpack() {
+                GenericArgKind::Type(ty) => pattern.matches(tcx, ty),
+                _ => *pattern == TyPattern::Any,
+            })
+    }
+}
+
+fn def_path_hash_parts(tcx: TyCtxt, def_id: DefId) -> (u64, u64) {
+    tcx.def_path_hash(def_id).0.as_value()
+}
+
+/// The names of the primitive types, which we resolve without looking for an item.
+const PRIMITIVE_TYPES: &[&str] = &[
+    "bool", "char", "str", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+    "u32", "u64", "u128", "usize",
+];
+
+/// A path to a function/method, as written in a `kani::stub` attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FnPath {
+    /// A path like `foo::bar` or `Vec::<u8>::push`.
+    Path(Vec<Segment>),
+    /// A path with a qualified self type, like `<MyClock as Clock>::now`.
+    Qualified { self_ty: TyPath, trait_: Option<Vec<Segment>>, method: Segment },
+}
+
+/// A segment of a path, with its generic arguments (if any).
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Segment {
+    name: String,
+    args: Vec<TyPath>,
+}
+
+/// A type, as written in a path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TyPath {
+    Infer,
+    Ref(bool, Box<TyPath>),
+    Slice(Box<TyPath>),
+    Tuple(Vec<TyPath>),
+    Path(Vec<Segment>),
+}
+
+/// rustc represents initial `::` as `{{root}}`.
+const ROOT: &str = "{{root}}";
+
+impl FnPath {
+    fn parse(path_str: &str) -> Option<FnPath> {
+        let mut parser = Parser { tokens: tokenize(path_str)?, pos: 0 };
+        let path = parser.fn_path()?;
+        (parser.pos == parser.tokens.len()).then_some(path)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    PathSep,
+    Lt,
+    Gt,
+    Comma,
+    Amp,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+}
+
+fn tokenize(path_str: &str) -> Option<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = path_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            ':' if chars.next_if_eq(&':').is_some() => Token::PathSep,
+            '<' => Token::Lt,
+            '>' => Token::Gt,
+            ',' => Token::Comma,
+            '&' => Token::Amp,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    ident.push(c);
+                }
+                Token::Ident(ident)
+            }
+            _ => return None,
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+/// A recursive descent parser for the paths we support.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let matches = self.peek() == Some(token);
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+
+    fn expect(&mut self, token: &Token) -> Option<()> {
+        self.eat(token).then_some(())
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.eat(&Token::Ident(keyword.to_string()))
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(Token::Ident(ident)) => {
+                let ident = ident.clone();
+                self.pos += 1;
+                Some(ident)
+            }
+            _ => None,
+        }
+    }
+
+    fn fn_path(&mut self) -> Option<FnPath> {
+        if self.eat(&Token::Lt) {
+            let self_ty = self.ty()?;
+            let trait_ = if self.eat_keyword("as") { Some(self.path()?) } else { None };
+            self.expect(&Token::Gt)?;
+            self.expect(&Token::PathSep)?;
+            let method = self.segment()?;
+            Some(FnPath::Qualified { self_ty, trait_, method })
+        } else {
+            self.path().map(FnPath::Path)
+        }
+    }
+
+    fn path(&mut self) -> Option<Vec<Segment>> {
+        let mut segments = vec![];
+        if self.eat(&Token::PathSep) {
+            segments.push(Segment { name: ROOT.to_string(), args: vec![] });
+        }
+        loop {
+            segments.push(self.segment()?);
+            if !self.eat(&Token::PathSep) {
+                return Some(segments);
+            }
+        }
+    }
+
+    /// Parses a segment with optional generic arguments, which may be written as `name::<..>`
+    /// or `name<..>`.
+    fn segment(&mut self) -> Option<Segment> {
+        let name = self.ident()?;
+        let has_args = self.peek() == Some(&Token::Lt)
+            || (self.peek() == Some(&Token::PathSep)
+                && self.tokens.get(self.pos + 1) == Some(&Token::Lt));
+        let mut args = vec![];
+        if has_args {
+            self.eat(&Token::PathSep);
+            self.expect(&Token::Lt)?;
+            args = self.ty_list(&Token::Gt)?;
+        }
+        Some(Segment { name, args })
+    }
+
+    /// Parses a comma separated list of types up to the `end` token.
+    fn ty_list(&mut self, end: &Token) -> Option<Vec<TyPath>> {
+        let mut tys = vec![];
+        while !self.eat(end) {
+            tys.push(self.ty()?);
+            if !self.eat(&Token::Comma) {
+                self.expect(end)?;
+                break;
+            }
+        }
+        Some(tys)
+    }
+
+    fn ty(&mut self) -> Option<TyPath> {
+        match self.peek()? {
+            Token::Amp => {
+                self.pos += 1;
+                let mutable = self.eat_keyword("mut");
+                Some(TyPath::Ref(mutable, Box::new(self.ty()?)))
+            }
+            Token::LBracket => {
+                self.pos += 1;
+                let ty = self.ty()?;
+                self.expect(&Token::RBracket)?;
+                Some(TyPath::Slice(Box::new(ty)))
+            }
+            Token::LParen => {
+                self.pos += 1;
+                let mut tys = self.ty_list(&Token::RParen)?;
+                // `(T)` is just `T`, whereas `(T,)` is a tuple.
+                if tys.len() == 1 && self.tokens[self.pos - 2] != Token::Comma {
+                    Some(tys.remove(0))
+                } else {
+                    Some(TyPath::Tuple(tys))
+                }
+            }
+            Token::Ident(ident) if ident == "_" => {
+                self.pos += 1;
+                Some(TyPath::Infer)
+            }
+            _ => self.path().map(TyPath::Path),
+        }
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.name != ROOT {
+            write!(f, "{}", self.name)?;
+        }
+        if !self.args.is_empty() {
+            write!(f, "::<{}>", join(&self.args))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TyPath::Infer => write!(f, "_"),
+            TyPath::Ref(mutable, ty) => write!(f, "&{}{ty}", if *mutable { "mut " } else { "" }),
+            TyPath::Slice(ty) => write!(f, "[{ty}]"),
+            TyPath::Tuple(tys) if tys.len() == 1 => write!(f, "({},)", tys[0]),
+            TyPath::Tuple(tys) => write!(f, "({})", join(tys)),
+            TyPath::Path(segments) => write!(f, "{}", join_segments(segments)),
+        }
+    }
+}
+
+impl fmt::Display for FnPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FnPath::Path(segments) => write!(f, "{}", join_segments(segments)),
+            FnPath::Qualified { self_ty, trait_: None, method } => {
+                write!(f, "<{self_ty}>::{method}")
+            }
+            FnPath::Qualified { self_ty, trait_: Some(trait_), method } => {
+                write!(f, "<{self_ty} as {}>::{method}", join_segments(trait_))
+            }
+        }
+    }
+}
+
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    items.iter().map(T::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn join_segments(segments: &[Segment]) -> String {
+    segments.iter().map(Segment::to_string).collect::<Vec<_>>().join("::")
+}
+
+fn names(segments: &[Segment]) -> Segments {
+    segments.iter().map(|segment| segment.name.clone()).collect()
+}
+
+/// Resolves a path without a qualified self type to a function, or to a method of a type or
+/// trait.
+fn resolve_fn(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    segments: &[Segment],
+) -> ResolveResult<ResolvedFn> {
+    let (method, prefix) = segments.split_last().unwrap();
+    if let Some(def_id) = resolve_item(tcx, current_module, names(segments)) {
+        if tcx.def_kind(def_id) == DefKind::Fn {
+            let mut generic_args = GenericArgs::new(tcx, def_id);
+            generic_args.set_own_args(tcx, current_module, def_id, &method.args)?;
+            return Ok(ResolvedFn { def_id, generic_args: generic_args.finish() });
+        }
+    }
+    let Some(owner) = prefix.last() else { return Err(None) };
+    let owner_id = resolve_type_or_trait(tcx, current_module, names(prefix)).ok_or(None)?;
+    if tcx.def_kind(owner_id) == DefKind::Trait {
+        let trait_args = resolve_ty_args(tcx, current_module, owner_id, &owner.args)?;
+        resolve_trait_method(tcx, current_module, owner_id, TyPattern::Any, &trait_args, method)
+    } else {
+        let type_args = resolve_ty_args(tcx, current_module, owner_id, &owner.args)?;
+        let self_pattern = TyPattern::Adt(def_path_hash_parts(tcx, owner_id), type_args);
+        resolve_method_of_type(tcx, current_module, owner_id, &self_pattern, method)
+    }
+}
+
+/// Resolves a method of a struct, enum or union, which can be defined in an inherent `impl`
+/// block, or by a trait in scope that the type implements.
+fn resolve_method_of_type(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    type_id: DefId,
+    self_pattern: &TyPattern,
+    method: &Segment,
+) -> ResolveResult<ResolvedFn> {
+    let name = &method.name;
+    tracing::debug!("Resolving `{name}` in type `{}`", tcx.def_path_str(type_id));
+    for impl_ in tcx.inherent_impls(type_id) {
+        if let Some(def_id) = resolve_in_impl(tcx, *impl_, name) {
+            let mut generic_args = GenericArgs::new(tcx, def_id);
+            generic_args.unify(tcx.type_of(*impl_), self_pattern);
+            generic_args.set_own_args(tcx, current_module, def_id, &method.args)?;
+            return Ok(ResolvedFn { def_id, generic_args: generic_args.finish() });
+        }
+    }
+
+    // Look for the method in the traits in scope implemented by the type.
+    let mut candidates = vec![];
+    for trait_id in traits_in_scope(tcx, current_module) {
+        let trait_impls = tcx.trait_impls_of(trait_id);
+        let impls =
+            trait_impls.non_blanket_impls().get(&SimplifiedTypeGen::AdtSimplifiedType(type_id));
+        for impl_ in impls.into_iter().flatten() {
+            if resolve_in_impl(tcx, *impl_, name).is_some()
+                || resolve_in_impl(tcx, trait_id, name).is_some()
+            {
+                candidates.push(trait_id);
+                break;
+            }
+        }
+    }
+    match candidates[..] {
+        [] => {
+            tracing::debug!("Unable to resolve `{name}` in type `{}`", tcx.def_path_str(type_id));
+            Err(None)
+        }
+        [trait_id] => {
+            resolve_trait_method(tcx, current_module, trait_id, self_pattern.clone(), &[], method)
+        }
+        _ => Err(Some(format!(
+            "`{}::{name}` is ambiguous: it is provided by the traits {}; use a path like \
+            `<{0} as Trait>::{name}` instead",
+            tcx.def_path_str(type_id),
+            candidates
+                .iter()
+                .map(|id| format!("`{}`", tcx.def_path_str(*id)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Lists the traits in scope in a local module, as for method calls: the traits it defines or
+/// imports (including through glob imports), and the traits of the standard library prelude.
+fn traits_in_scope(tcx: TyCtxt, current_module: LocalDefId) -> Vec<DefId> {
+    let mut traits = vec![];
+    for item_id in tcx.hir().module_items(current_module) {
+        let item = tcx.hir().item(item_id);
+        match item.kind {
+            ItemKind::Trait(..) => traits.push(item.owner_id.def_id.to_def_id()),
+            ItemKind::Use(path, UseKind::Single) => {
+                if let Res::Def(DefKind::Trait, def_id) = path.res {
+                    traits.push(def_id);
+                }
+            }
+            ItemKind::Use(path, UseKind::Glob) => {
+                if let Res::Def(DefKind::Mod, def_id) = path.res {
+                    traits.extend(module_traits(tcx, def_id));
+                }
+            }
+            _ => (),
+        }
+    }
+    let prelude = ["std", "prelude", "v1"].into_iter().map(str::to_string).collect();
+    if let Some(prelude_id) = resolve_foreign_module(tcx, prelude) {
+        traits.extend(module_traits(tcx, prelude_id));
+    }
+    traits.sort();
+    traits.dedup();
+    traits
+}
+
+/// Lists the traits that a module defines or re-exports.
+fn module_traits(tcx: TyCtxt, module: DefId) -> Vec<DefId> {
+    match module.as_local() {
+        Some(local_module) => tcx
+            .hir()
+            .module_items(local_module)
+            .filter_map(|item_id| {
+                let item = tcx.hir().item(item_id);
+                match item.kind {
+                    ItemKind::Trait(..) => Some(item.owner_id.def_id.to_def_id()),
+                    ItemKind::Use(path, UseKind::Single) => match path.res {
+                        Res::Def(DefKind::Trait, def_id) => Some(def_id),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            })
+            .collect(),
+        None => tcx
+            .module_children(module)
+            .iter()
+            .filter_map(|child| match child.res {
+                Res::Def(DefKind::Trait, def_id) => Some(def_id),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/// Resolves a path like `<SelfTy as Trait>::method`.
+fn resolve_trait_impl_method(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    self_ty: &TyPath,
+    trait_: &[Segment],
+    method: &Segment,
+) -> ResolveResult<ResolvedFn> {
+    let trait_id = resolve_type_or_trait(tcx, current_module, names(trait_)).ok_or(None)?;
+    if tcx.def_kind(trait_id) != DefKind::Trait {
+        return Err(Some(format!("`{}` is not a trait", join_segments(trait_))));
+    }
+    let self_pattern = resolve_ty(tcx, current_module, self_ty)?;
+    let trait_args = resolve_ty_args(tcx, current_module, trait_id, &trait_.last().unwrap().args)?;
+    resolve_trait_method(tcx, current_module, trait_id, self_pattern, &trait_args, method)
+}
+
+/// Resolves a method of a trait for the types that match `self_pattern`: the method of the
+/// `impl` block for that type if there is a single one that defines the method, or the default
+/// implementation of the method in the trait, restricted to these types.
+fn resolve_trait_method(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    trait_id: DefId,
+    self_pattern: TyPattern,
+    trait_args: &[TyPattern],
+    method: &Segment,
+) -> ResolveResult<ResolvedFn> {
+    let name = &method.name;
+    let trait_name = tcx.def_path_str(trait_id);
+    let Some(trait_method) = resolve_in_impl(tcx, trait_id, name) else {
+        return Err(Some(format!("trait `{trait_name}` has no method `{name}`")));
+    };
+
+    if self_pattern != TyPattern::Any {
+        // Find the implementations of the trait that match the path.
+        let impls: Vec<DefId> = tcx
+            .all_impls(trait_id)
+            .filter(|impl_| {
+                let trait_ref = tcx.impl_trait_ref(*impl_).unwrap();
+                self_pattern.matches(tcx, trait_ref.self_ty())
+                    && trait_args
+                        .iter()
+                        .zip(trait_ref.substs.types().skip(1))
+                        .all(|(pattern, ty)| pattern.matches(tcx, ty))
+            })
+            .collect();
+        // Blanket implementations match every type, so prefer the others.
+        let specific: Vec<DefId> = impls
+            .iter()
+            .copied()
+            .filter(|impl_| {
+                !matches!(tcx.impl_trait_ref(*impl_).unwrap().self_ty().kind(), TyKind::Param(_))
+            })
+            .collect();
+        let impls = if specific.is_empty() { impls } else { specific };
+        match impls[..] {
+            [] => {
+                return Err(Some(format!("no implementation of `{trait_name}` matches the path")));
+            }
+            [impl_] => {
+                if let Some(def_id) = resolve_in_impl(tcx, impl_, name) {
+                    let trait_ref = tcx.impl_trait_ref(impl_).unwrap();
+                    let mut generic_args = GenericArgs::new(tcx, def_id);
+                    generic_args.unify(trait_ref.self_ty(), &self_pattern);
+                    for (ty, pattern) in trait_ref.substs.types().skip(1).zip(trait_args) {
+                        generic_args.unify(ty, pattern);
+                    }
+                    generic_args.set_own_args(tcx, current_module, def_id, &method.args)?;
+                    return Ok(ResolvedFn { def_id, generic_args: generic_args.finish() });
+                }
+            }
+            _ => {
+                return Err(Some(format!(
+                    "the path matches {} implementations of `{trait_name}`: {}",
+                    impls.len(),
+                    impls
+                        .iter()
+                        .map(|impl_| format!("`{}`", tcx.def_path_str(*impl_)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+        }
+    }
+
+    // The method is not defined by the implementation, so we use the default implementation
+    // from the trait.
+    if !tcx.impl_defaultness(trait_method).has_value() {
+        return Err(Some(format!(
+            "`{trait_name}::{name}` has no default implementation; use a path like \
+            `<Type as {trait_name}>::{name}` to refer to the implementation for a type"
+        )));
+    }
+    let mut generic_args = GenericArgs::new(tcx, trait_method);
+    generic_args.patterns[0] = self_pattern;
+    for (pattern, index) in trait_args.iter().zip(type_param_indices(tcx, trait_id)) {
+        generic_args.patterns[index] = pattern.clone();
+    }
+    generic_args.set_own_args(tcx, current_module, trait_method, &method.args)?;
+    Ok(ResolvedFn { def_id: trait_method, generic_args: generic_args.finish() })
+}
+
+/// The patterns for all generic arguments of a function/method, which we build up while
+/// resolving a path.
+struct GenericArgs {
+    patterns: Vec<TyPattern>,
+}
+
+impl GenericArgs {
+    fn new(tcx: TyCtxt, def_id: DefId) -> Self {
+        GenericArgs { patterns: vec![TyPattern::Any; tcx.generics_of(def_id).count()] }
+    }
+
+    /// Sets the patterns of the generic type parameters declared by `def_id` itself (i.e., not
+    /// by its parent), in order.
+    fn set_own_args(
+        &mut self,
+        tcx: TyCtxt,
+        current_module: LocalDefId,
+        def_id: DefId,
+        args: &[TyPath],
+    ) -> ResolveResult<()> {
+        let patterns = resolve_ty_args(tcx, current_module, def_id, args)?;
+        for (pattern, index) in patterns.into_iter().zip(type_param_indices(tcx, def_id)) {
+            self.patterns[index] = pattern;
+        }
+        Ok(())
+    }
+
+    /// Matches the type of a generic definition (e.g., the self type of an `impl` block)
+    /// against a pattern, and sets the patterns of the generic parameters that occur in it.
+    fn unify<'tcx>(&mut self, ty: Ty<'tcx>, pattern: &TyPattern) {
+        match (ty.kind(), pattern) {
+            (_, TyPattern::Any) => {}
+            (TyKind::Param(param), _) => self.patterns[param.index as usize] = pattern.clone(),
+            (TyKind::Adt(_, substs), TyPattern::Adt(_, patterns)) => {
+                for (ty, pattern) in substs.types().zip(patterns) {
+                    self.unify(ty, pattern);
+                }
+            }
+            (TyKind::Ref(_, ty, _), TyPattern::Ref(_, pattern))
+            | (TyKind::Slice(ty), TyPattern::Slice(pattern)) => self.unify(*ty, pattern),
+            (TyKind::Tuple(tys), TyPattern::Tuple(patterns)) => {
+                for (ty, pattern) in tys.iter().zip(patterns) {
+                    self.unify(ty, pattern);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the patterns, unless they match all instances.
+    fn finish(self) -> Option<Vec<TyPattern>> {
+        self.patterns.iter().any(|pattern| *pattern != TyPattern::Any).then_some(self.patterns)
+    }
+}
+
+/// The indices of the generic type parameters declared by `def_id` itself, in order. This
+/// excludes the `Self` parameter of traits.
+fn type_param_indices(tcx: TyCtxt, def_id: DefId) -> Vec<usize> {
+    let generics = tcx.generics_of(def_id);
+    generics
+        .params
+        .iter()
+        .filter(|param| matches!(param.kind, GenericParamDefKind::Type { synthetic: false, .. }))
+        .filter(|param| !(generics.has_self && param.index == 0))
+        .map(|param| param.index as usize)
+        .collect()
+}
+
+/// Resolves the generic type arguments given to `def_id` in a path.
+fn resolve_ty_args(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    def_id: DefId,
+    args: &[TyPath],
+) -> ResolveResult<Vec<TyPattern>> {
+    let expected = type_param_indices(tcx, def_id).len();
+    if args.len() > expected {
+        return Err(Some(format!(
+            "`{}` takes {expected} generic argument(s) but {} were supplied",
+            tcx.def_path_str(def_id),
+            args.len()
+        )));
+    }
+    args.iter().map(|arg| resolve_ty(tcx, current_module, arg)).collect()
+}
+
+/// Resolves a type in a path to a pattern.
+fn resolve_ty(tcx: TyCtxt, current_module: LocalDefId, ty: &TyPath) -> ResolveResult<TyPattern> {
+    let resolve = |ty| resolve_ty(tcx, current_module, ty).map(Box::new);
+    Ok(match ty {
+        TyPath::Infer => TyPattern::Any,
+        TyPath::Ref(mutable, ty) => TyPattern::Ref(*mutable, resolve(ty)?),
+        TyPath::Slice(ty) => TyPattern::Slice(resolve(ty)?),
+        TyPath::Tuple(tys) => TyPattern::Tuple(
+            tys.iter().map(|ty| resolve_ty(tcx, current_module, ty)).collect::<Result<_, _>>()?,
+        ),
+        TyPath::Path(segments)
+            if segments.len() == 1
+                && segments[0].args.is_empty()
+                && PRIMITIVE_TYPES.contains(&segments[0].name.as_str()) =>
+        {
+            TyPattern::Primitive(segments[0].name.clone())
+        }
+        TyPath::Path(segments) => {
+            let def_id = resolve_type_or_trait(tcx, current_module, names(segments))
+                .filter(|def_id| {
+                    matches!(
+                        tcx.def_kind(*def_id),
+                        DefKind::Struct | DefKind::Enum | DefKind::Union
+                    )
+                })
+                .ok_or_else(|| Some(format!("unable to resolve type: {ty}")))?;
+            let args = &segments.last().unwrap().args;
+            TyPattern::Adt(
+                def_path_hash_parts(tcx, def_id),
+                resolve_ty_args(tcx, current_module, def_id, args)?,
+            )
+        }
+    })
+}
+
+/// Resolves a path to a type or trait. Besides the paths that [resolve_item] resolves, this
+/// supports the names from the standard library prelude, such as `Vec`.
+fn resolve_type_or_trait(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    segments: Segments,
+) -> Option<DefId> {
+    let is_name = segments.len() == 1;
+    resolve_item(tcx, current_module, segments.clone()).or_else(|| {
+        if is_name {
+            let mut prelude: Segments =
+                ["std", "prelude", "v1"].into_iter().map(str::to_string).collect();
+            prelude.extend(segments);
+            resolve_external(tcx, prelude)
+        } else {
+            None
+        }
+    })
+}
+
+/// Attempts to resolve a simple path (in the form of segments) to the `DefId`
+/// of a function, type or trait.
+fn resolve_item(tcx: TyCtxt, current_module: LocalDefId, segments: Segments) -> Option<DefId> {
+    let path = to_path(tcx, current_module, segments)?;
     match &path.base {
         Base::ExternPrelude => resolve_external(tcx, path.segments),
         Base::LocalModule { id, may_be_external_path } => {
@@ -81,19 +829,16 @@ fn new(base: Base, segments: Segments) -> Self {
     }
 }
 
-/// Takes a string representation of a path and turns it into a `Path` data
+/// Takes the segments of a path and turns them into a `Path` data
 /// structure, resolving qualifiers (like `crate`, etc.) along the way.
-fn to_path(tcx: TyCtxt, current_module: LocalDefId, name: &str) -> Option<Path> {
+fn to_path(tcx: TyCtxt, current_module: LocalDefId, mut segments: Segments) -> Option<Path> {
+    let name = segments_to_string(&segments);
     tracing::debug!("Normalizing path `{name}`");
 
     const CRATE: &str = "crate";
-    // rustc represents initial `::` as `{{root}}`.
-    const ROOT: &str = "{{root}}";
     const SELF: &str = "self";
     const SUPER: &str = "super";
 
-    // Split the string into segments separated by `::`.
-    let mut segments: Segments = name.split("::").map(str::to_string).collect();
     if segments.is_empty() {
         return Some(Path::new(
             Base::LocalModule { id: current_module, may_be_external_path: false },
@@ -163,6 +908,20 @@ fn resolve_external(tcx: TyCtxt, mut segments: Segments) -> Option<DefId> {
     None
 }
 
+/// Resolves an external path to a module.
+fn resolve_foreign_module(tcx: TyCtxt, mut segments: Segments) -> Option<DefId> {
+    let first = segments.pop_front()?;
+    let krate = tcx.crates(()).iter().find(|krate| tcx.crate_name(**krate).as_str() == first)?;
+    let mut module = DefId { index: CRATE_DEF_INDEX, krate: *krate };
+    for name in segments {
+        module = tcx.module_children(module).iter().find_map(|child| match child.res {
+            Res::Def(DefKind::Mod, def_id) if child.ident.as_str() == name => Some(def_id),
+            _ => None,
+        })?;
+    }
+    Some(module)
+}
+
 /// Resolves a path relative to a foreign module.
 fn resolve_in_foreign_module(
     tcx: TyCtxt,
@@ -181,10 +940,13 @@ fn resolve_in_foreign_module(
     })?;
     for child in tcx.module_children(foreign_mod) {
         match child.res {
-            Res::Def(DefKind::Fn, def_id) => {
+            Res::Def(
+                DefKind::Fn | DefKind::Struct | DefKind::Enum | DefKind::Union | DefKind::Trait,
+                def_id,
+            ) => {
                 if first == child.ident.as_str() && segments.len() == 1 {
                     tracing::debug!(
-                        "Resolved `{first}` as a function in foreign module `{}`",
+                        "Resolved `{first}` as an item in foreign module `{}`",
                         tcx.def_path_str(foreign_mod)
                     );
                     return Some(def_id);
@@ -196,14 +958,6 @@ fn resolve_in_foreign_module(
                     return resolve_in_foreign_module(tcx, inner_mod_id, segments);
                 }
             }
-            Res::Def(DefKind::Struct, type_id) | Res::Def(DefKind::Enum, type_id) => {
-                if first == child.ident.as_str() && segments.len() == 2 {
-                    let maybe_resolved = resolve_in_inherent_impls(tcx, type_id, &segments[1]);
-                    if maybe_resolved.is_some() {
-                        return maybe_resolved;
-                    }
-                }
-            }
             _ => {}
         }
     }
@@ -243,10 +997,14 @@ fn resolve_relative(
         let item = tcx.hir().item(item_id);
         let def_id = item.owner_id.def_id.to_def_id();
         match item.kind {
-            ItemKind::Fn(..) => {
+            ItemKind::Fn(..)
+            | ItemKind::Struct(..)
+            | ItemKind::Enum(..)
+            | ItemKind::Union(..)
+            | ItemKind::Trait(..) => {
                 if first == item.ident.as_str() && segments.len() == 1 {
                     tracing::debug!(
-                        "Resolved `{first}` as a function in local {}",
+                        "Resolved `{first}` as an item in local {}",
                         current_module_string()
                     );
                     return Some(def_id);
@@ -258,14 +1016,6 @@ fn resolve_relative(
                     return resolve_relative(tcx, def_id.expect_local(), segments);
                 }
             }
-            ItemKind::Enum(..) | ItemKind::Struct(..) => {
-                if first == item.ident.as_str() && segments.len() == 2 {
-                    let maybe_resolved = resolve_in_inherent_impls(tcx, def_id, &segments[1]);
-                    if maybe_resolved.is_some() {
-                        return maybe_resolved;
-                    }
-                }
-            }
             _ => (),
         }
     }
@@ -274,13 +1024,13 @@ fn resolve_relative(
     None
 }
 
-/// Resolves a name in an `impl` block.
+/// Resolves a name in an `impl` block or a trait.
 fn resolve_in_impl(tcx: TyCtxt, impl_id: DefId, name: &str) -> Option<DefId> {
     tracing::debug!("Resolving `{name}` in impl block `{}`", tcx.def_path_str(impl_id));
     for assoc_item in tcx.associated_item_def_ids(impl_id) {
         let item_path = tcx.def_path_str(*assoc_item);
         let last = item_path.split("::").last().unwrap();
-        if last == name {
+        if last == name && tcx.def_kind(*assoc_item) == DefKind::AssocFn {
             tracing::debug!("Resolved `{name}` in impl block `{}`", tcx.def_path_str(impl_id));
             return Some(*assoc_item);
         }
@@ -289,20 +1039,6 @@ fn resolve_in_impl(tcx: TyCtxt, impl_id: DefId, name: &str) -> Option<DefId> {
     None
 }
 
-/// Resolves a name in the inherent `impl` blocks of a type (i.e., non-trait
-/// `impl`s).
-fn resolve_in_inherent_impls(tcx: TyCtxt, type_id: DefId, name: &str) -> Option<DefId> {
-    tracing::debug!("Resolving `{name}` in type `{}`", tcx.def_path_str(type_id));
-    for impl_ in tcx.inherent_impls(type_id) {
-        let maybe_resolved = resolve_in_impl(tcx, *impl_, name);
-        if maybe_resolved.is_some() {
-            return maybe_resolved;
-        }
-    }
-    tracing::debug!("Unable to resolve `{name}` in type `{}`", tcx.def_path_str(type_id));
-    None
-}
-
 /// Does the current module have a (direct) submodule with the given name?
 fn has_submodule_with_name(tcx: TyCtxt, current_module: LocalDefId, name: &str) -> bool {
     for item_id in tcx.hir().module_items(current_module) {
@@ -315,3 +1051,65 @@ fn has_submodule_with_name(tcx: TyCtxt, current_module: LocalDefId, name: &str)
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(name: &str, args: Vec<TyPath>) -> Segment {
+        Segment { name: name.to_string(), args }
+    }
+
+    fn ty(name: &str) -> TyPath {
+        TyPath::Path(vec![segment(name, vec![])])
+    }
+
+    #[test]
+    fn parse_simple_paths() {
+        assert_eq!(
+            FnPath::parse("foo::bar"),
+            Some(FnPath::Path(vec![segment("foo", vec![]), segment("bar", vec![])]))
+        );
+        assert_eq!(
+            FnPath::parse(":: foo"),
+            Some(FnPath::Path(vec![segment(ROOT, vec![]), segment("foo", vec![])]))
+        );
+        assert_eq!(FnPath::parse("foo::"), None);
+        assert_eq!(FnPath::parse("foo bar"), None);
+        assert_eq!(FnPath::parse("foo(1)"), None);
+    }
+
+    #[test]
+    fn parse_generic_paths() {
+        let expected = FnPath::Path(vec![segment("Vec", vec![ty("u8")]), segment("push", vec![])]);
+        assert_eq!(FnPath::parse("Vec::<u8>::push"), Some(expected.clone()));
+        assert_eq!(FnPath::parse("Vec < u8 > :: push"), Some(expected));
+        assert_eq!(
+            FnPath::parse("foo::<Vec<Vec<u8>>, _>").unwrap().to_string(),
+            "foo::<Vec::<Vec::<u8>>, _>"
+        );
+        assert_eq!(
+            FnPath::parse("foo::<&mut [u8], (u8,), (u8), ()>").unwrap().to_string(),
+            "foo::<&mut [u8], (u8,), u8, ()>"
+        );
+        assert_eq!(FnPath::parse("Vec::<u8::push"), None);
+    }
+
+    #[test]
+    fn parse_qualified_paths() {
+        assert_eq!(
+            FnPath::parse("<MyClock as Clock>::now"),
+            Some(FnPath::Qualified {
+                self_ty: ty("MyClock"),
+                trait_: Some(vec![segment("Clock", vec![])]),
+                method: segment("now", vec![]),
+            })
+        );
+        assert_eq!(
+            FnPath::parse("<Foo<u8> as From<u16>>::from").unwrap().to_string(),
+            "<Foo::<u8> as From::<u16>>::from"
+        );
+        assert_eq!(FnPath::parse("<[u8]>::len").unwrap().to_string(), "<[u8]>::len");
+        assert_eq!(FnPath::parse("<MyClock as Clock>"), None);
+    }
+}
@@ -1,36 +1,43 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-//! This file contains code for extracting stubbing-related attributes.
+//! This file contains code for extracting stubbing-related attributes, and for
+//! merging them with the stubs that apply to all harnesses of a crate.
 
 use rustc_ast::Attribute;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_driver::RunCompiler;
 use rustc_driver::{Callbacks, Compilation};
 use rustc_errors::ErrorGuaranteed;
-use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::def_id::{LocalDefId, CRATE_DEF_ID};
 use rustc_hir::definitions::DefPathHash;
 use rustc_interface::interface::Compiler;
 use rustc_interface::Queries;
 use rustc_middle::ty::TyCtxt;
+use rustc_span::Span;
 
 use crate::kani_middle::attributes::{extract_path_arguments, partition_kanitool_attributes};
-use crate::kani_middle::resolve::resolve_path;
+use crate::kani_middle::resolve::{resolve_path, ResolvedFn, TyPattern};
+
+use super::Stub;
 
 /// Collects the stubs from the harnesses in a crate, running rustc (to
-/// expansion) with the supplied arguments `rustc_args`.
+/// expansion) with the supplied arguments `rustc_args`. The stubs in
+/// `crate_stubs` (pairs of paths relative to the crate root) apply to all
+/// harnesses, like the ones given by crate-level `kanitool::stub` attributes.
 pub fn collect_stub_mappings(
     rustc_args: &[String],
-) -> Result<FxHashMap<String, FxHashMap<DefPathHash, DefPathHash>>, ErrorGuaranteed> {
-    let mut callbacks = CollectorCallbacks::default();
+    crate_stubs: Vec<(String, String)>,
+) -> Result<FxHashMap<String, Vec<Stub>>, ErrorGuaranteed> {
+    let mut callbacks = CollectorCallbacks { crate_stubs, stub_mapping: FxHashMap::default() };
     let compiler = RunCompiler::new(rustc_args, &mut callbacks);
     compiler.run().map(|_| callbacks.stub_mapping)
 }
 
 /// A rustc callback that is used to collect the stub mappings specified for
 /// each harness.
-#[derive(Default)]
 struct CollectorCallbacks {
-    stub_mapping: FxHashMap<String, FxHashMap<DefPathHash, DefPathHash>>,
+    crate_stubs: Vec<(String, String)>,
+    stub_mapping: FxHashMap<String, Vec<Stub>>,
 }
 
 impl Callbacks for CollectorCallbacks {
@@ -41,6 +48,9 @@ fn after_expansion<'tcx>(
         queries: &'tcx Queries<'tcx>,
     ) -> Compilation {
         queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            // The crate-wide stubs are only resolved in crates with harnesses,
+            // since they are usually given for a whole workspace.
+            let mut crate_stubs = None;
             for item in tcx.hir_crate_items(()).items() {
                 let local_def_id = item.owner_id.def_id;
                 let def_id = local_def_id.to_def_id();
@@ -49,14 +59,21 @@ fn after_expansion<'tcx>(
                 if proof.is_empty() {
                     continue;
                 }
-                let mut stub_pairs = FxHashMap::default();
+                let crate_stubs =
+                    crate_stubs.get_or_insert_with(|| collect_crate_stubs(tcx, &self.crate_stubs));
+                let current_module = tcx.parent_module_from_def_id(local_def_id);
+                let mut stubs = vec![];
+                let mut unstubs = vec![];
                 for (name, attr) in other {
-                    if name == "stub" {
-                        update_stub_mapping(tcx, local_def_id, attr, &mut stub_pairs);
+                    match name.as_str() {
+                        "stub" => update_stub_mapping(tcx, current_module, attr, &mut stubs),
+                        "unstub" => unstubs.extend(extract_unstub(tcx, current_module, attr)),
+                        _ => {}
                     }
                 }
+                let inherited = remove_unstubbed(tcx, crate_stubs, &unstubs);
                 let harness_name = tcx.def_path_str(def_id);
-                self.stub_mapping.insert(harness_name, stub_pairs);
+                self.stub_mapping.insert(harness_name, merge_stubs(inherited, stubs));
             }
             tcx.sess.abort_if_errors();
             // We do not need to continue compilation after we've collected the stub mappings
@@ -65,14 +82,88 @@ fn after_expansion<'tcx>(
     }
 }
 
+/// Collects the stubs that apply to all harnesses of the crate: the ones given
+/// as arguments (e.g., from `Cargo.toml`), overridden by the ones given by
+/// crate-level `kanitool::stub` attributes.
+fn collect_crate_stubs(tcx: TyCtxt, stub_args: &[(String, String)]) -> Vec<Stub> {
+    let mut arg_stubs = vec![];
+    for (original, replacement) in stub_args {
+        if let Some(stub) = resolve_stub(tcx, CRATE_DEF_ID, original, replacement, None) {
+            add_stub(tcx, &mut arg_stubs, stub, None);
+        }
+    }
+    let (_, attrs) = partition_kanitool_attributes(tcx.hir().krate_attrs());
+    let mut attr_stubs = vec![];
+    for (name, attr) in attrs {
+        if name == "stub" {
+            update_stub_mapping(tcx, CRATE_DEF_ID, attr, &mut attr_stubs);
+        }
+    }
+    merge_stubs(arg_stubs, attr_stubs)
+}
+
+/// Returns the stubs in `stubs`, replacing the ones for the same
+/// function/method instances as a stub in `overrides`, and adding the other
+/// stubs in `overrides`.
+fn merge_stubs(stubs: Vec<Stub>, mut overrides: Vec<Stub>) -> Vec<Stub> {
+    let inherited = stubs.into_iter().filter(|stub| {
+        !overrides
+            .iter()
+            .any(|other| other.original == stub.original && other.generic_args == stub.generic_args)
+    });
+    let mut merged: Vec<Stub> = inherited.collect();
+    merged.append(&mut overrides);
+    merged
+}
+
+/// Returns the crate-wide stubs that are not disabled by a `kani::unstub`
+/// attribute of a harness. An attribute without generic arguments disables all
+/// the stubs of a function/method.
+fn remove_unstubbed(
+    tcx: TyCtxt,
+    crate_stubs: &[Stub],
+    unstubs: &[(Span, ResolvedFn)],
+) -> Vec<Stub> {
+    for (span, unstub) in unstubs {
+        let original = tcx.def_path_hash(unstub.def_id);
+        if !crate_stubs.iter().any(|stub| is_unstubbed(stub, original, &unstub.generic_args)) {
+            tcx.sess.span_warn(
+                *span,
+                format!(
+                    "`{}` is not stubbed for all harnesses; attribute `kani::unstub` has no effect",
+                    tcx.def_path_str(unstub.def_id)
+                ),
+            );
+        }
+    }
+    crate_stubs
+        .iter()
+        .filter(|stub| {
+            !unstubs.iter().any(|(_, unstub)| {
+                is_unstubbed(stub, tcx.def_path_hash(unstub.def_id), &unstub.generic_args)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+fn is_unstubbed(stub: &Stub, original: DefPathHash, generic_args: &Option<Vec<TyPattern>>) -> bool {
+    stub.original == original && (generic_args.is_none() || *generic_args == stub.generic_args)
+}
+
+/// Reports an error at `span`, or without a location for the stubs that are
+/// not given by an attribute.
+fn report_error(tcx: TyCtxt, span: Option<Span>, msg: String) {
+    match span {
+        Some(span) => tcx.sess.span_err(span, msg),
+        None => tcx.sess.err(msg),
+    };
+}
+
 /// Given a `kani::stub` attribute, tries to extract a pair of paths (the
 /// original function/method, and its stub). Returns `None` and errors if the
 /// attribute's arguments are not two paths.
-fn extract_stubbing_pair(
-    tcx: TyCtxt,
-    harness: LocalDefId,
-    attr: &Attribute,
-) -> Option<(DefId, DefId)> {
+fn extract_stubbing_pair(tcx: TyCtxt, attr: &Attribute) -> Option<(String, String)> {
     // Extract the attribute arguments
     let args = extract_path_arguments(attr);
     if args.len() != 2 {
@@ -90,47 +181,118 @@ fn extract_stubbing_pair(
         );
         return None;
     }
+    let mut args = args.into_iter().flatten();
+    Some((args.next().unwrap(), args.next().unwrap()))
+}
 
-    // Resolve the attribute arguments to `DefId`s
-    let current_module = tcx.parent_module_from_def_id(harness);
-    let resolve = |name: &str| -> Option<DefId> {
-        let maybe_resolved = resolve_path(tcx, current_module, name);
-        if let Some(def_id) = maybe_resolved {
-            tracing::debug!(?def_id, "Resolved {name} to {}", tcx.def_path_str(def_id));
-        } else {
-            tcx.sess.span_err(attr.span, format!("unable to resolve function/method: {}", name));
+/// Resolves the paths of a stub relative to `current_module`. Returns `None`
+/// and errors if they cannot be resolved, or if the stub is not a function.
+fn resolve_stub(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    original: &str,
+    replacement: &str,
+    span: Option<Span>,
+) -> Option<Stub> {
+    let resolve = |name: &str| -> Option<ResolvedFn> {
+        match resolve_path(tcx, current_module, name) {
+            Ok(resolved) => {
+                tracing::debug!(
+                    ?resolved,
+                    "Resolved {name} to {}",
+                    tcx.def_path_str(resolved.def_id)
+                );
+                Some(resolved)
+            }
+            Err(msg) => {
+                report_error(tcx, span, msg);
+                None
+            }
         }
-        maybe_resolved
     };
-    let orig = resolve(args[0].as_deref().unwrap());
-    let stub = resolve(args[1].as_deref().unwrap());
-    Some((orig?, stub?))
+    let orig = resolve(original);
+    let stub = resolve(replacement);
+    if let Some(ResolvedFn { def_id, generic_args: Some(_) }) = &stub {
+        report_error(
+            tcx,
+            span,
+            format!(
+                "the stub `{}` must be given as a path without generic arguments",
+                tcx.def_path_str(*def_id)
+            ),
+        );
+        return None;
+    }
+    let (orig, stub) = (orig?, stub?);
+    Some(Stub {
+        original: tcx.def_path_hash(orig.def_id),
+        replacement: tcx.def_path_hash(stub.def_id),
+        generic_args: orig.generic_args,
+    })
+}
+
+/// Adds `new_stub` to the running list of stubs `stubs` that maps a
+/// function/method (or some of its instances) to its stub. Errors if the same
+/// function/method instances are mapped more than once.
+fn add_stub(tcx: TyCtxt, stubs: &mut Vec<Stub>, new_stub: Stub, span: Option<Span>) {
+    let other = stubs.iter().find(|other| {
+        other.original == new_stub.original && other.generic_args == new_stub.generic_args
+    });
+    match other {
+        None => stubs.push(new_stub),
+        Some(other) if other.replacement != new_stub.replacement => {
+            let def_path_str =
+                |hash| tcx.def_path_str(tcx.def_path_hash_to_def_id(hash, &mut || panic!()));
+            report_error(
+                tcx,
+                span,
+                format!(
+                    "duplicate stub mapping: {} mapped to {} and {}",
+                    def_path_str(new_stub.original),
+                    def_path_str(new_stub.replacement),
+                    def_path_str(other.replacement)
+                ),
+            );
+        }
+        Some(_) => {}
+    }
 }
 
-/// Updates the running map `stub_pairs` that maps a function/method to its
-/// stub. Errors if a function/method is mapped more than once.
+/// Updates the running list of stubs `stubs` with the stub given by a
+/// `kani::stub` attribute.
 fn update_stub_mapping(
     tcx: TyCtxt,
-    harness: LocalDefId,
+    current_module: LocalDefId,
     attr: &Attribute,
-    stub_pairs: &mut FxHashMap<DefPathHash, DefPathHash>,
+    stubs: &mut Vec<Stub>,
 ) {
-    if let Some((orig_id, stub_id)) = extract_stubbing_pair(tcx, harness, attr) {
-        let orig_hash = tcx.def_path_hash(orig_id);
-        let stub_hash = tcx.def_path_hash(stub_id);
-        let other_opt = stub_pairs.insert(orig_hash, stub_hash);
-        if let Some(other) = other_opt {
-            if other != stub_hash {
-                tcx.sess.span_err(
-                    attr.span,
-                    format!(
-                        "duplicate stub mapping: {} mapped to {} and {}",
-                        tcx.def_path_str(orig_id),
-                        tcx.def_path_str(stub_id),
-                        tcx.def_path_str(tcx.def_path_hash_to_def_id(other, &mut || panic!()))
-                    ),
-                );
-            }
+    if let Some((original, replacement)) = extract_stubbing_pair(tcx, attr) {
+        if let Some(stub) =
+            resolve_stub(tcx, current_module, &original, &replacement, Some(attr.span))
+        {
+            add_stub(tcx, stubs, stub, Some(attr.span));
+        }
+    }
+}
+
+/// Given a `kani::unstub` attribute, resolves the function/method whose
+/// crate-wide stubs it disables. Returns `None` and errors if the attribute's
+/// argument is not a single path that can be resolved.
+fn extract_unstub(
+    tcx: TyCtxt,
+    current_module: LocalDefId,
+    attr: &Attribute,
+) -> Option<(Span, ResolvedFn)> {
+    let args = extract_path_arguments(attr);
+    let [Some(path)] = &args[..] else {
+        tcx.sess.span_err(attr.span, "Attribute `kani::unstub` takes a single path argument");
+        return None;
+    };
+    match resolve_path(tcx, current_module, path) {
+        Ok(resolved) => Some((attr.span, resolved)),
+        Err(msg) => {
+            tcx.sess.span_err(attr.span, msg);
+            None
         }
     }
 }
@@ -3,20 +3,68 @@
 //! This module contains code related to the MIR-to-MIR pass that performs the
 //! stubbing of functions and methods. The primary function of the module is
 //! `transform`, which takes the `DefId` of a function/method and returns the
-//! body of its stub, if appropriate. The stub mapping it uses is set via rustc
+//! body of its stub, if appropriate. Stubs that only replace some instances of
+//! a generic function/method are applied by `stub_instance` instead, when the
+//! instances are resolved. The stub mapping they use is set via rustc
 //! arguments.
 
 use lazy_static::lazy_static;
 use regex::Regex;
-use rustc_data_structures::{fingerprint::Fingerprint, fx::FxHashMap};
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::{def_id::DefId, definitions::DefPathHash};
-use rustc_middle::{mir::Body, ty::TyCtxt};
+use rustc_middle::mir::Body;
+use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts, SubstsRef};
+use rustc_middle::ty::{EarlyBinder, Instance, InstanceDef, ParamEnv, TyCtxt};
+use rustc_span::Span;
+
+use crate::kani_middle::resolve::TyPattern;
+
+/// A stub for a function/method. If `generic_args` is not `None`, the stub only
+/// replaces the instances of the original function/method whose generic
+/// arguments match the patterns.
+#[derive(Clone, Debug)]
+pub struct Stub {
+    pub original: DefPathHash,
+    pub replacement: DefPathHash,
+    pub generic_args: Option<Vec<TyPattern>>,
+}
+
+/// The stubs of the current compiler session, indexed by the function/method
+/// they replace.
+#[derive(Default)]
+struct StubMapping {
+    /// The stubs that replace all instances of a function/method.
+    stubs: FxHashMap<DefId, DefId>,
+    /// The stubs that only replace the instances whose generic arguments match
+    /// the patterns.
+    instance_stubs: FxHashMap<DefId, Vec<(Vec<TyPattern>, DefId)>>,
+}
+
+impl StubMapping {
+    fn insert(
+        &mut self,
+        original: DefId,
+        replacement: DefId,
+        generic_args: Option<Vec<TyPattern>>,
+    ) {
+        match generic_args {
+            None => {
+                self.stubs.insert(original, replacement);
+            }
+            Some(patterns) => {
+                self.instance_stubs.entry(original).or_default().push((patterns, replacement))
+            }
+        }
+    }
+}
 
 /// Returns the `DefId` of the stub for the function/method identified by the
-/// parameter `def_id`, and `None` if the function/method is not stubbed.
+/// parameter `def_id`, and `None` if the function/method is not stubbed. This
+/// only considers the stubs that replace all instances of a function/method;
+/// see [stub_instance] for the others.
 pub fn get_stub(tcx: TyCtxt, def_id: DefId) -> Option<DefId> {
-    let mapping = get_stub_mapping(tcx)?;
-    mapping.get(&def_id).copied()
+    get_stub_mapping(tcx)?.stubs.get(&def_id).copied()
 }
 
 /// Returns the new body of a function/method if it has been stubbed out;
@@ -35,12 +83,112 @@ pub fn transform<'tcx>(
     old_body
 }
 
+/// Returns the instance of the stub that replaces a (monomorphized) instance of a
+/// function/method, if the instance is stubbed by a path with generic
+/// arguments (e.g., `Vec::<u8>::len`). Otherwise, returns `instance`: the
+/// stubs that replace all instances are applied by [transform] instead.
+///
+/// Errors if the stub cannot replace the instance, unless `span` is `None`;
+/// the original instance is kept in that case.
+pub fn stub_instance<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    span: Option<Span>,
+) -> Instance<'tcx> {
+    let InstanceDef::Item(item) = instance.def else { return instance };
+    let Some(mapping) = get_stub_mapping(tcx) else { return instance };
+    let Some(stubs) = mapping.instance_stubs.get(&item.did) else { return instance };
+    let stub = stubs
+        .iter()
+        .find(|(patterns, _)| TyPattern::matches_substs(tcx, patterns, instance.substs));
+    stub.and_then(|(_, replacement)| instantiate_stub(tcx, instance, *replacement, span))
+        .unwrap_or(instance)
+}
+
+/// Creates the instance of the stub `stub` that replaces `instance`, and checks
+/// that their signatures match.
+fn instantiate_stub<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    stub: DefId,
+    span: Option<Span>,
+) -> Option<Instance<'tcx>> {
+    let error = |msg: String| {
+        if let Some(span) = span {
+            tcx.sess.span_err(span, msg);
+        }
+    };
+    let stub_generics = tcx.generics_of(stub);
+    let stub_instance = if !stub_generics.requires_monomorphization(tcx) {
+        Instance::mono(tcx, stub)
+    } else if stub_generics.count() == instance.substs.len() {
+        Instance::resolve(tcx, ParamEnv::reveal_all(), stub, instance.substs).ok().flatten()?
+    } else {
+        error(format!(
+            "mismatch in the number of generic parameters: `{instance}` takes {} generic \
+            parameter(s), stub `{}` takes {}; stubs for some instances of a generic \
+            function/method must either have no generic parameters or the same ones",
+            instance.substs.len(),
+            tcx.def_path_str(stub),
+            stub_generics.count()
+        ));
+        return None;
+    };
+
+    let param_env = ParamEnv::reveal_all();
+    let sig = |instance: Instance<'tcx>| {
+        tcx.normalize_erasing_late_bound_regions(param_env, instance.ty(tcx, param_env).fn_sig(tcx))
+    };
+    let (old_sig, stub_sig) = (sig(instance), sig(stub_instance));
+    if old_sig.inputs().len() != stub_sig.inputs().len() {
+        error(format!(
+            "arity mismatch: `{instance}` takes {} argument(s), stub `{stub_instance}` takes {}",
+            old_sig.inputs().len(),
+            stub_sig.inputs().len()
+        ));
+        return None;
+    }
+    let mut matches = true;
+    if old_sig.output() != stub_sig.output() {
+        error(format!(
+            "return type differs: stub `{stub_instance}` has type `{}` where `{instance}` has \
+            type `{}`",
+            stub_sig.output(),
+            old_sig.output()
+        ));
+        matches = false;
+    }
+    for (i, (old_ty, stub_ty)) in old_sig.inputs().iter().zip(stub_sig.inputs()).enumerate() {
+        if old_ty != stub_ty {
+            error(format!(
+                "type of parameter {i} differs: stub `{stub_instance}` has type `{stub_ty}` \
+                where `{instance}` has type `{old_ty}`"
+            ));
+            matches = false;
+        }
+    }
+    matches.then_some(stub_instance)
+}
+
+/// Lists the generic parameters of a function/method (including the ones of
+/// its parent) for diagnostics, e.g., `T, U` for `fn foo<T, U>()`.
+fn generic_params_string(tcx: TyCtxt, def_id: DefId) -> String {
+    let mut params = vec![];
+    let mut current = Some(def_id);
+    while let Some(def_id) = current {
+        let generics = tcx.generics_of(def_id);
+        params.splice(0..0, generics.params.iter().map(|param| param.name.to_string()));
+        current = generics.parent;
+    }
+    if params.is_empty() { "none".to_string() } else { params.join(", ") }
+}
+
 /// Checks whether the stub is compatible with the original function/method: do
 /// the arities and types (of the parameters and return values) match up? This
 /// does **NOT** check whether the type variables are constrained to implement
 /// the same traits; trait mismatches are checked during monomorphization.
 fn check_compatibility<'a, 'tcx>(
-    tcx: TyCtxt,
+    tcx: TyCtxt<'tcx>,
     old_def_id: DefId,
     old_body: &'a Body<'tcx>,
     stub_def_id: DefId,
@@ -64,30 +212,54 @@ fn check_compatibility<'a, 'tcx>(
     let old_num_generics = tcx.generics_of(old_def_id).count();
     let stub_num_generics = tcx.generics_of(stub_def_id).count();
     if old_num_generics != stub_num_generics {
+        tcx.sess
+            .struct_span_err(
+                tcx.def_span(stub_def_id),
+                format!(
+                    "mismatch in the number of generic parameters: original function/method `{}` takes {} generic parameters(s), stub `{}` takes {}",
+                    tcx.def_path_str(old_def_id),
+                    old_num_generics,
+                    tcx.def_path_str(stub_def_id),
+                    stub_num_generics
+                ),
+            )
+            .note(format!(
+                "the generic parameters of `{}` are: {}; the generic parameters of `{}` are: {}",
+                tcx.def_path_str(old_def_id),
+                generic_params_string(tcx, old_def_id),
+                tcx.def_path_str(stub_def_id),
+                generic_params_string(tcx, stub_def_id)
+            ))
+            .emit();
+        return false;
+    }
+    // Check whether the generic parameters have the same kinds, so that we can
+    // rename the ones of the stub to the ones of the original function/method.
+    let old_substs = InternalSubsts::identity_for_item(tcx, old_def_id);
+    let stub_substs = InternalSubsts::identity_for_item(tcx, stub_def_id);
+    if !same_kinds(old_substs, stub_substs) {
         tcx.sess.span_err(
             tcx.def_span(stub_def_id),
             format!(
-                "mismatch in the number of generic parameters: original function/method `{}` takes {} generic parameters(s), stub `{}` takes {}",
+                "mismatch in the kinds of generic parameters: original function/method `{}` has generic parameters `{}`, stub `{}` has `{}`",
                 tcx.def_path_str(old_def_id),
-                old_num_generics,
+                generic_params_string(tcx, old_def_id),
                 tcx.def_path_str(stub_def_id),
-                stub_num_generics
+                generic_params_string(tcx, stub_def_id)
             ),
         );
         return false;
     }
-    // Check whether the types match. Index 0 refers to the returned value,
-    // indices [1, `arg_count`] refer to the parameters.
-    // TODO: We currently force generic parameters in the stub to have exactly
-    // the same names as their counterparts in the original function/method;
-    // instead, we should be checking for the equivalence of types up to the
-    // renaming of generic parameters.
-    // <https://github.com/model-checking/kani/issues/1953>
+    // Check whether the types match, up to the renaming of generic parameters:
+    // we substitute the generic parameters of the stub with the ones of the
+    // original function/method in the same position. Index 0 refers to the
+    // returned value, indices [1, `arg_count`] refer to the parameters.
     let mut matches = true;
     for i in 0..=old_body.arg_count {
         let old_arg = old_body.local_decls.get(i.into()).unwrap();
         let new_arg = stub_body.local_decls.get(i.into()).unwrap();
-        if old_arg.ty != new_arg.ty {
+        let new_ty = EarlyBinder(new_arg.ty).subst(tcx, old_substs);
+        if old_arg.ty != new_ty {
             let prefix = if i == 0 {
                 "return type differs".to_string()
             } else {
@@ -113,39 +285,36 @@ fn check_compatibility<'a, 'tcx>(
 const RUSTC_ARG_PREFIX: &str = "kani_stubs=";
 
 /// Serializes the stub mapping into a rustc argument.
-pub fn mk_rustc_arg(stub_mapping: FxHashMap<DefPathHash, DefPathHash>) -> String {
+pub fn mk_rustc_arg(stubs: Vec<Stub>) -> String {
     // Serialize each `DefPathHash` as a pair of `u64`s, and the whole mapping
-    // as an association list.
-    let mut pairs = Vec::new();
-    for (k, v) in stub_mapping {
-        let kparts = k.0.as_value();
-        let vparts = v.0.as_value();
-        pairs.push((kparts, vparts));
-    }
+    // as a list of triples.
+    let triples: Vec<_> = stubs
+        .into_iter()
+        .map(|stub| (stub.original.0.as_value(), stub.replacement.0.as_value(), stub.generic_args))
+        .collect();
     // Store our serialized mapping as a fake LLVM argument (safe to do since
     // LLVM will never see them).
-    format!("-Cllvm-args='{RUSTC_ARG_PREFIX}{}'", serde_json::to_string(&pairs).unwrap())
+    format!("-Cllvm-args='{RUSTC_ARG_PREFIX}{}'", serde_json::to_string(&triples).unwrap())
 }
 
 /// Deserializes the stub mapping from the rustc argument value.
-fn deserialize_mapping(tcx: TyCtxt, val: &str) -> FxHashMap<DefId, DefId> {
+fn deserialize_mapping(tcx: TyCtxt, val: &str) -> StubMapping {
     type Item = (u64, u64);
     let item_to_def_id = |item: Item| -> DefId {
         let hash = DefPathHash(Fingerprint::new(item.0, item.1));
         tcx.def_path_hash_to_def_id(hash, &mut || panic!())
     };
-    let pairs: Vec<(Item, Item)> = serde_json::from_str(val).unwrap();
-    let mut m = FxHashMap::default();
-    for (k, v) in pairs {
-        let kid = item_to_def_id(k);
-        let vid = item_to_def_id(v);
-        m.insert(kid, vid);
+    let triples: Vec<(Item, Item, Option<Vec<TyPattern>>)> = serde_json::from_str(val).unwrap();
+    let mut mapping = StubMapping::default();
+    for (original, replacement, generic_args) in triples {
+        mapping.insert(item_to_def_id(original), item_to_def_id(replacement), generic_args);
     }
-    m
+    mapping
 }
 
-/// Retrieves the stub mapping from the compiler configuration.
-fn get_stub_mapping(tcx: TyCtxt) -> Option<FxHashMap<DefId, DefId>> {
+/// Retrieves the stub mapping from the compiler configuration. The mapping is a rustc argument,
+/// so each compiler session (we run one per stub set) gets its own.
+fn get_stub_mapping(tcx: TyCtxt) -> Option<StubMapping> {
     // Use a static so that we compile the regex only once.
     lazy_static! {
         static ref RE: Regex = Regex::new(&format!("'{RUSTC_ARG_PREFIX}(.*)'")).unwrap();
@@ -157,3 +326,15 @@ fn get_stub_mapping(tcx: TyCtxt) -> Option<FxHashMap<DefId, DefId>> {
     }
     None
 }
+
+/// Do the generic parameters have the same kinds, position by position?
+fn same_kinds(old: SubstsRef, stub: SubstsRef) -> bool {
+    old.iter().zip(stub.iter()).all(|(old, stub)| {
+        matches!(
+            (old.unpack(), stub.unpack()),
+            (GenericArgKind::Lifetime(_), GenericArgKind::Lifetime(_))
+                | (GenericArgKind::Type(_), GenericArgKind::Type(_))
+                | (GenericArgKind::Const(_), GenericArgKind::Const(_))
+        )
+    })
+}
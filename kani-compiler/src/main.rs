@@ -14,6 +14,7 @@
 #![feature(rustc_private)]
 #![feature(more_qualified_paths)]
 extern crate rustc_ast;
+extern crate rustc_ast_pretty;
 extern crate rustc_codegen_ssa;
 extern crate rustc_data_structures;
 extern crate rustc_driver;
@@ -23,6 +24,7 @@
 extern crate rustc_interface;
 extern crate rustc_metadata;
 extern crate rustc_middle;
+extern crate rustc_mir_dataflow;
 extern crate rustc_session;
 extern crate rustc_span;
 extern crate rustc_target;
@@ -41,7 +43,7 @@
 use kani_queries::{QueryDb, ReachabilityType, UserInput};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_driver::{Callbacks, RunCompiler};
-use rustc_hir::definitions::DefPathHash;
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -75,6 +77,11 @@ fn rustc_gotoc_flags(lib_path: &str) -> Vec<String> {
         "crate-attr=feature(register_tool)",
         "-Z",
         "crate-attr=register_tool(kanitool)",
+        // Allow crate-level `#![kani::stub(..)]` attributes, which are expanded by `kani_macros`.
+        "-Z",
+        "crate-attr=feature(custom_inner_attributes)",
+        "-Z",
+        "crate-attr=feature(proc_macro_hygiene)",
         "-L",
         lib_path,
         "--extern",
@@ -91,37 +98,120 @@ fn main() -> Result<(), &'static str> {
     let matches = parser::parser().get_matches_from(args);
     init_session(&matches);
 
-    // Configure queries.
+    // Generate rustc args.
+    let rustc_args = generate_rustc_args(&matches);
+
+    // If appropriate, collect the stub mappings and compile one model per stub set.
+    if matches.get_flag(parser::ENABLE_STUBBING)
+        && matches.reachability_type() == ReachabilityType::Harnesses
+    {
+        let crate_stubs: Vec<(String, String)> = matches
+            .get_many::<String>(parser::STUB)
+            .into_iter()
+            .flatten()
+            .filter_map(|stub| stub.split_once('='))
+            .map(|(original, replacement)| (original.to_string(), replacement.to_string()))
+            .collect();
+        let all_stub_mappings = stubbing::collect_stub_mappings(&rustc_args, crate_stubs)
+            .or(Err("Failed to compile crate"))?;
+        if let Some(harness) = matches.get_one::<String>(parser::HARNESS) {
+            let mapping = find_harness_stub_mapping(harness, all_stub_mappings).unwrap_or_default();
+            let mut queries = configure_queries(&matches);
+            queries.set_stubbing_enabled(true);
+            let mut rustc_args = rustc_args;
+            rustc_args.push(stubbing::mk_rustc_arg(mapping));
+            return run_compiler(&matches, &rustc_args, queries);
+        }
+        return compile_stub_sets(&matches, &rustc_args, all_stub_mappings);
+    }
+
+    run_compiler(&matches, &rustc_args, configure_queries(&matches))
+}
+
+/// Configure the queries according to the user arguments.
+fn configure_queries(matches: &ArgMatches) -> QueryDb {
     let mut queries = QueryDb::default();
     queries.set_emit_vtable_restrictions(matches.get_flag(parser::RESTRICT_FN_PTRS));
     queries.set_check_assertion_reachability(matches.get_flag(parser::ASSERTION_REACH_CHECKS));
     queries.set_output_pretty_json(matches.get_flag(parser::PRETTY_OUTPUT_FILES));
     queries.set_ignore_global_asm(matches.get_flag(parser::IGNORE_GLOBAL_ASM));
     queries.set_reachability_analysis(matches.reachability_type());
+    queries.set_default_models(!matches.get_flag(parser::NO_DEFAULT_MODELS));
+    queries.set_uninit_checks(matches.get_flag(parser::UNINIT_CHECKS));
+    queries.set_aliasing_checks(matches.get_flag(parser::ALIASING_CHECKS));
+    queries.set_alignment_checks(!matches.get_flag(parser::NO_ALIGNMENT_CHECKS));
+    queries.set_validity_checks(!matches.get_flag(parser::NO_VALIDITY_CHECKS));
+    queries.set_conversion_checks(matches.conversion_checks());
+    queries.set_nondet_alloc_failure(matches.get_flag(parser::NONDET_ALLOC_FAILURE));
+    queries.set_nondet_alloc_failure_min_size(
+        matches.get_one::<u64>(parser::NONDET_ALLOC_FAILURE_MIN_SIZE).copied().unwrap_or(0),
+    );
+    queries.set_nondet_alloc_failure_crates(
+        matches
+            .get_many::<String>(parser::NONDET_ALLOC_FAILURE_CRATE)
+            .map_or(vec![], |crates| crates.cloned().collect()),
+    );
+    queries.set_leak_check(matches.get_flag(parser::LEAK_CHECK));
+    queries.set_record_nondet(matches.get_flag(parser::RECORD_NONDET));
+    queries.set_vacuity_checks(matches.get_flag(parser::VACUITY_CHECKS));
     #[cfg(feature = "unsound_experiments")]
     crate::unsound_experiments::arg_parser::add_unsound_experiment_args_to_queries(
         &mut queries,
-        &matches,
+        matches,
     );
+    queries
+}
 
-    // Generate rustc args.
-    let mut rustc_args = generate_rustc_args(&matches);
-
-    // If appropriate, collect and set the stub mapping.
-    if matches.get_flag(parser::ENABLE_STUBBING)
-        && queries.get_reachability_analysis() == ReachabilityType::Harnesses
-    {
+/// Compile the crate once for each distinct set of stubs used by its harnesses.
+///
+/// Since stubbing replaces function bodies for a whole compilation session, harnesses with
+/// different stubs cannot share a goto model. Each compilation only starts codegen from the
+/// harnesses that use its stub set and writes a model with a distinct name, which the harness
+/// metadata points to. The first model uses the regular output name.
+fn compile_stub_sets(
+    matches: &ArgMatches,
+    rustc_args: &[String],
+    stub_mappings: FxHashMap<String, Vec<stubbing::Stub>>,
+) -> Result<(), &'static str> {
+    // Group the harnesses by their (serialized) stub set. Use an ordered map so the models
+    // are named deterministically.
+    let mut stub_sets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (harness, stubs) in stub_mappings {
+        stub_sets.entry(stubbing::mk_rustc_arg(stubs)).or_default().push(harness);
+    }
+    if stub_sets.is_empty() {
+        // No harnesses, so there is a single (empty) model.
+        stub_sets.insert(stubbing::mk_rustc_arg(vec![]), vec![]);
+    }
+    let cap_lints = !rustc_args.iter().any(|arg| arg.starts_with("--cap-lints"));
+    for (idx, (stub_arg, harnesses)) in stub_sets.into_iter().enumerate() {
+        let mut queries = configure_queries(matches);
         queries.set_stubbing_enabled(true);
-        let all_stub_mappings =
-            stubbing::collect_stub_mappings(&rustc_args).or(Err("Failed to compile crate"))?;
-        let harness = matches.get_one::<String>(parser::HARNESS).unwrap();
-        let mapping = find_harness_stub_mapping(harness, all_stub_mappings).unwrap_or_default();
-        rustc_args.push(stubbing::mk_rustc_arg(mapping));
+        let mut rustc_args = rustc_args.to_vec();
+        rustc_args.push(stub_arg);
+        if idx > 0 {
+            queries.set_model_suffix(Some(format!("stubs-{idx}")));
+            // Only report lints once for the crate.
+            if cap_lints {
+                rustc_args.push(String::from("--cap-lints=allow"));
+            }
+        }
+        if !harnesses.is_empty() {
+            queries.set_harness_filter(Some(harnesses));
+        }
+        run_compiler(matches, &rustc_args, queries)?;
     }
+    Ok(())
+}
 
-    // Configure and run compiler.
+/// Configure and run the compiler with the given arguments.
+fn run_compiler(
+    matches: &ArgMatches,
+    rustc_args: &[String],
+    queries: QueryDb,
+) -> Result<(), &'static str> {
     let mut callbacks = KaniCallbacks {};
-    let mut compiler = RunCompiler::new(&rustc_args, &mut callbacks);
+    let mut compiler = RunCompiler::new(rustc_args, &mut callbacks);
     if matches.get_flag("goto-c") {
         if cfg!(feature = "cprover") {
             compiler.set_make_codegen_backend(Some(Box::new(move |_cfg| {
@@ -172,6 +262,10 @@ fn generate_rustc_args(args: &ArgMatches) -> Vec<String> {
                 .unwrap_or(&default_path.to_str().unwrap().to_string()),
         );
         rustc_args.extend_from_slice(&gotoc_args);
+        if args.get_flag(parser::ALIASING_CHECKS) {
+            // The aliasing checks instrument the retags of references.
+            rustc_args.push(String::from("-Zmir-emit-retag"));
+        }
     }
 
     if args.get_flag(parser::RUSTC_VERSION) {
@@ -272,8 +366,8 @@ fn sysroot_path(args: &ArgMatches) -> PathBuf {
 /// qualified names.
 fn find_harness_stub_mapping(
     harness: &str,
-    stub_mappings: FxHashMap<String, FxHashMap<DefPathHash, DefPathHash>>,
-) -> Option<FxHashMap<DefPathHash, DefPathHash>> {
+    stub_mappings: FxHashMap<String, Vec<stubbing::Stub>>,
+) -> Option<Vec<stubbing::Stub>> {
     let suffix = String::from("::") + harness;
     for (name, mapping) in stub_mappings {
         if name == harness || name.ends_with(&suffix) {
@@ -3,7 +3,7 @@
 
 use clap::value_parser;
 use clap::{builder::PossibleValuesParser, command, Arg, ArgAction, ArgMatches, Command};
-use kani_queries::ReachabilityType;
+use kani_queries::{ConversionCheck, ReachabilityType};
 use std::env;
 use std::ffi::OsString;
 use std::str::FromStr;
@@ -49,6 +49,46 @@
 /// Option name used to enable stubbing.
 pub const ENABLE_STUBBING: &str = "enable-stubbing";
 
+/// Option name used to add a stub to all harnesses.
+pub const STUB: &str = "stub";
+
+/// Option name used to disable the default model of the `getrandom` crate.
+pub const NO_DEFAULT_MODELS: &str = "no-default-models";
+
+/// Option name used to enable the checks for reads of uninitialized memory.
+pub const UNINIT_CHECKS: &str = "uninit-checks";
+
+/// Option name used to enable the checks of the aliasing rules.
+pub const ALIASING_CHECKS: &str = "aliasing-checks";
+
+/// Option name used to disable the alignment checks of raw pointer accesses.
+pub const NO_ALIGNMENT_CHECKS: &str = "no-alignment-checks";
+
+/// Option name used to disable the validity checks of the values produced by unsafe operations.
+pub const NO_VALIDITY_CHECKS: &str = "no-validity-checks";
+
+/// Option name used to enable the checks of lossy `as` conversions.
+pub const CONVERSION_CHECKS: &str = "conversion-checks";
+
+/// Option name used to let the allocations of every harness nondeterministically fail.
+pub const NONDET_ALLOC_FAILURE: &str = "nondet-alloc-failure";
+
+/// Option name used to set the minimum size of the allocations that may fail.
+pub const NONDET_ALLOC_FAILURE_MIN_SIZE: &str = "nondet-alloc-failure-min-size";
+
+/// Option name used to restrict the allocations that may fail to the ones of a crate.
+pub const NONDET_ALLOC_FAILURE_CRATE: &str = "nondet-alloc-failure-crate";
+
+/// Option name used to enable the detection of memory leaks.
+pub const LEAK_CHECK: &str = "leak-check";
+
+/// Option name used to pass the values of `kani::any()` to `__KANI_record_nondet`, which the
+/// driver uses to search for more counterexamples.
+pub const RECORD_NONDET: &str = "record-nondet";
+
+/// Option name used to enable the vacuity checks of the assumptions and assertions of user code.
+pub const VACUITY_CHECKS: &str = "vacuity-checks";
+
 /// Option name used to pass extra rustc-options.
 pub const RUSTC_OPTIONS: &str = "rustc-options";
 
@@ -142,7 +182,7 @@ pub fn parser() -> Command {
         .arg(
             Arg::new(ASSERTION_REACH_CHECKS)
                 .long(ASSERTION_REACH_CHECKS)
-                .help("Check the reachability of every assertion.")
+                .help("Check the reachability of every assertion, and of the end of each harness.")
                 .action(ArgAction::SetTrue),
         )
         .arg(
@@ -167,11 +207,12 @@ pub fn parser() -> Command {
                 .action(ArgAction::SetTrue),
         )
         .arg(
-            // TODO: Remove this argument once stubbing works for multiple harnesses at a time.
-            // <https://github.com/model-checking/kani/issues/1841>
             Arg::new(HARNESS)
                 .long(HARNESS)
-                .help("Selects the harness to target.")
+                .help(
+                    "Selects the harness to target. With stubbing, only the stubs of this \
+                    harness are applied, and a single model is generated.",
+                )
                 .value_name("HARNESS")
                 .action(ArgAction::Set),
         )
@@ -179,7 +220,95 @@ pub fn parser() -> Command {
             Arg::new(ENABLE_STUBBING)
                 .long(ENABLE_STUBBING)
                 .help("Instruct the compiler to perform stubbing.")
-                .requires(HARNESS)
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(STUB)
+                .long(STUB)
+                .help("Stub a function/method in all harnesses (relative to the crate root).")
+                .value_name("ORIGINAL=REPLACEMENT")
+                .requires(ENABLE_STUBBING)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(NO_DEFAULT_MODELS)
+                .long(NO_DEFAULT_MODELS)
+                .help(
+                    "Do not replace the `getrandom` function of the `getrandom` crate with \
+                    Kani's model.",
+                )
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(UNINIT_CHECKS)
+                .long(UNINIT_CHECKS)
+                .help("Instrument the code to check for reads of uninitialized memory.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(ALIASING_CHECKS)
+                .long(ALIASING_CHECKS)
+                .help("Instrument the code to check the aliasing rules of references and raw pointers.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(NO_ALIGNMENT_CHECKS)
+                .long(NO_ALIGNMENT_CHECKS)
+                .help("Do not check that the raw pointers that are accessed are properly aligned.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(NO_VALIDITY_CHECKS)
+                .long(NO_VALIDITY_CHECKS)
+                .help("Do not check the validity of the values produced by unsafe operations in user code.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(CONVERSION_CHECKS)
+                .long(CONVERSION_CHECKS)
+                .value_parser(PossibleValuesParser::new(ConversionCheck::VARIANTS))
+                .value_delimiter(',')
+                .help("Check that the numeric `as` conversions of the given kinds in user code are lossless.")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(NONDET_ALLOC_FAILURE)
+                .long(NONDET_ALLOC_FAILURE)
+                .help("Let the memory allocations of every harness nondeterministically fail.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(NONDET_ALLOC_FAILURE_MIN_SIZE)
+                .long(NONDET_ALLOC_FAILURE_MIN_SIZE)
+                .value_parser(value_parser!(u64))
+                .help("Only let the allocations of at least this many bytes fail.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new(NONDET_ALLOC_FAILURE_CRATE)
+                .long(NONDET_ALLOC_FAILURE_CRATE)
+                .help("Only let the allocations requested by the code of this crate fail.")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(LEAK_CHECK)
+                .long(LEAK_CHECK)
+                .help("Check that the memory allocated by a harness is freed when it returns.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(RECORD_NONDET)
+                .long(RECORD_NONDET)
+                .help("Record the values of `kani::any()` to search for more counterexamples.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(VACUITY_CHECKS)
+                .long(VACUITY_CHECKS)
+                .help(
+                    "Check the reachability of the assumptions of user code, and report the \
+                    reachability of its assertions as vacuity checks.",
+                )
                 .action(ArgAction::SetTrue),
         );
     #[cfg(feature = "unsound_experiments")]
@@ -190,6 +319,7 @@ pub fn parser() -> Command {
 
 pub trait KaniCompilerParser {
     fn reachability_type(&self) -> ReachabilityType;
+    fn conversion_checks(&self) -> Vec<ConversionCheck>;
 }
 
 impl KaniCompilerParser for ArgMatches {
@@ -197,6 +327,12 @@ fn reachability_type(&self) -> ReachabilityType {
         self.get_one::<String>(REACHABILITY)
             .map_or(ReachabilityType::None, |arg| ReachabilityType::from_str(arg).unwrap())
     }
+
+    fn conversion_checks(&self) -> Vec<ConversionCheck> {
+        self.get_many::<String>(CONVERSION_CHECKS).map_or(vec![], |args| {
+            args.map(|arg| ConversionCheck::from_str(arg).unwrap()).collect()
+        })
+    }
 }
 
 /// Retrieves the arguments from the command line and process hack to incorporate CARGO arguments.
@@ -264,12 +400,34 @@ fn test_stubbing_flags() {
         assert!(matches.get_flag("enable-stubbing"));
         assert_eq!(matches.get_one::<String>("harness"), Some(&"foo".to_string()));
 
-        // `--enable-stubbing` cannot be called without `--harness`
+        // `--enable-stubbing` can be called without `--harness`
         let args = vec!["kani-compiler", "--enable-stubbing"];
+        let matches = parser().get_matches_from(args);
+        assert!(matches.get_flag("enable-stubbing"));
+        assert_eq!(matches.get_one::<String>("harness"), None);
+
+        let args = vec!["kani-compiler", "--enable-stubbing", "--harness", "foo", "--stub=a=b"];
+        let matches = parser().get_matches_from(args);
+        let stubs: Vec<&String> = matches.get_many::<String>("stub").unwrap().collect();
+        assert_eq!(stubs, vec!["a=b"]);
+
+        // `--stub` cannot be called without `--enable-stubbing`
+        let args = vec!["kani-compiler", "--stub=a=b"];
         let err = parser().try_get_matches_from(args).unwrap_err();
         assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
     }
 
+    #[test]
+    fn test_no_default_models_flag() {
+        let args = vec!["kani-compiler", "--goto-c"];
+        let matches = parser().get_matches_from(args);
+        assert!(!matches.get_flag("no-default-models"));
+
+        let args = vec!["kani-compiler", "--no-default-models"];
+        let matches = parser().get_matches_from(args);
+        assert!(matches.get_flag("no-default-models"));
+    }
+
     #[test]
     fn test_cargo_kani_hack_noop() {
         let args = ["kani-compiler", "some/path"];
@@ -0,0 +1,109 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Renders the description of a failed check of `--aliasing-checks` with the locations where the
+//! borrow it used was created and invalidated.
+//!
+//! The compiler assigns the ID of every borrow and access (an event) to `ALIASING_EVENT_VAR` at
+//! the location of the event. When a use of an invalidated borrow fails the check, it assigns the
+//! IDs of the events that created and invalidated the borrow to `ALIASING_BORROW_VAR` and
+//! `ALIASING_CONFLICT_VAR`. This module looks up the locations of those events in the trace.
+
+use crate::cbmc_output_parser::{filepath, TraceData, TraceItem};
+use kani_metadata::checks::{
+    ALIASING_BORROW_VAR, ALIASING_CONFLICT_VAR, ALIASING_EVENT_VAR, ALIASING_VIOLATION_DESC,
+};
+
+/// Renders the description of a failed aliasing check, e.g., `aliasing violation: use of a borrow
+/// created at src/lib.rs:3 that was invalidated at src/lib.rs:4`. Returns `None` if the check is
+/// not an aliasing check, or if the locations of its events are not in the trace.
+pub fn render_aliasing_violation(description: &str, trace: &[TraceItem]) -> Option<String> {
+    if description != ALIASING_VIOLATION_DESC {
+        return None;
+    }
+    let borrow = event_location(trace, last_value(trace, ALIASING_BORROW_VAR)?)?;
+    let conflict = event_location(trace, last_value(trace, ALIASING_CONFLICT_VAR)?)?;
+    Some(format!(
+        "aliasing violation: use of a borrow created at {borrow} that was invalidated at {conflict}"
+    ))
+}
+
+/// The last value assigned to the variable `var` in the trace.
+fn last_value<'a>(trace: &'a [TraceItem], var: &str) -> Option<&'a str> {
+    trace.iter().rev().find_map(|item| assigned_value(item, var))
+}
+
+/// The location (`<file>:<line>`) of the step that recorded the event with the given ID.
+fn event_location(trace: &[TraceItem], event: &str) -> Option<String> {
+    let item = trace.iter().find(|item| assigned_value(item, ALIASING_EVENT_VAR) == Some(event))?;
+    let location = item.source_location.as_ref()?;
+    Some(format!("{}:{}", filepath(location.file.clone()?), location.line.as_ref()?))
+}
+
+/// The value assigned by a trace step, if it assigns the variable `var`.
+fn assigned_value<'a>(item: &'a TraceItem, var: &str) -> Option<&'a str> {
+    if item.step_type != "assignment" || item.lhs.as_deref() != Some(var) {
+        return None;
+    }
+    match item.value.as_ref()?.data.as_ref()? {
+        TraceData::NonBool(data) => Some(data),
+        TraceData::Bool(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{SourceLocation, TraceValue};
+
+    fn assignment(lhs: &str, value: &str, line: usize) -> TraceItem {
+        TraceItem {
+            thread: 0,
+            step_type: "assignment".to_string(),
+            hidden: false,
+            lhs: Some(lhs.to_string()),
+            source_location: Some(SourceLocation {
+                column: Some("5".to_string()),
+                file: Some("/tmp/lib.rs".to_string()),
+                function: Some("check".to_string()),
+                line: Some(line.to_string()),
+            }),
+            value: Some(TraceValue {
+                name: "integer".to_string(),
+                binary: None,
+                data: Some(TraceData::NonBool(value.to_string())),
+                width: None,
+            }),
+            output_id: None,
+        }
+    }
+
+    #[test]
+    fn check_violation_locations() {
+        let trace = vec![
+            assignment(ALIASING_EVENT_VAR, "1", 3),
+            assignment(ALIASING_EVENT_VAR, "2", 4),
+            assignment(ALIASING_EVENT_VAR, "3", 5),
+            assignment(ALIASING_BORROW_VAR, "1", 5),
+            assignment(ALIASING_CONFLICT_VAR, "2", 5),
+        ];
+        assert_eq!(
+            render_aliasing_violation(ALIASING_VIOLATION_DESC, &trace).unwrap(),
+            format!(
+                "aliasing violation: use of a borrow created at {0}:3 that was invalidated at {0}:4",
+                filepath("/tmp/lib.rs".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn check_unknown_events() {
+        let trace = vec![
+            assignment(ALIASING_EVENT_VAR, "1", 3),
+            assignment(ALIASING_BORROW_VAR, "0", 3),
+            assignment(ALIASING_CONFLICT_VAR, "0", 3),
+        ];
+        assert_eq!(render_aliasing_violation(ALIASING_VIOLATION_DESC, &trace), None);
+        assert_eq!(render_aliasing_violation("assertion failed: x", &trace), None);
+    }
+}
@@ -4,9 +4,13 @@
 #[cfg(feature = "unsound_experiments")]
 use crate::unsound_experiments::UnsoundExperimentArgs;
 
+use clap::builder::{PossibleValuesParser, TypedValueParser};
 use clap::{error::Error, error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use kani_queries::ConversionCheck;
 use std::ffi::OsString;
 use std::path::PathBuf;
+use std::str::FromStr;
+use strum::VariantNames as _;
 
 // By default we configure CBMC to use 16 bits to represent the object bits in pointers.
 const DEFAULT_OBJECT_BITS: u32 = 16;
@@ -46,6 +50,22 @@ pub struct CargoKaniArgs {
 pub enum CargoKaniSubcommand {
     #[command(hide = true)]
     Assess,
+
+    /// Write a cargo-fuzz target that runs the given proof harness on fuzzer inputs
+    #[command(hide = true)]
+    FuzzTarget {
+        /// The proof harness to wrap
+        harness: String,
+    },
+
+    /// Convert an input found by the fuzzer into a concrete playback unit test of the harness
+    #[command(hide = true)]
+    FuzzPlayback {
+        /// The proof harness of the fuzz target
+        harness: String,
+        /// The fuzzer input, e.g. a crash artifact
+        input: PathBuf,
+    },
 }
 
 // Common arguments for invoking Kani. This gets put into KaniContext, whereas
@@ -72,6 +92,25 @@ pub struct KaniArgs {
         value_enum
     )]
     pub concrete_playback: Option<ConcretePlaybackMode>,
+    /// Report up to N distinct counterexamples for each failing harness.
+    /// After each failure, Kani asks CBMC again with the previous assignment of `kani::any()`
+    /// values excluded. Combine with `--concrete-playback` to get a unit test for each of them.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("enable_unstable"),
+        conflicts_with_all(&["visualize"]),
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub counterexamples: Option<u32>,
+    /// Once a counterexample is found, iteratively look for a failing input with smaller integer
+    /// values until it is locally minimal. The trace and the concrete playback test use that
+    /// minimal input.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"), conflicts_with_all(&["visualize"]))]
+    pub minimize_counterexample: bool,
     /// Keep temporary files generated throughout Kani process. This is already the default
     /// behavior for `cargo-kani`.
     #[arg(long, hide_short_help = true)]
@@ -180,18 +219,6 @@ pub struct KaniArgs {
     #[arg(short, long, hide = true, requires("enable_unstable"))]
     pub jobs: Option<Option<usize>>,
 
-    // Hide option till https://github.com/model-checking/kani/issues/697 is
-    // fixed.
-    /// Use abstractions for the standard library.
-    /// This is an experimental feature and requires `--enable-unstable` to be used
-    #[arg(long, hide = true, requires("enable_unstable"))]
-    pub use_abs: bool,
-    // Hide option till https://github.com/model-checking/kani/issues/697 is
-    // fixed.
-    /// Choose abstraction for modules of standard library if available
-    #[arg(long, default_value = "std", ignore_case = true, hide = true, value_enum)]
-    pub abs_type: AbstractionType,
-
     /// Enable extra pointer checks such as invalid pointers in relation operations and pointer
     /// arithmetic overflow.
     /// This feature is unstable and it may yield false counter examples. It requires
@@ -206,7 +233,8 @@ pub struct KaniArgs {
     /// Disable restricting the targets of virtual table function pointer calls
     #[arg(long, hide_short_help = true)]
     pub no_restrict_vtable: bool,
-    /// Turn off assertion reachability checks
+    /// Turn off assertion reachability checks, and the check that the end of each harness is
+    /// reachable
     #[arg(long)]
     pub no_assertion_reach_checks: bool,
 
@@ -215,6 +243,51 @@ pub struct KaniArgs {
     #[arg(long, hide_short_help = true, requires("enable_unstable"))]
     pub ignore_global_asm: bool,
 
+    /// Check that the program doesn't read uninitialized memory. Every typed read of a value
+    /// fails if one of the bytes that hold its data is uninitialized.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub uninit_checks: bool,
+
+    /// Check that unsafe code follows the aliasing rules of Rust, with a model inspired by
+    /// Stacked Borrows. Uses of references and raw pointers fail if their borrow was invalidated
+    /// by a conflicting borrow or access.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub aliasing_checks: bool,
+
+    /// Let the memory allocations of every harness nondeterministically fail, to explore the
+    /// error paths of fallible allocations (e.g., `try_reserve`). A harness can also enable this
+    /// with `#[kani::nondet_alloc_failure]`.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub nondet_alloc_failure: bool,
+    /// Only let the allocations of at least this many bytes fail
+    #[arg(long, hide_short_help = true, value_name = "BYTES", requires("enable_unstable"))]
+    pub nondet_alloc_failure_min_size: Option<u64>,
+    /// Only let the allocations requested by the code of this crate fail.
+    /// Can be specified multiple times
+    #[arg(
+        long = "nondet-alloc-failure-crate",
+        hide_short_help = true,
+        value_name = "CRATE",
+        requires("enable_unstable")
+    )]
+    pub nondet_alloc_failure_crates: Vec<String>,
+
+    /// Check that the memory allocated during the execution of a harness is freed when it
+    /// returns, and report each leaked allocation at its allocation site in user code.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub leak_check: bool,
+
+    /// Also check that the assumptions and the assertions of your code are reachable, and warn
+    /// about the ones that aren't when verification succeeds. The end of each harness is always
+    /// checked, unless assertion reachability checks are turned off.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub vacuity_checks: bool,
+
     /// Execute CBMC's sanity checks to ensure the goto-program we generate is correct.
     #[arg(long, hide_short_help = true, requires("enable_unstable"))]
     pub run_sanity_checks: bool,
@@ -237,10 +310,21 @@ pub struct KaniArgs {
         long,
         hide_short_help = true,
         requires("enable_unstable"),
-        requires("harness"),
         conflicts_with("concrete_playback")
     )]
     pub enable_stubbing: bool,
+
+    /// Stub a function/method in all harnesses, given as `<ORIGINAL>=<REPLACEMENT>`. The paths
+    /// are resolved relative to the crate root. This only has an effect with
+    /// `--enable-stubbing`. Stubs can also be configured in the `package.metadata.kani.stubs`
+    /// table of `Cargo.toml`.
+    #[arg(long = "stub", value_name = "ORIGINAL=REPLACEMENT", hide_short_help = true)]
+    pub stubs: Vec<String>,
+
+    /// Do not replace the `getrandom` function of the `getrandom` crate, the randomness source of
+    /// `rand`, with Kani's model, which is used by default (except with `--concrete-playback`).
+    #[arg(long, hide_short_help = true)]
+    pub no_default_models: bool,
 }
 
 impl KaniArgs {
@@ -288,32 +372,6 @@ pub enum OutputFormat {
     Old,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
-pub enum AbstractionType {
-    Std,
-    Kani,
-    // Clap defaults to `c-ffi`
-    CFfi,
-    // Clap defaults to `no-back`
-    NoBack,
-}
-impl std::fmt::Display for AbstractionType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Std => f.write_str("std"),
-            Self::Kani => f.write_str("kani"),
-            Self::CFfi => f.write_str("c-ffi"),
-            Self::NoBack => f.write_str("no-back"),
-        }
-    }
-}
-#[cfg(test)]
-impl AbstractionType {
-    pub fn variants() -> Vec<&'static str> {
-        vec!["std", "kani", "c-ffi", "no-back"]
-    }
-}
-
 #[derive(Debug, Parser)]
 pub struct CheckArgs {
     // Rust argument parsers (/clap) don't have the convenient '--flag' and '--no-flag' boolean pairs, so approximate
@@ -332,6 +390,20 @@ pub struct CheckArgs {
     #[arg(long)]
     pub no_memory_safety_checks: bool,
 
+    /// Turn on alignment checks of raw pointer accesses
+    #[arg(long)]
+    pub alignment_checks: bool,
+    /// Turn off alignment checks of raw pointer accesses
+    #[arg(long)]
+    pub no_alignment_checks: bool,
+
+    /// Turn on validity checks of the values produced by unsafe operations
+    #[arg(long)]
+    pub validity_checks: bool,
+    /// Turn off validity checks of the values produced by unsafe operations
+    #[arg(long)]
+    pub no_validity_checks: bool,
+
     /// Turn on default overflow checks
     #[arg(long)]
     pub overflow_checks: bool,
@@ -352,12 +424,32 @@ pub struct CheckArgs {
     /// Turn off default unwinding checks
     #[arg(long)]
     pub no_unwinding_checks: bool,
+
+    /// Turn on checks that numeric `as` conversions in your crate are lossless, for the given
+    /// comma-separated kinds of conversions. Functions annotated with
+    /// `#[kani::no_conversion_checks]` are not checked.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("enable_unstable"),
+        value_parser = PossibleValuesParser::new(ConversionCheck::VARIANTS)
+            .map(|kind| ConversionCheck::from_str(&kind).unwrap()),
+        value_delimiter = ','
+    )]
+    pub conversion_checks: Vec<ConversionCheck>,
 }
 
 impl CheckArgs {
     pub fn memory_safety_on(&self) -> bool {
         !self.no_default_checks && !self.no_memory_safety_checks || self.memory_safety_checks
     }
+    pub fn alignment_on(&self) -> bool {
+        !self.no_default_checks && !self.no_alignment_checks || self.alignment_checks
+    }
+    pub fn validity_on(&self) -> bool {
+        !self.no_default_checks && !self.no_validity_checks || self.validity_checks
+    }
     pub fn overflow_on(&self) -> bool {
         !self.no_default_checks && !self.no_overflow_checks || self.overflow_checks
     }
@@ -406,6 +498,26 @@ pub fn validate(&self) {
                 )
                 .exit()
         }
+        if matches!(self.command, Some(CargoKaniSubcommand::FuzzTarget { .. }))
+            && !self.common_opts.enable_unstable
+        {
+            Self::command()
+                .error(
+                    ErrorKind::MissingRequiredArgument,
+                    "fuzz-target is unstable and requires 'cargo kani --enable-unstable fuzz-target'",
+                )
+                .exit()
+        }
+        if matches!(self.command, Some(CargoKaniSubcommand::FuzzPlayback { .. }))
+            && !self.common_opts.enable_unstable
+        {
+            Self::command()
+                .error(
+                    ErrorKind::MissingRequiredArgument,
+                    "fuzz-playback is unstable and requires 'cargo kani --enable-unstable fuzz-playback'",
+                )
+                .exit()
+        }
     }
 }
 impl KaniArgs {
@@ -462,6 +574,20 @@ fn validate_inner(&self) -> Result<(), Error> {
                 --output-format=old.",
             ));
         }
+        if self.counterexamples.is_some() && self.output_format == OutputFormat::Old {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --counterexamples isn't compatible with \
+                --output-format=old.",
+            ));
+        }
+        if self.minimize_counterexample && self.output_format == OutputFormat::Old {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --minimize-counterexample isn't compatible with \
+                --output-format=old.",
+            ));
+        }
         if self.concrete_playback.is_some() && self.jobs() != Some(1) {
             // Concrete playback currently embeds a lot of assumptions about the order in which harnesses get called.
             return Err(Error::raw(
@@ -484,6 +610,15 @@ fn validate_inner(&self) -> Result<(), Error> {
                 "The `--dry-run` option is obsolete. Use --verbose instead.",
             ));
         }
+        if let Some(stub) = self.stubs.iter().find(|stub| !stub.contains('=')) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                &format!(
+                    "Invalid argument: `--stub` argument `{stub}` is not of the form \
+                    `<ORIGINAL>=<REPLACEMENT>`"
+                ),
+            ));
+        }
         if let Some(out_dir) = &self.target_dir {
             if out_dir.exists() && !out_dir.is_dir() {
                 return Err(Error::raw(
@@ -545,18 +680,6 @@ macro_rules! check_opt {
         };
     }
 
-    #[test]
-    fn check_abs_type() {
-        // Since we manually implemented this, consistency check it
-        for t in AbstractionType::variants() {
-            assert_eq!(t, format!("{}", AbstractionType::from_str(t, false).unwrap()));
-        }
-        check_opt!("--abs-type std", false, abs_type, AbstractionType::Std);
-        check_opt!("--abs-type kani", false, abs_type, AbstractionType::Kani);
-        check_opt!("--abs-type c-ffi", false, abs_type, AbstractionType::CFfi);
-        check_opt!("--abs-type no-back", false, abs_type, AbstractionType::NoBack);
-    }
-
     #[test]
     fn check_dry_run_fails() {
         // We don't support --dry-run anymore but we print a friendly reminder for now.
@@ -565,6 +688,34 @@ fn check_dry_run_fails() {
         assert_eq!(err.kind(), ErrorKind::ValueValidation);
     }
 
+    #[test]
+    fn check_fuzz_target_subcommand() {
+        let args =
+            CargoKaniArgs::parse_from(vec!["cargo-kani", "--enable-unstable", "fuzz-target", "h"]);
+        assert!(matches!(
+            args.command,
+            Some(CargoKaniSubcommand::FuzzTarget { harness }) if harness == "h"
+        ));
+    }
+
+    #[test]
+    fn check_fuzz_playback_subcommand() {
+        let args = CargoKaniArgs::parse_from(vec![
+            "cargo-kani",
+            "--enable-unstable",
+            "--concrete-playback=inplace",
+            "fuzz-playback",
+            "h",
+            "crash-123",
+        ]);
+        assert!(matches!(
+            args.command,
+            Some(CargoKaniSubcommand::FuzzPlayback { harness, input })
+                if harness == "h" && input == PathBuf::from("crash-123")
+        ));
+        assert_eq!(args.common_opts.concrete_playback, Some(ConcretePlaybackMode::InPlace));
+    }
+
     /// Kani should fail if the argument given is not a file.
     #[test]
     fn check_invalid_input_fails() {
@@ -591,11 +742,6 @@ fn parse_unstable_enabled(args: &str) -> Result<StandaloneArgs, Error> {
         StandaloneArgs::try_parse_from(args.split(' '))
     }
 
-    #[test]
-    fn check_abs_unstable() {
-        check_unstable_flag!("--use-abs", use_abs);
-    }
-
     #[test]
     fn check_restrict_vtable_unstable() {
         check_unstable_flag!("--restrict-vtable", restrict_vtable);
@@ -611,6 +757,43 @@ fn check_disable_slicing_unstable() {
         check_unstable_flag!("--no-slice-formula", no_slice_formula);
     }
 
+    #[test]
+    fn check_uninit_checks_unstable() {
+        check_unstable_flag!("--uninit-checks", uninit_checks);
+    }
+
+    #[test]
+    fn check_aliasing_checks_unstable() {
+        check_unstable_flag!("--aliasing-checks", aliasing_checks);
+    }
+
+    #[test]
+    fn check_nondet_alloc_failure_unstable() {
+        check_unstable_flag!("--nondet-alloc-failure", nondet_alloc_failure);
+        check_opt!(
+            "--nondet-alloc-failure-min-size 1024",
+            true,
+            nondet_alloc_failure_min_size,
+            Some(1024)
+        );
+        check_opt!(
+            "--nondet-alloc-failure-crate a --nondet-alloc-failure-crate b",
+            true,
+            nondet_alloc_failure_crates,
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn check_leak_check_unstable() {
+        check_unstable_flag!("--leak-check", leak_check);
+    }
+
+    #[test]
+    fn check_vacuity_checks_unstable() {
+        check_unstable_flag!("--vacuity-checks", vacuity_checks);
+    }
+
     #[test]
     fn check_concrete_playback_unstable() {
         check_opt!(
@@ -627,6 +810,33 @@ fn check_concrete_playback_unstable() {
         );
     }
 
+    #[test]
+    fn check_counterexamples_unstable() {
+        check_opt!("--counterexamples 3", true, counterexamples, Some(3));
+
+        // At least one counterexample must be requested.
+        let err = parse_unstable_enabled("--counterexamples 0").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+
+        // `--counterexamples` cannot be called with `--visualize`
+        let err = parse_unstable_enabled("--counterexamples 2 --visualize").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+
+        expect_validation_error(
+            "kani --counterexamples 2 --output-format=old --enable-unstable test.rs",
+            ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn check_minimize_counterexample_unstable() {
+        check_unstable_flag!("--minimize-counterexample", minimize_counterexample);
+        expect_validation_error(
+            "kani --minimize-counterexample --output-format=old --enable-unstable test.rs",
+            ErrorKind::ArgumentConflict,
+        );
+    }
+
     /// Check if parsing the given argument string results in the given error.
     fn expect_validation_error(arg: &str, err: ErrorKind) {
         let args = StandaloneArgs::parse_from(arg.split_whitespace());
@@ -649,9 +859,8 @@ fn check_concrete_playback_conflicts() {
     fn check_enable_stubbing() {
         check_unstable_flag!("--enable-stubbing --harness foo", enable_stubbing);
 
-        // `--enable-stubbing` cannot be called without `--harness`
-        let err = parse_unstable_enabled("--enable-stubbing").unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        // `--enable-stubbing` can be called without `--harness`
+        check_unstable_flag!("--enable-stubbing", enable_stubbing);
 
         // `--enable-stubbing` cannot be called with `--concrete-playback`
         let err =
@@ -659,4 +868,42 @@ fn check_enable_stubbing() {
                 .unwrap_err();
         assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
     }
+
+    #[test]
+    fn check_stubs() {
+        let args = parse_unstable_enabled(
+            "--enable-stubbing --harness foo --stub net::connect=mocks::connect --stub a=b",
+        )
+        .unwrap();
+        assert_eq!(args.common_opts.stubs, vec!["net::connect=mocks::connect", "a=b"]);
+
+        let args =
+            parse_unstable_enabled("--enable-stubbing --harness foo --stub net::connect").unwrap();
+        let err = args.common_opts.validate_inner().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn check_no_default_models() {
+        check_opt!("--harness foo", false, no_default_models, false);
+        check_opt!("--no-default-models", false, no_default_models, true);
+    }
+
+    #[test]
+    fn check_conversion_checks() {
+        let args = parse_unstable_disabled("--harness foo").unwrap();
+        assert!(args.common_opts.checks.conversion_checks.is_empty());
+
+        let args = parse_unstable_enabled("--conversion-checks truncation,float-to-int").unwrap();
+        assert_eq!(
+            args.common_opts.checks.conversion_checks,
+            vec![ConversionCheck::Truncation, ConversionCheck::FloatToInt]
+        );
+
+        let err = parse_unstable_disabled("--conversion-checks truncation").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let err = parse_unstable_enabled("--conversion-checks rounding").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
 }
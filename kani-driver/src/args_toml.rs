@@ -95,6 +95,22 @@ fn toml_to_args(tomldata: &str) -> Result<(Vec<OsString>, Vec<OsString>)> {
     let mut args = Vec::new();
     let mut cbmc_args = Vec::new();
 
+    // Stubs that apply to all harnesses. The ones of a package override the ones of the
+    // workspace for the same original function/method.
+    let mut stubs: BTreeMap<String, Value> = BTreeMap::new();
+    for table in ["workspace.metadata.kani.stubs", "package.metadata.kani.stubs", "kani.stubs"] {
+        if let Some(val) = get_table(&config, table) {
+            stubs.extend(val.iter().map(|(x, y)| (x.to_owned(), y.to_owned())));
+        }
+    }
+    for (original, replacement) in stubs {
+        let Some(replacement) = replacement.as_str() else {
+            bail!("stub for {} is not a string", original);
+        };
+        args.push("--stub".into());
+        args.push(format!("{original}={replacement}").into());
+    }
+
     for (flag, value) in map {
         if flag == "cbmc-args" {
             // --cbmc-args has to come last because it eats all remaining arguments
@@ -165,6 +181,32 @@ fn check_toml_parsing() {
         assert_eq!(b.1, vec!["--cbmc-args", "--fake"]);
     }
 
+    #[test]
+    fn check_toml_stubs() {
+        let a = "[workspace.metadata.kani.stubs]
+                      \"net::connect\" = \"mocks::connect\"
+                      \"net::send\" = \"mocks::send\"
+                 [package.metadata.kani.stubs]
+                      \"net::send\" = \"mocks::send_all\"
+                 [package.metadata.kani.flags]
+                      enable-stubbing = true";
+        let b = toml_to_args(a).unwrap();
+        assert_eq!(
+            b.0,
+            vec![
+                "--stub",
+                "net::connect=mocks::connect",
+                "--stub",
+                "net::send=mocks::send_all",
+                "--enable-stubbing"
+            ]
+        );
+
+        let a = "[package.metadata.kani.stubs]
+                      \"net::connect\" = 42";
+        assert!(toml_to_args(a).is_err());
+    }
+
     #[test]
     fn check_merge_args_with_only_command_line_args() {
         let cmd_args: Vec<OsString> =
@@ -105,6 +105,12 @@ pub fn cargo_build(&self) -> Result<CargoOutputs> {
 
         // Only joing them at the end. All kani flags must come first.
         kani_args.extend_from_slice(&rustc_args);
+        // Quote the flags, since the compiler splits `KANIFLAGS` like a shell would (e.g., stubs
+        // may contain spaces).
+        let kani_flags: Vec<OsString> = kani_args
+            .iter()
+            .map(|arg| shell_words::quote(&arg.to_string_lossy()).into_owned().into())
+            .collect();
 
         let mut found_target = false;
         let packages = packages_to_verify(&self.args, &metadata);
@@ -117,7 +123,7 @@ pub fn cargo_build(&self) -> Result<CargoOutputs> {
                     .args(&pkg_args)
                     .env("RUSTC", &self.kani_compiler)
                     .env("RUSTFLAGS", "--kani-flags")
-                    .env("KANIFLAGS", &crate::util::join_osstring(&kani_args, " "));
+                    .env("KANIFLAGS", &crate::util::join_osstring(&kani_flags, " "));
 
                 self.run_terminal(cmd)?;
                 found_target = true;
@@ -138,7 +144,7 @@ pub fn cargo_build(&self) -> Result<CargoOutputs> {
 }
 
 /// Given a `path` with glob characters in it (e.g. `*.json`), return a vector of matching files
-fn glob(path: &Path) -> Result<Vec<PathBuf>> {
+pub(crate) fn glob(path: &Path) -> Result<Vec<PathBuf>> {
     let results = glob::glob(path.to_str().context("Non-UTF-8 path enountered")?)?;
     // the logic to turn "Iter<Result<T, E>>" into "Result<Vec<T>, E>" doesn't play well
     // with anyhow, so a type annotation is required
@@ -80,7 +80,6 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
             }
         };
 
-        self.gen_and_add_concrete_playback(harness, &verification_results)?;
         Ok(verification_results)
     }
 
@@ -124,14 +123,17 @@ pub fn cbmc_flags(
             args.push("--validate-ssa-equation".into());
         }
 
-        if !self.args.visualize
-            && self.args.concrete_playback.is_none()
-            && !self.args.no_slice_formula
-        {
+        // Concrete playback and the counterexample search all extract the values of
+        // `kani::any()` from the trace.
+        let needs_trace = self.args.concrete_playback.is_some()
+            || self.args.counterexamples.is_some()
+            || self.args.minimize_counterexample;
+
+        if !self.args.visualize && !needs_trace && !self.args.no_slice_formula {
             args.push("--slice-formula".into());
         }
 
-        if self.args.concrete_playback.is_some() {
+        if needs_trace {
             args.push("--trace".into());
         }
 
@@ -160,12 +162,11 @@ pub fn cbmc_check_flags(&self) -> Vec<OsString> {
             // --signed-overflow-check
             // So these options are deliberately skipped to avoid erroneously re-checking operations.
 
-            // TODO: Implement conversion checks as an optional check.
-            // They are a well defined operation in rust, but they may yield unexpected results to
-            // many users. https://github.com/model-checking/kani/issues/840
-            // We might want to create a transformation pass instead of enabling CBMC since Kani
-            // compiler sometimes rely on the bitwise conversion of signed <-> unsigned.
-            // args.push("--conversion-check".into());
+            // Conversions are a well defined operation in rust, but they may yield unexpected
+            // results to many users. https://github.com/model-checking/kani/issues/840
+            // We don't use CBMC's --conversion-check since Kani compiler sometimes rely on the
+            // bitwise conversion of signed <-> unsigned. Instead, the compiler instruments the
+            // `as` conversions in user code with `--conversion-checks`.
         }
 
         if self.args.checks.unwinding_on() {
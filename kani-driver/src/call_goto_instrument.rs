@@ -5,7 +5,7 @@
 use std::ffi::OsString;
 use std::fs::File;
 use std::io::BufReader;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::metadata::collect_and_link_function_pointer_restrictions;
@@ -16,15 +16,18 @@
 
 impl KaniSession {
     /// Instrument and optimize a goto binary in-place.
+    ///
+    /// `extra_inputs` are linked into the model while it is specialized to `harness`.
     pub fn instrument_model(
         &self,
         input: &Path,
         output: &Path,
         project: &Project,
         harness: &HarnessMetadata,
+        extra_inputs: &[PathBuf],
     ) -> Result<()> {
         // We actually start by calling goto-cc to start the specialization:
-        self.specialize_to_proof_harness(input, output, &harness.mangled_name)?;
+        self.specialize_to_proof_harness(input, extra_inputs, output, &harness.mangled_name)?;
 
         let restrictions = project.get_harness_artifact(&harness, ArtifactType::VTableRestriction);
         if let Some(restrictions_path) = restrictions {
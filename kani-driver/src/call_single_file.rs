@@ -89,9 +89,52 @@ pub fn kani_specific_flags(&self) -> Vec<OsString> {
         if self.args.ignore_global_asm {
             flags.push("--ignore-global-asm".into());
         }
+        if self.args.uninit_checks {
+            flags.push("--uninit-checks".into());
+        }
+        if self.args.aliasing_checks {
+            flags.push("--aliasing-checks".into());
+        }
+        if self.args.nondet_alloc_failure {
+            flags.push("--nondet-alloc-failure".into());
+        }
+        if let Some(min_size) = self.args.nondet_alloc_failure_min_size {
+            flags.push(format!("--nondet-alloc-failure-min-size={min_size}").into());
+        }
+        for krate in &self.args.nondet_alloc_failure_crates {
+            flags.push(format!("--nondet-alloc-failure-crate={krate}").into());
+        }
+        if self.args.leak_check {
+            flags.push("--leak-check".into());
+        }
+        if self.args.counterexamples.is_some() || self.args.minimize_counterexample {
+            flags.push("--record-nondet".into());
+        }
+        if self.args.vacuity_checks {
+            flags.push("--vacuity-checks".into());
+        }
+        if !self.args.checks.alignment_on() {
+            flags.push("--no-alignment-checks".into());
+        }
+        if !self.args.checks.validity_on() {
+            flags.push("--no-validity-checks".into());
+        }
+        if !self.args.checks.conversion_checks.is_empty() {
+            let kinds: Vec<_> =
+                self.args.checks.conversion_checks.iter().map(|kind| kind.as_ref()).collect();
+            flags.push(format!("--conversion-checks={}", kinds.join(",")).into());
+        }
 
         if self.args.enable_stubbing {
             flags.push("--enable-stubbing".into());
+            for stub in &self.args.stubs {
+                flags.push(format!("--stub={stub}").into());
+            }
+        }
+        // The default model is not applied when a concrete playback test runs, so it is disabled
+        // when generating one.
+        if self.args.no_default_models || self.args.concrete_playback.is_some() {
+            flags.push("--no-default-models".into());
         }
         if let Some(harness) = &self.args.harness {
             flags.push(format!("--harness={harness}").into());
@@ -107,15 +150,6 @@ pub fn kani_specific_flags(&self) -> Vec<OsString> {
     /// These are also used by call_cargo to pass as the env var KANIFLAGS.
     pub fn kani_rustc_flags(&self) -> Vec<OsString> {
         let mut flags = Vec::<OsString>::new();
-        if self.args.use_abs {
-            flags.push("-Z".into());
-            flags.push("force-unstable-if-unmarked=yes".into()); // ??
-            flags.push("--cfg=use_abs".into());
-            flags.push("--cfg".into());
-            let abs_type = format!("abs_type={}", self.args.abs_type.to_string().to_lowercase());
-            flags.push(abs_type.into());
-        }
-
         if let Some(seed_opt) = self.args.randomize_layout {
             flags.push("-Z".into());
             flags.push("randomize-layout".into());
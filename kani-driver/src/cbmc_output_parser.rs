@@ -83,6 +83,7 @@ pub struct PropertyId {
 
 impl Property {
     const COVER_PROPERTY_CLASS: &str = "cover";
+    const VACUITY_CHECK_CLASS: &str = "vacuity_check";
 
     pub fn property_class(&self) -> String {
         self.property_id.class.clone()
@@ -93,6 +94,12 @@ pub fn is_cover_property(&self) -> bool {
         self.property_id.class == Self::COVER_PROPERTY_CLASS
     }
 
+    /// Returns true if this is a check that the harness isn't vacuous (e.g., that its end is
+    /// reachable). These are covers too, but they aren't reported as properties.
+    pub fn is_vacuity_check(&self) -> bool {
+        self.property_id.class == Self::VACUITY_CHECK_CLASS
+    }
+
     pub fn property_name(&self) -> String {
         let class = &self.property_id.class;
         let id = self.property_id.id;
@@ -240,7 +247,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 }
 
 /// Returns a path relative to the current working directory.
-fn filepath(file: String) -> String {
+pub fn filepath(file: String) -> String {
     let file_path = PathBuf::from(file.clone());
     let cur_dir = env::current_dir().unwrap();
 
@@ -265,6 +272,9 @@ pub struct TraceItem {
     pub lhs: Option<String>,
     pub source_location: Option<SourceLocation>,
     pub value: Option<TraceValue>,
+    /// The ID of an output step (e.g., the message of a `kani::log!` call).
+    #[serde(rename = "outputID")]
+    pub output_id: Option<String>,
 }
 
 /// Struct that represents a trace value.
@@ -1,13 +1,18 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::aliasing::render_aliasing_violation;
 use crate::args::OutputFormat;
-use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, TraceItem};
+use crate::cbmc_output_parser::{filepath, CheckStatus, ParserItem, Property, TraceItem};
+use crate::fmt_args::{log_entries, render_failure_message};
 use console::style;
+use kani_metadata::checks::{
+    VACUITY_ASSUMPTION_DESC, VACUITY_ASSUMPTION_HOLDS_DESC, VACUITY_HARNESS_END_DESC,
+};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use rustc_demangle::demangle;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 type CbmcAltDescriptions = HashMap<&'static str, Vec<(&'static str, Option<&'static str>)>>;
 
@@ -152,6 +157,9 @@
 const UNWINDING_ASSERT_DESC: &str = "unwinding assertion loop";
 const DEFAULT_ASSERTION: &str = "assertion";
 const REACH_CHECK_DESC: &str = "[KANI_REACHABILITY_CHECK]";
+/// The description of the vacuity checks of the assertions of user code, which we copy from
+/// their reachability checks (see `add_assertion_vacuity_checks`).
+const VACUITY_ASSERTION_DESC: &str = "assertion is reachable";
 
 impl ParserItem {
     /// Determines if an item must be skipped or not.
@@ -258,6 +266,10 @@ pub fn format_result(properties: &Vec<Property>, show_checks: bool) -> String {
     }
 
     for prop in properties {
+        if prop.is_vacuity_check() {
+            // These are reported as warnings below.
+            continue;
+        }
         let name = prop.property_name();
         let status = &prop.status;
         let description = &prop.description;
@@ -323,7 +335,8 @@ pub fn format_result(properties: &Vec<Property>, show_checks: bool) -> String {
         + number_covers_unsatisfiable
         + number_covers_undetermined;
 
-    let number_properties = properties.len() - number_cover_properties;
+    let number_vacuity_checks = properties.iter().filter(|prop| prop.is_vacuity_check()).count();
+    let number_properties = properties.len() - number_cover_properties - number_vacuity_checks;
 
     let summary = format!("\n ** {number_checks_failed} of {} failed", number_properties);
     result_str.push_str(&summary);
@@ -372,6 +385,18 @@ pub fn format_result(properties: &Vec<Property>, show_checks: bool) -> String {
     for prop in failed_tests {
         let failure_message = build_failure_message(prop.description.clone(), &prop.trace.clone());
         result_str.push_str(&failure_message);
+        result_str.push_str(&format_log(&prop.trace));
+    }
+
+    // Satisfied cover properties are only reported here if they logged messages.
+    for prop in properties
+        .iter()
+        .filter(|prop| prop.is_cover_property() && prop.status == CheckStatus::Satisfied)
+    {
+        let log = format_log(&prop.trace);
+        if !log.is_empty() {
+            result_str.push_str(&format!("Satisfied Cover: {}\n{log}", prop.description));
+        }
     }
 
     let verification_result =
@@ -379,6 +404,11 @@ pub fn format_result(properties: &Vec<Property>, show_checks: bool) -> String {
     let overall_result = format!("\nVERIFICATION:- {verification_result}\n");
     result_str.push_str(&overall_result);
 
+    // A vacuous harness can only be mistaken for a successful one.
+    if number_checks_failed == 0 {
+        result_str.push_str(&format_vacuity_warnings(properties));
+    }
+
     // Ideally, we should generate two `ParserItem::Message` and push them
     // into the parser iterator so they are the next messages to be processed.
     // However, we haven't figured out the best way to do this for now.
@@ -398,10 +428,90 @@ pub fn format_result(properties: &Vec<Property>, show_checks: bool) -> String {
     result_str
 }
 
+/// Formats the warnings about the vacuity checks that are unsatisfiable, i.e., the locations
+/// that the harness can't reach although it should:
+///  * The end of the harness.
+///  * The assumptions of user code, and the code after them. If an assumption is reachable but
+///    the code after it isn't, the assumption makes the remaining path infeasible.
+///  * The assertions of user code.
+///
+/// A location may have several checks (e.g., in a generic function), so it's only considered
+/// unreachable if all of them are unsatisfiable.
+///
+/// We also warn if none of the assertions of the harness is reachable, according to their
+/// reachability checks, whether they are in user code or not.
+fn format_vacuity_warnings(properties: &[Property]) -> String {
+    let mut unreachable: BTreeMap<&str, BTreeMap<String, bool>> = BTreeMap::new();
+    for prop in properties.iter().filter(|prop| prop.is_vacuity_check()) {
+        let is_unreachable = prop.status == CheckStatus::Unsatisfiable;
+        unreachable
+            .entry(prop.description.as_str())
+            .or_default()
+            .entry(prop.source_location.to_string())
+            .and_modify(|all_unreachable| *all_unreachable &= is_unreachable)
+            .or_insert(is_unreachable);
+    }
+    let unreachable_locations = |description: &str| -> Vec<String> {
+        unreachable
+            .get(description)
+            .map(|locations| {
+                locations
+                    .iter()
+                    .filter(|(_, is_unreachable)| **is_unreachable)
+                    .map(|(location, _)| location.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut warnings = String::new();
+    if !unreachable_locations(VACUITY_HARNESS_END_DESC).is_empty() {
+        warnings.push_str(
+            "** WARNING: The end of the harness is unreachable, so its verification may be \
+            vacuous. Check that its assumptions are not contradictory.\n",
+        );
+    }
+    let infeasible_assumptions = unreachable_locations(VACUITY_ASSUMPTION_HOLDS_DESC);
+    let unreachable_assumptions = unreachable_locations(VACUITY_ASSUMPTION_DESC);
+    for location in infeasible_assumptions {
+        if !unreachable_assumptions.contains(&location) {
+            warnings.push_str(&format!(
+                "** WARNING: The assumption at {location} makes the remaining path infeasible.\n"
+            ));
+        }
+    }
+    let mut assertions = properties
+        .iter()
+        .filter(|prop| prop.property_class() == "assertion" && prop.reach.is_some())
+        .peekable();
+    if assertions.peek().is_some() && assertions.all(|prop| prop.status == CheckStatus::Unreachable)
+    {
+        warnings.push_str("** WARNING: None of the assertions of the harness is reachable.\n");
+    } else {
+        for location in unreachable_locations(VACUITY_ASSERTION_DESC) {
+            warnings
+                .push_str(&format!("** WARNING: The assertion at {location} is unreachable.\n"));
+        }
+    }
+    warnings
+}
+
 /// Attempts to build a message for a failed property with as much detailed
 /// information on the source location as possible.
+/// The description of an aliasing violation is rendered with the locations of its borrows. Other
+/// descriptions are followed by the message rendered with the values of the arguments recorded in
+/// the trace, if any.
 fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) -> String {
-    let backup_failure_message = format!("Failed Checks: {description}\n");
+    let steps = trace.as_deref().unwrap_or_default();
+    let (description, message) = match render_aliasing_violation(&description, steps) {
+        Some(violation) => (violation, None),
+        None => {
+            let message = render_failure_message(&description, steps);
+            (description, message)
+        }
+    };
+    let message = message.map(|message| format!(" Message: {message}\n")).unwrap_or_default();
+    let backup_failure_message = format!("Failed Checks: {description}\n{message}");
     if trace.is_none() {
         return backup_failure_message;
     }
@@ -421,13 +531,32 @@ fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) ->
         let failure_function = failure_source.function.unwrap();
         let failure_line = failure_source.line.unwrap();
         return format!(
-            "Failed Checks: {}\n File: \"{}\", line {}, in {}\n",
-            description, failure_file, failure_line, failure_function
+            "Failed Checks: {}\n{} File: \"{}\", line {}, in {}\n",
+            description, message, failure_file, failure_line, failure_function
         );
     }
     backup_failure_message
 }
 
+/// Formats the messages logged with `kani::log!` along a trace, or returns an empty string if
+/// there are none.
+fn format_log(trace: &Option<Vec<TraceItem>>) -> String {
+    let entries = log_entries(trace.as_deref().unwrap_or_default());
+    if entries.is_empty() {
+        return String::new();
+    }
+    let mut log = String::from(" Log:\n");
+    for entry in entries {
+        match entry.location.and_then(|location| Some((location.file?, location.line?))) {
+            Some((file, line)) => {
+                log.push_str(&format!("  [{}:{line}] {}\n", filepath(file), entry.message))
+            }
+            None => log.push_str(&format!("  {}\n", entry.message)),
+        }
+    }
+    log
+}
+
 /// Edits an error message.
 ///
 /// At present, we only know one case where CBMC emits an error message, related
@@ -470,6 +599,9 @@ pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) ->
         modify_undefined_function_checks(properties);
     // Split all properties into two groups: Regular properties and reachability checks
     let (properties_without_reachs, reach_checks) = filter_reach_checks(properties_with_undefined);
+    // Keep the reachability checks of user assertions as vacuity checks
+    let properties_without_reachs =
+        add_assertion_vacuity_checks(properties_without_reachs, &reach_checks);
     // Filter out successful sanity checks introduced during compilation
     let properties_without_sanity_checks = filter_sanity_checks(properties_without_reachs);
     // Annotate properties with the results of reachability checks
@@ -586,14 +718,14 @@ fn update_properties_with_reach_status(
     properties
 }
 
-/// Update the results of cover properties.
+/// Update the results of cover properties (including vacuity checks).
 /// We encode cover(cond) as assert(!cond), so if the assertion
 /// fails, then the cover property is satisfied and vice versa.
 /// - SUCCESS -> UNSATISFIABLE
 /// - FAILURE -> SATISFIED
 fn update_results_of_cover_checks(mut properties: Vec<Property>) -> Vec<Property> {
     for prop in properties.iter_mut() {
-        if prop.is_cover_property() {
+        if prop.is_cover_property() || prop.is_vacuity_check() {
             if prop.status == CheckStatus::Success {
                 prop.status = CheckStatus::Unsatisfiable;
             } else if prop.status == CheckStatus::Failure {
@@ -643,6 +775,22 @@ fn filter_reach_checks(properties: Vec<Property>) -> (Vec<Property>, Vec<Propert
     filter_properties(properties, REACH_CHECK_DESC)
 }
 
+/// With `--vacuity-checks`, the reachability checks of the assertions of user code are also
+/// vacuity checks. This adds a copy of each of them with the description of an assertion vacuity
+/// check, so that we can warn about the unreachable ones like for the other vacuity checks.
+fn add_assertion_vacuity_checks(
+    mut properties: Vec<Property>,
+    reach_checks: &[Property],
+) -> Vec<Property> {
+    properties.extend(
+        reach_checks.iter().filter(|prop| prop.is_vacuity_check()).map(|prop| Property {
+            description: VACUITY_ASSERTION_DESC.to_string(),
+            ..prop.clone()
+        }),
+    );
+    properties
+}
+
 /// Filters out Kani-generated sanity checks with a `SUCCESS` status
 fn filter_sanity_checks(properties: Vec<Property>) -> Vec<Property> {
     properties
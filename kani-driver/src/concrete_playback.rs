@@ -42,36 +42,48 @@ pub fn gen_and_add_concrete_playback(
                     harness.pretty_name
                 ),
                 Some(concrete_vals) => {
-                    let concrete_playback =
-                        format_unit_test(&harness, &concrete_vals, self.args.randomize_layout);
-                    match playback_mode {
-                        ConcretePlaybackMode::Print => {
-                            println!(
-                                "Concrete playback unit test for `{}`:\n```\n{}\n```",
-                                &harness.pretty_name, &concrete_playback.unit_test_str
-                            );
-                            println!(
-                                "INFO: To automatically add the concrete playback unit test `{}` to the \
-                        src code, run Kani with `--concrete-playback=inplace`.",
-                                &concrete_playback.unit_test_name
-                            );
-                        }
-                        ConcretePlaybackMode::InPlace => {
-                            if !self.args.quiet {
-                                println!(
-                                    "INFO: Now modifying the source code to include the concrete playback unit test `{}`.",
-                                    &concrete_playback.unit_test_name
-                                );
-                            }
-                            self.modify_src_code(
-                                &harness.original_file,
-                                harness.original_end_line,
-                                &concrete_playback,
-                            )
-                            .expect("Failed to modify source code");
-                        }
-                    }
+                    self.add_concrete_playback(harness, playback_mode, &concrete_vals)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate a concrete playback unit test that gives `concrete_vals` to the `kani::any()`
+    /// calls of `harness`, and print it or add it to the source code.
+    pub fn add_concrete_playback(
+        &self,
+        harness: &HarnessMetadata,
+        playback_mode: ConcretePlaybackMode,
+        concrete_vals: &[ConcreteVal],
+    ) -> Result<()> {
+        let concrete_playback =
+            format_unit_test(harness, concrete_vals, self.args.randomize_layout);
+        match playback_mode {
+            ConcretePlaybackMode::Print => {
+                println!(
+                    "Concrete playback unit test for `{}`:\n```\n{}\n```",
+                    &harness.pretty_name, &concrete_playback.unit_test_str
+                );
+                println!(
+                    "INFO: To automatically add the concrete playback unit test `{}` to the \
+            src code, run Kani with `--concrete-playback=inplace`.",
+                    &concrete_playback.unit_test_name
+                );
+            }
+            ConcretePlaybackMode::InPlace => {
+                if !self.args.quiet {
+                    println!(
+                        "INFO: Now modifying the source code to include the concrete playback unit test `{}`.",
+                        &concrete_playback.unit_test_name
+                    );
                 }
+                self.modify_src_code(
+                    &harness.original_file,
+                    harness.original_end_line,
+                    &concrete_playback,
+                )
+                .expect("Failed to modify source code");
             }
         }
         Ok(())
@@ -226,15 +238,18 @@ fn format_unit_test(
     vec![concrete_val_1],
     // interp_concrete_val_2
     vec![concrete_val_2], ...
+    The comment is omitted for values without an interpretation (e.g., from a fuzzer input).
     */
     let vec_whitespace = " ".repeat(8);
     let vecs_as_str = concrete_vals
         .iter()
         .map(|concrete_val| {
-            format!(
-                "{vec_whitespace}// {}\n{vec_whitespace}vec!{:?}",
-                concrete_val.interp_val, concrete_val.byte_arr
-            )
+            let comment = if concrete_val.interp_val.is_empty() {
+                String::new()
+            } else {
+                format!("{vec_whitespace}// {}\n", concrete_val.interp_val)
+            };
+            format!("{comment}{vec_whitespace}vec!{:?}", concrete_val.byte_arr)
         })
         .collect::<Vec<String>>()
         .join(",\n");
@@ -299,9 +314,10 @@ struct UnitTest {
 ///         ..., ] }
 ///     ..., ] }
 /// ```
-mod concrete_vals_extractor {
+pub(crate) mod concrete_vals_extractor {
     use crate::cbmc_output_parser::{CheckStatus, Property, TraceItem};
 
+    #[derive(Clone)]
     pub struct ConcreteVal {
         pub byte_arr: Vec<u8>,
         pub interp_val: String,
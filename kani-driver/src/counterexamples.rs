@@ -0,0 +1,453 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for searching for more counterexamples of a harness once a first one was found.
+//! This implements `--counterexamples N` and `--minimize-counterexample`.
+//!
+//! With these options, the compiler passes every value produced by `kani::any()` to
+//! `__KANI_record_nondet` (see its `--record-nondet` option), which is defined as a weak no-op in
+//! `kani_lib.c`. To constrain the inputs of the next CBMC call, we generate a C file with a strong
+//! definition of that function, link it into the model of the harness and invoke CBMC again.
+
+use crate::args::OutputFormat;
+use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::concrete_playback::concrete_vals_extractor::{extract_harness_values, ConcreteVal};
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::util::alter_extension;
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Upper bound on the number of CBMC calls spent minimizing a single counterexample.
+const MAX_MINIMIZATION_STEPS: usize = 64;
+
+/// Everything needed to check a harness again under a new definition of `__KANI_record_nondet`.
+pub(crate) struct CounterexampleSearch<'a> {
+    pub session: &'a KaniSession,
+    pub project: &'a Project,
+    pub harness: &'a HarnessMetadata,
+    /// The linked model of the project.
+    pub goto_file: &'a Path,
+    /// The model specialized to `harness`, which was checked to obtain the first result.
+    pub specialized_obj: &'a Path,
+}
+
+impl<'a> CounterexampleSearch<'a> {
+    /// Search for more counterexamples after the one reported in `first_result`.
+    /// Each new counterexample is printed (and played back, if requested) as it is found.
+    pub fn enumerate(&self, first_result: &VerificationResult) -> Result<()> {
+        let args = &self.session.args;
+        let max = args.counterexamples.unwrap_or(1);
+        let mut blocked: Vec<Vec<ConcreteVal>> = vec![];
+        let mut last_result: Option<VerificationResult> = None;
+
+        for index in 2..=max {
+            let previous = last_result.as_ref().unwrap_or(first_result);
+            let Some(concrete_vals) = failing_input(previous) else {
+                println!(
+                    "WARNING: Kani could not search for more counterexamples of `{}` because \
+                    there were no failing panic checks.",
+                    self.harness.pretty_name
+                );
+                break;
+            };
+            if concrete_vals.is_empty() {
+                if !args.quiet {
+                    println!(
+                        "INFO: The failure of `{}` does not depend on any `kani::any()` value, \
+                        so there are no other counterexamples.",
+                        self.harness.pretty_name
+                    );
+                }
+                break;
+            }
+            blocked.push(concrete_vals);
+
+            let code = record_nondet_definition(&blocked, None);
+            let mut result = self.check(&format!("counterexample-{index}"), code)?;
+            if result.status == VerificationStatus::Success {
+                if !args.quiet {
+                    println!(
+                        "INFO: Found {} distinct counterexample(s) for `{}`; there are no others.",
+                        index - 1,
+                        self.harness.pretty_name
+                    );
+                }
+                break;
+            }
+            if args.minimize_counterexample {
+                result = self.minimize(&format!("counterexample-{index}"), &blocked, result)?;
+            }
+            if !args.quiet {
+                println!("Counterexample {index} for harness {}:", self.harness.pretty_name);
+                if args.output_format != OutputFormat::Old {
+                    println!("{}", result.render(&args.output_format));
+                }
+            }
+            self.session.gen_and_add_concrete_playback(self.harness, &result)?;
+            last_result = Some(result);
+        }
+        Ok(())
+    }
+
+    /// Iteratively look for a failing input whose integer values are smaller than the ones in
+    /// `result`, until no smaller one exists. Executions that reproduce one of the `blocked`
+    /// inputs are still excluded.
+    ///
+    /// Each step first asks for one of the integer inputs to at least halve in magnitude, and
+    /// only falls back to asking for any decrease if that fails. The result is locally minimal:
+    /// no integer input can be decreased without increasing another one.
+    pub fn minimize(
+        &self,
+        label: &str,
+        blocked: &[Vec<ConcreteVal>],
+        mut result: VerificationResult,
+    ) -> Result<VerificationResult> {
+        let mut shrink = Shrink::Halve;
+        // Candidates that fail before reaching the last `kani::any()` of the current input
+        // escape the constraint of the generated code, so we check it here and exclude the
+        // ones that aren't smaller from the next step.
+        let mut rejected: Vec<Vec<ConcreteVal>> = vec![];
+        for step in 0..MAX_MINIMIZATION_STEPS {
+            let Some(current) = failing_input(&result) else { return Ok(result) };
+            if !current.iter().any(|val| IntegerInput::from_concrete_val(val).is_some()) {
+                return Ok(result);
+            }
+            let excluded: Vec<_> = blocked.iter().chain(&rejected).cloned().collect();
+            let code = record_nondet_definition(&excluded, Some((&current, shrink)));
+            let candidate = self.check(&format!("{label}-minimize-{step}"), code)?;
+            let candidate_input = match candidate.status {
+                VerificationStatus::Failure => failing_input(&candidate),
+                VerificationStatus::Success => None,
+            };
+            if let Some(input) = candidate_input {
+                if input.len() < current.len() && !is_shrunk(&current, &input, shrink) {
+                    rejected.push(input);
+                    continue;
+                }
+                result = candidate;
+                shrink = Shrink::Halve;
+                rejected.clear();
+            } else if shrink == Shrink::Halve {
+                shrink = Shrink::Decrease;
+            } else {
+                return Ok(result);
+            }
+        }
+        println!(
+            "WARNING: Stopped minimizing the counterexample of `{}` after {MAX_MINIMIZATION_STEPS} \
+            steps. The reported input may not be minimal.",
+            self.harness.pretty_name
+        );
+        Ok(result)
+    }
+
+    /// Check the harness with `record_nondet` as the definition of `__KANI_record_nondet`.
+    fn check(&self, label: &str, record_nondet: String) -> Result<VerificationResult> {
+        let c_file = alter_extension(self.specialized_obj, &format!("{label}.c"));
+        let model = alter_extension(self.specialized_obj, &format!("{label}.out"));
+        self.session.record_temporary_files(&[&c_file, &model]);
+        fs::write(&c_file, record_nondet)?;
+        self.session.instrument_model(
+            self.goto_file,
+            &model,
+            self.project,
+            self.harness,
+            &[c_file],
+        )?;
+        self.session.with_timer(|| self.session.run_cbmc(&model, self.harness), "run_cmbc")
+    }
+}
+
+/// The values of `kani::any()` that lead to the failure reported in `result`.
+fn failing_input(result: &VerificationResult) -> Option<Vec<ConcreteVal>> {
+    result.results.as_ref().and_then(|results| extract_harness_values(results))
+}
+
+/// How much smaller the next counterexample must be during minimization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Shrink {
+    /// The magnitude of at least one integer input must be at most half of its current one.
+    Halve,
+    /// The magnitude of at least one integer input must be smaller than its current one.
+    Decrease,
+}
+
+/// An integer produced by `kani::any()`, as a number of bytes and a magnitude.
+#[derive(Debug, PartialEq, Eq)]
+struct IntegerInput {
+    size: usize,
+    magnitude: u128,
+    /// Whether the value was negative, which tells us it has a signed type.
+    negative: bool,
+}
+
+impl IntegerInput {
+    /// Interpret a concrete value as an integer, if CBMC displayed it as one.
+    fn from_concrete_val(val: &ConcreteVal) -> Option<IntegerInput> {
+        let size = val.byte_arr.len();
+        // CBMC displays unsigned and long values with a C suffix, e.g. `102ul`.
+        let digits = val.interp_val.trim_end_matches(['u', 'l']);
+        let is_integer = digits.parse::<i128>().is_ok() || digits.parse::<u128>().is_ok();
+        if !is_integer || size == 0 || size > 16 {
+            return None;
+        }
+        let bits =
+            val.byte_arr.iter().rev().fold(0u128, |acc, byte| (acc << 8) | u128::from(*byte));
+        let negative = val.interp_val.starts_with('-');
+        let magnitude = if negative { modulus(size).wrapping_sub(bits) } else { bits };
+        Some(IntegerInput { size, magnitude, negative })
+    }
+
+    /// A C condition on the unsigned `value` read from memory that holds iff the magnitude of
+    /// the integer it represents is at most `bound`.
+    fn magnitude_at_most(&self, bound: u128) -> String {
+        if !self.negative {
+            format!("value <= {}", c_literal(bound))
+        } else if bound == 0 {
+            "value == 0".to_string()
+        } else {
+            // Negative values are stored in two's complement.
+            let min = modulus(self.size).wrapping_sub(bound);
+            format!("(value <= {} || value >= {})", c_literal(bound), c_literal(min))
+        }
+    }
+
+    /// A C condition that holds iff the magnitude of `value` satisfies `shrink`.
+    fn shrunk(&self, shrink: Shrink) -> String {
+        match shrink {
+            Shrink::Halve => self.magnitude_at_most(self.magnitude / 2),
+            Shrink::Decrease if self.magnitude == 0 => "0".to_string(),
+            Shrink::Decrease => self.magnitude_at_most(self.magnitude - 1),
+        }
+    }
+}
+
+/// Whether one of the integer inputs of `candidate` shrinks the one of `current` at the same
+/// position as requested. The generated code only checks this when the candidate reaches the last
+/// input of `current`.
+fn is_shrunk(current: &[ConcreteVal], candidate: &[ConcreteVal], shrink: Shrink) -> bool {
+    current.iter().zip(candidate).any(|(current, candidate)| {
+        let (Some(current), Some(candidate)) =
+            (IntegerInput::from_concrete_val(current), IntegerInput::from_concrete_val(candidate))
+        else {
+            return false;
+        };
+        current.size == candidate.size
+            && match shrink {
+                Shrink::Halve => candidate.magnitude <= current.magnitude / 2,
+                Shrink::Decrease => candidate.magnitude < current.magnitude,
+            }
+    })
+}
+
+/// 2^(8 * size), wrapping to 0 for 16 bytes.
+fn modulus(size: usize) -> u128 {
+    if size >= 16 { 0 } else { 1u128 << (8 * size) }
+}
+
+/// C has no 128-bit integer literals, so wide constants are built from two halves.
+fn c_literal(value: u128) -> String {
+    if value <= u64::MAX as u128 {
+        format!("{value}ull")
+    } else {
+        format!("(((unsigned __int128){}ull << 64) | {}ull)", value >> 64, value as u64)
+    }
+}
+
+/// Generate a C definition of `__KANI_record_nondet` that:
+///  - blocks every execution where the sequence of `kani::any()` values matches one of the
+///    `blocked` sequences;
+///  - if `bound` is given, only allows executions whose integer inputs are not larger than the
+///    ones of the given sequence and where at least one of them shrinks as requested.
+///
+/// Executions are constrained as soon as the last value of a sequence is recorded, so the
+/// assertion that failed for that input is never reached again. Executions that fail before
+/// recording as many values as the `bound` sequence aren't required to shrink, so the caller must
+/// check them with `is_shrunk`. The generated code is loop free
+/// to avoid interfering with the unwinding bound of the harness.
+fn record_nondet_definition(
+    blocked: &[Vec<ConcreteVal>],
+    bound: Option<(&[ConcreteVal], Shrink)>,
+) -> String {
+    let mut code = String::from("#include <stddef.h>\n#include <stdint.h>\n\n");
+    code.push_str("static size_t __KANI_nondet_count = 0;\n");
+    for idx in 0..blocked.len() {
+        writeln!(code, "static _Bool __KANI_blocked_{idx}_matches = 1;").unwrap();
+    }
+    if bound.is_some() {
+        code.push_str("static _Bool __KANI_shrunk = 0;\n");
+    }
+    code.push_str(
+        "\nvoid __KANI_record_nondet(const uint8_t *bytes, size_t size)\n{\n    \
+        size_t index = __KANI_nondet_count++;\n",
+    );
+    let bound_len = bound.map_or(0, |(vals, _)| vals.len());
+    let max_len = blocked.iter().map(Vec::len).chain([bound_len]).max().unwrap_or(0);
+    for position in 0..max_len {
+        writeln!(code, "    if (index == {position}) {{").unwrap();
+        for (idx, vals) in blocked.iter().enumerate() {
+            if let Some(val) = vals.get(position) {
+                let matches = format!("__KANI_blocked_{idx}_matches");
+                let bytes_eq = val
+                    .byte_arr
+                    .iter()
+                    .enumerate()
+                    .map(|(i, byte)| format!(" && bytes[{i}] == {byte}"))
+                    .collect::<String>();
+                writeln!(
+                    code,
+                    "        {matches} = {matches} && size == {}{bytes_eq};",
+                    val.byte_arr.len()
+                )
+                .unwrap();
+                if position + 1 == vals.len() {
+                    writeln!(code, "        __CPROVER_assume(!{matches});").unwrap();
+                }
+            }
+        }
+        if let Some((vals, shrink)) = bound && let Some(val) = vals.get(position) {
+            if let Some(input) = IntegerInput::from_concrete_val(val) {
+                let value = (0..input.size)
+                    .map(|i| format!("(unsigned __int128)bytes[{i}] << {}", 8 * i))
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                writeln!(code, "        __CPROVER_assume(size == {});", input.size).unwrap();
+                writeln!(code, "        unsigned __int128 value = {value};").unwrap();
+                writeln!(
+                    code,
+                    "        __CPROVER_assume({});",
+                    input.magnitude_at_most(input.magnitude)
+                )
+                .unwrap();
+                writeln!(code, "        __KANI_shrunk = __KANI_shrunk || {};", input.shrunk(shrink))
+                    .unwrap();
+            }
+            if position + 1 == vals.len() {
+                code.push_str("        __CPROVER_assume(__KANI_shrunk);\n");
+            }
+        }
+        code.push_str("    }\n");
+    }
+    code.push_str("}\n");
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn val(bytes: &[u8], interp: &str) -> ConcreteVal {
+        ConcreteVal { byte_arr: bytes.to_vec(), interp_val: interp.to_string() }
+    }
+
+    #[test]
+    fn check_blocking_definition() {
+        let blocked = [vec![val(&[1, 0], "1"), val(&[7], "7")], vec![val(&[3, 4], "1027")]];
+        let code = record_nondet_definition(&blocked, None);
+        assert!(code.contains("static _Bool __KANI_blocked_0_matches = 1;"));
+        assert!(code.contains("static _Bool __KANI_blocked_1_matches = 1;"));
+        assert!(!code.contains("__KANI_shrunk"));
+        assert!(code.contains(
+            "__KANI_blocked_0_matches = __KANI_blocked_0_matches && size == 2 \
+            && bytes[0] == 1 && bytes[1] == 0;"
+        ));
+        assert!(code.contains(
+            "__KANI_blocked_0_matches = __KANI_blocked_0_matches && size == 1 && bytes[0] == 7;"
+        ));
+        // The second assignment has a single value, so it is blocked at the first position.
+        let first = code.find("if (index == 0)").unwrap();
+        let second = code.find("if (index == 1)").unwrap();
+        let assume_1 = code.find("__CPROVER_assume(!__KANI_blocked_1_matches);").unwrap();
+        let assume_0 = code.find("__CPROVER_assume(!__KANI_blocked_0_matches);").unwrap();
+        assert!(first < assume_1 && assume_1 < second);
+        assert!(second < assume_0);
+        assert!(!code.contains("if (index == 2)"));
+    }
+
+    #[test]
+    fn check_unconstrained_definition() {
+        let code = record_nondet_definition(&[], None);
+        assert_eq!(
+            code,
+            "#include <stddef.h>\n#include <stdint.h>\n\n\
+            static size_t __KANI_nondet_count = 0;\n\n\
+            void __KANI_record_nondet(const uint8_t *bytes, size_t size)\n{\n    \
+            size_t index = __KANI_nondet_count++;\n}\n"
+        );
+    }
+
+    #[test]
+    fn check_integer_input_parsing() {
+        let long = IntegerInput::from_concrete_val(&val(&[5, 0, 0, 0, 0, 0, 0, 0], "5ul")).unwrap();
+        assert_eq!(long, IntegerInput { size: 8, magnitude: 5, negative: false });
+        let negative_long =
+            IntegerInput::from_concrete_val(&val(&[0xfb, 0xff, 0xff, 0xff], "-5l")).unwrap();
+        assert_eq!(negative_long, IntegerInput { size: 4, magnitude: 5, negative: true });
+        assert_eq!(IntegerInput::from_concrete_val(&val(&[], "0")), None);
+        assert_eq!(IntegerInput::from_concrete_val(&val(&[0; 17], "0")), None);
+        assert_eq!(IntegerInput::from_concrete_val(&val(&[65, 0, 0, 0], "'A'")), None);
+    }
+
+    #[test]
+    fn check_integer_input() {
+        let positive = IntegerInput::from_concrete_val(&val(&[0x10, 0x01], "272")).unwrap();
+        assert_eq!(positive, IntegerInput { size: 2, magnitude: 272, negative: false });
+        assert_eq!(positive.shrunk(Shrink::Halve), "value <= 136ull");
+        assert_eq!(positive.shrunk(Shrink::Decrease), "value <= 271ull");
+
+        let suffixed = IntegerInput::from_concrete_val(&val(&[102, 0, 0, 0], "102u")).unwrap();
+        assert_eq!(suffixed, IntegerInput { size: 4, magnitude: 102, negative: false });
+
+        let negative = IntegerInput::from_concrete_val(&val(&[0xfe], "-2")).unwrap();
+        assert_eq!(negative, IntegerInput { size: 1, magnitude: 2, negative: true });
+        assert_eq!(negative.magnitude_at_most(2), "(value <= 2ull || value >= 254ull)");
+        assert_eq!(negative.shrunk(Shrink::Decrease), "(value <= 1ull || value >= 255ull)");
+        assert_eq!(negative.magnitude_at_most(0), "value == 0");
+
+        let wide = IntegerInput::from_concrete_val(&val(&[0xff; 16], "-1")).unwrap();
+        assert_eq!(wide.magnitude, 1);
+        assert_eq!(
+            wide.magnitude_at_most(1),
+            "(value <= 1ull || value >= (((unsigned __int128)18446744073709551615ull << 64) \
+            | 18446744073709551615ull))"
+        );
+
+        assert_eq!(IntegerInput::from_concrete_val(&val(&[1], "TRUE")), None);
+        assert_eq!(IntegerInput::from_concrete_val(&val(&[0, 0, 0xc0, 0x3f], "1.5")), None);
+    }
+
+    #[test]
+    fn check_minimizing_definition() {
+        let current = [val(&[1], "TRUE"), val(&[100, 0, 0, 0], "100")];
+        let code = record_nondet_definition(&[], Some((&current, Shrink::Halve)));
+        assert!(code.contains("static _Bool __KANI_shrunk = 0;"));
+        // Non-integer inputs are left unconstrained.
+        let first = code.find("if (index == 0) {\n    }").unwrap();
+        let second = code.find("if (index == 1)").unwrap();
+        assert!(first < second);
+        assert!(code.contains("__CPROVER_assume(size == 4);"));
+        assert!(code.contains(
+            "unsigned __int128 value = (unsigned __int128)bytes[0] << 0 | \
+            (unsigned __int128)bytes[1] << 8 | (unsigned __int128)bytes[2] << 16 | \
+            (unsigned __int128)bytes[3] << 24;"
+        ));
+        assert!(code.contains("__CPROVER_assume(value <= 100ull);"));
+        assert!(code.contains("__KANI_shrunk = __KANI_shrunk || value <= 50ull;"));
+        assert!(code.ends_with("        __CPROVER_assume(__KANI_shrunk);\n    }\n}\n"));
+    }
+
+    #[test]
+    fn check_shrunk_shorter_input() {
+        let current = [val(&[100, 0, 0, 0], "100"), val(&[9], "9")];
+        assert!(is_shrunk(&current, &[val(&[50, 0, 0, 0], "50")], Shrink::Halve));
+        assert!(!is_shrunk(&current, &[val(&[51, 0, 0, 0], "51")], Shrink::Halve));
+        assert!(is_shrunk(&current, &[val(&[99, 0, 0, 0], "99")], Shrink::Decrease));
+        assert!(!is_shrunk(&current, &[val(&[100, 0, 0, 0], "100")], Shrink::Decrease));
+        // Inputs of different types aren't comparable.
+        assert!(!is_shrunk(&current, &[val(&[1], "1")], Shrink::Decrease));
+    }
+}
@@ -0,0 +1,534 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Renders the messages of failed assertions and panics, and of `kani::log!` entries, with the
+//! values of their arguments.
+//!
+//! Our overrides of `assert!`, `panic!` and `unreachable!` (in `library/std`) use the
+//! stringified arguments as the description of the check, e.g., `"index {} out of range", i`.
+//! When the check fails, the value of each positional argument of a primitive type is assigned
+//! to a global variable `__kani_fmt_arg_<index>_<type>`, and the operands of `assert_eq!` and
+//! `assert_ne!` to `__kani_assert_operand_<index>_<type>` (see the `RecordValue` hook in
+//! `kani-compiler`). For a string or a slice, the variable (with the type `str` or `slice`) gets
+//! its length, and its first elements are assigned to `__kani_fmt_arg_<index>_<element>_<type>`.
+//! The overrides also assign `__kani_fmt_message`, to tell the stringified format strings apart
+//! from other descriptions. All these assignments have the location of the check they belong to.
+//! This module looks up those assignments in the trace of the failed check, and substitutes the
+//! values in the format string, e.g., `index 5 out of range`.
+//!
+//! `kani::log!` records its arguments the same way, followed by an output step whose ID is
+//! `[KANI_LOG] ` and the stringified arguments of the macro (see the `Log` hook).
+
+use crate::cbmc_output_parser::{SourceLocation, TraceData, TraceItem};
+use kani_metadata::checks::MAX_RECORDED_ELEMENTS;
+use std::collections::HashMap;
+
+const FMT_ARG_PREFIX: &str = "__kani_fmt_arg_";
+const ASSERT_OPERAND_PREFIX: &str = "__kani_assert_operand_";
+const FMT_MESSAGE_VAR: &str = "__kani_fmt_message";
+const LOG_OUTPUT_PREFIX: &str = "[KANI_LOG] ";
+
+/// A value recorded in the trace, with the name of its Rust type (e.g., `u32`).
+#[derive(Clone)]
+struct RecordedValue {
+    ty: String,
+    data: TraceData,
+}
+
+/// The recorded value of an argument. Strings and slices are recorded as their length (with the
+/// type `str` or `slice`) and their first elements, by index.
+#[derive(Default)]
+struct RecordedArg {
+    value: Option<RecordedValue>,
+    elements: HashMap<usize, RecordedValue>,
+}
+
+/// A message logged with `kani::log!`.
+pub struct LogEntry {
+    pub location: Option<SourceLocation>,
+    pub message: String,
+}
+
+/// Renders the message of a failed check with the values recorded for it in its trace, i.e.,
+/// the values recorded at the location of the check (the last step of the trace).
+///
+/// Returns `None` if there is nothing to render: the description wasn't marked by our overrides
+/// as a stringified format string with arguments, or the value of one of its arguments wasn't
+/// recorded (e.g., an argument of a type that isn't recorded, or a named argument), and no
+/// operands of a failed `assert_eq!` or `assert_ne!` were recorded. The values of the operands
+/// are appended to the message.
+pub fn render_failure_message(description: &str, trace: &[TraceItem]) -> Option<String> {
+    let check_trace: Vec<&TraceItem> =
+        match trace.last().and_then(|failure| failure.source_location.as_ref()) {
+            Some(location) => trace.iter().filter(|item| same_location(item, location)).collect(),
+            None => vec![],
+        };
+    let is_fmt_message = check_trace
+        .iter()
+        .any(|item| item.step_type == "assignment" && item.lhs.as_deref() == Some(FMT_MESSAGE_VAR));
+    let fmt_args = recorded_args(check_trace.iter().copied(), FMT_ARG_PREFIX);
+    let message = is_fmt_message.then(|| render_fmt_message(description, &fmt_args)).flatten();
+    let operands = recorded_args(check_trace.iter().copied(), ASSERT_OPERAND_PREFIX);
+    let operands = match (operands.get(&0), operands.get(&1)) {
+        (Some(left), Some(right)) => render_arg(left, "?").zip(render_arg(right, "?")),
+        _ => None,
+    };
+    match (message, operands) {
+        (message, Some((left, right))) => Some(format!(
+            "{} (left: `{left}`, right: `{right}`)",
+            message.as_deref().unwrap_or(description)
+        )),
+        (message, None) => message,
+    }
+}
+
+/// Collects the messages logged with `kani::log!` along the trace, in order, rendered with the
+/// values of their arguments. The messages with arguments whose values weren't recorded are
+/// reported as the stringified arguments of the macro.
+pub fn log_entries(trace: &[TraceItem]) -> Vec<LogEntry> {
+    let mut entries = vec![];
+    let mut start = 0;
+    for (i, item) in trace.iter().enumerate() {
+        if let Some(msg) = log_message(item) {
+            let fmt_args = recorded_args(trace[start..i].iter(), FMT_ARG_PREFIX);
+            let message = render_fmt_message(msg, &fmt_args)
+                .or_else(|| {
+                    parse_str_literal(msg).and_then(|(msg, rest)| rest.is_empty().then_some(msg))
+                })
+                .unwrap_or_else(|| msg.to_string());
+            entries.push(LogEntry { location: item.source_location.clone(), message });
+            start = i + 1;
+        }
+    }
+    entries
+}
+
+/// Collects the last value assigned to each variable `<prefix><index>_<type>` in the steps, and
+/// the last value of each element `<prefix><index>_<element>_<type>` of a string or slice.
+fn recorded_args<'a>(
+    steps: impl Iterator<Item = &'a TraceItem>,
+    prefix: &str,
+) -> HashMap<usize, RecordedArg> {
+    let mut args: HashMap<usize, RecordedArg> = HashMap::new();
+    for (index, element, value) in steps.filter_map(|item| recorded_value(item, prefix)) {
+        let arg = args.entry(index).or_default();
+        match element {
+            None => arg.value = Some(value),
+            Some(element) => {
+                arg.elements.insert(element, value);
+            }
+        }
+    }
+    args
+}
+
+/// Is the trace step at `location` (the same file, function, line and column)?
+fn same_location(item: &TraceItem, location: &SourceLocation) -> bool {
+    item.source_location.as_ref().map_or(false, |item_location| {
+        item_location.file == location.file
+            && item_location.function == location.function
+            && item_location.line == location.line
+            && item_location.column == location.column
+    })
+}
+
+/// Returns the index of the argument, the index of the element (for an element of a string or
+/// slice), and the value recorded by a trace step, if it assigns a variable
+/// `<prefix><index>_<type>` or `<prefix><index>_<element>_<type>`.
+fn recorded_value(item: &TraceItem, prefix: &str) -> Option<(usize, Option<usize>, RecordedValue)> {
+    if item.step_type != "assignment" {
+        return None;
+    }
+    let name = item.lhs.as_ref()?.strip_prefix(prefix)?;
+    let (index, ty) = name.split_once('_')?;
+    let (element, ty) = match ty.split_once('_') {
+        Some((element, ty)) => (Some(element.parse().ok()?), ty),
+        None => (None, ty),
+    };
+    let data = item.value.as_ref()?.data.clone()?;
+    Some((index.parse().ok()?, element, RecordedValue { ty: ty.to_string(), data }))
+}
+
+/// Returns the stringified arguments of `kani::log!`, if the trace step is a log entry.
+fn log_message(item: &TraceItem) -> Option<&str> {
+    if item.step_type != "output" {
+        return None;
+    }
+    item.output_id.as_ref()?.strip_prefix(LOG_OUTPUT_PREFIX)
+}
+
+/// Renders a recorded argument according to the format spec of its placeholder (e.g., `x` in
+/// `{:#x}`). Returns `None` if its value (or the value of one of its elements) wasn't recorded.
+/// The elements of strings and slices after the first [`MAX_RECORDED_ELEMENTS`] are rendered as
+/// `...`.
+fn render_arg(arg: &RecordedArg, spec: &str) -> Option<String> {
+    let value = arg.value.as_ref()?;
+    if value.ty != "str" && value.ty != "slice" {
+        return render_value(value, spec);
+    }
+    let TraceData::NonBool(len) = &value.data else { return None };
+    let len: usize = len.parse().ok()?;
+    let recorded = len.min(MAX_RECORDED_ELEMENTS);
+    let truncated = recorded < len;
+    if (0..recorded).any(|i| !arg.elements.contains_key(&i)) {
+        return None;
+    }
+    let elements = &arg.elements;
+    if value.ty == "str" {
+        let bytes: Option<Vec<u8>> = (0..recorded)
+            .map(|i| match &elements[&i].data {
+                TraceData::NonBool(data) => data.parse().ok(),
+                TraceData::Bool(_) => None,
+            })
+            .collect();
+        let s = String::from_utf8_lossy(&bytes?).into_owned();
+        let ellipsis = if truncated { "..." } else { "" };
+        return Some(if spec.ends_with('?') { format!("{s:?}{ellipsis}") } else { s + ellipsis });
+    }
+    let mut rendered =
+        (0..recorded).map(|i| render_value(&elements[&i], spec)).collect::<Option<Vec<_>>>()?;
+    if truncated {
+        rendered.push("...".to_string());
+    }
+    Some(format!("[{}]", rendered.join(", ")))
+}
+
+/// Renders a recorded value of a primitive type according to the format spec of its placeholder.
+/// Fill, alignment, width and precision are ignored.
+fn render_value(value: &RecordedValue, spec: &str) -> Option<String> {
+    let data = match &value.data {
+        TraceData::Bool(b) => return Some(b.to_string()),
+        TraceData::NonBool(data) => data,
+    };
+    if value.ty == "char" {
+        let c = char::from_u32(data.parse().ok()?)?;
+        return Some(if spec.ends_with('?') { format!("{c:?}") } else { c.to_string() });
+    }
+    let signed = value.ty.starts_with('i');
+    let bits: u32 = value.ty[1..].parse().unwrap_or(64);
+    // The bit pattern of the value, for the hexadecimal, octal and binary formats.
+    let raw = if signed {
+        let v: i128 = data.parse().ok()?;
+        if bits == 128 { v as u128 } else { (v as u128) & ((1 << bits) - 1) }
+    } else {
+        data.parse().ok()?
+    };
+    let alternate = spec.contains('#');
+    Some(match spec.chars().last() {
+        Some('x') if alternate => format!("{raw:#x}"),
+        Some('x') => format!("{raw:x}"),
+        Some('X') if alternate => format!("{raw:#X}"),
+        Some('X') => format!("{raw:X}"),
+        Some('o') if alternate => format!("{raw:#o}"),
+        Some('o') => format!("{raw:o}"),
+        Some('b') if alternate => format!("{raw:#b}"),
+        Some('b') => format!("{raw:b}"),
+        _ => data.clone(),
+    })
+}
+
+/// Renders a stringified format string with arguments, e.g., `"{} is {}", x, "odd"`, possibly
+/// after a prefix ending in `": "` (as in `unreachable!`). Returns `None` if the description
+/// doesn't have this shape, or if the value of one of the arguments it uses wasn't recorded.
+fn render_fmt_message(description: &str, fmt_args: &HashMap<usize, RecordedArg>) -> Option<String> {
+    let start = description.find('"')?;
+    let prefix = &description[..start];
+    if !(prefix.is_empty() || prefix.ends_with(": ")) {
+        return None;
+    }
+    let (fmt, rest) = parse_str_literal(&description[start..])?;
+    split_args(rest.strip_prefix(',')?)?;
+    let mut message = String::from(prefix);
+    let mut chars = fmt.chars();
+    let mut next_index = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                message.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                message.push('}');
+            }
+            '{' => {
+                let (placeholder, rest) = chars.as_str().split_once('}')?;
+                chars = rest.chars();
+                let (arg, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+                // Named arguments and captured variables are not recorded.
+                let index = if arg.is_empty() {
+                    next_index += 1;
+                    next_index - 1
+                } else {
+                    arg.parse::<usize>().ok()?
+                };
+                message.push_str(&render_arg(fmt_args.get(&index)?, spec)?);
+            }
+            c => message.push(c),
+        }
+    }
+    Some(message)
+}
+/// Parses the string literal at the start of `input`. Returns its value and the rest of the input.
+fn parse_str_literal(input: &str) -> Option<(String, &str)> {
+    let mut chars = input.strip_prefix('"')?.char_indices();
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &input[i + 2..])),
+            '\\' => value.push(match chars.next()?.1 {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                '0' => '\0',
+                c @ ('\\' | '"' | '\'') => c,
+                _ => return None,
+            }),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Splits the stringified arguments of a format string at the top-level commas. Returns `None` if
+/// there are no arguments or if the brackets are unbalanced.
+fn split_args(input: &str) -> Option<Vec<&str>> {
+    let mut args = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    let mut in_str = false;
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if in_str => {
+                chars.next();
+            }
+            '"' => in_str = !in_str,
+            _ if in_str => {}
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth == 0 => return None,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(input[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if in_str || depth != 0 {
+        return None;
+    }
+    let last = input[start..].trim();
+    if !last.is_empty() {
+        args.push(last);
+    }
+    if args.is_empty() || args.iter().any(|arg| arg.is_empty()) { None } else { Some(args) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::TraceValue;
+
+    fn location(line: usize) -> Option<SourceLocation> {
+        Some(SourceLocation {
+            column: Some("5".to_string()),
+            file: Some("main.rs".to_string()),
+            function: Some("check".to_string()),
+            line: Some(line.to_string()),
+        })
+    }
+
+    fn step(step_type: &str, line: usize) -> TraceItem {
+        TraceItem {
+            thread: 0,
+            step_type: step_type.to_string(),
+            hidden: false,
+            lhs: None,
+            source_location: location(line),
+            value: None,
+            output_id: None,
+        }
+    }
+
+    fn assignment(lhs: &str, data: TraceData, line: usize) -> TraceItem {
+        TraceItem {
+            lhs: Some(lhs.to_string()),
+            value: Some(TraceValue {
+                name: "integer".to_string(),
+                binary: None,
+                data: Some(data),
+                width: None,
+            }),
+            ..step("assignment", line)
+        }
+    }
+
+    fn log(msg: &str) -> TraceItem {
+        TraceItem { output_id: Some(format!("{LOG_OUTPUT_PREFIX}{msg}")), ..step("output", 1) }
+    }
+
+    fn int(lhs: &str, value: &str, line: usize) -> TraceItem {
+        assignment(lhs, TraceData::NonBool(value.to_string()), line)
+    }
+
+    /// The trace of a formatted message that fails at line 10, with the given recorded values.
+    fn fmt_trace(values: Vec<TraceItem>) -> Vec<TraceItem> {
+        let mut trace = vec![assignment(FMT_MESSAGE_VAR, TraceData::Bool(true), 10)];
+        trace.extend(values);
+        trace.push(step("failure", 10));
+        trace
+    }
+
+    /// The steps that record a string or slice argument (its length and its elements).
+    fn sequence(prefix: &str, ty: &str, elements: &[(&str, &str)]) -> Vec<TraceItem> {
+        let mut steps = vec![int(&format!("{prefix}{ty}"), &elements.len().to_string(), 10)];
+        for (i, (elem_ty, value)) in elements.iter().enumerate() {
+            steps.push(int(&format!("{prefix}{i}_{elem_ty}"), value, 10));
+        }
+        steps
+    }
+
+    #[test]
+    fn check_recorded_args() {
+        let trace = fmt_trace(vec![
+            int("__kani_fmt_arg_0_u32", "3", 10),
+            int("__kani_fmt_arg_0_u32", "5", 10),
+            int("__kani_fmt_arg_1_usize", "4", 10),
+        ]);
+        assert_eq!(
+            render_failure_message(r#""index {} out of range for length {}", i, v.len()"#, &trace)
+                .unwrap(),
+            "index 5 out of range for length 4"
+        );
+    }
+
+    #[test]
+    fn check_format_specs() {
+        let trace = fmt_trace(vec![
+            int("__kani_fmt_arg_0_i8", "-1", 10),
+            int("__kani_fmt_arg_1_char", "97", 10),
+            assignment("__kani_fmt_arg_2_bool", TraceData::Bool(true), 10),
+        ]);
+        assert_eq!(
+            render_failure_message(r#""{0:#x} {0} {1:?} {1} {2} {{}}", x, c, b"#, &trace).unwrap(),
+            "0xff -1 'a' a true {}"
+        );
+    }
+
+    #[test]
+    fn check_sequence_args() {
+        let mut values = sequence("__kani_fmt_arg_0_", "str", &[("u8", "104"), ("u8", "105")]);
+        values.extend(sequence("__kani_fmt_arg_1_", "slice", &[("u16", "1"), ("u16", "2")]));
+        values.extend(sequence("__kani_fmt_arg_2_", "slice", &[("char", "97")]));
+        values.extend(sequence("__kani_fmt_arg_3_", "str", &[]));
+        let trace = fmt_trace(values);
+        assert_eq!(
+            render_failure_message(r#""{0} {0:?} {1:?} {1:x?} {2:?} [{3}]", s, v, c, e"#, &trace)
+                .unwrap(),
+            r#"hi "hi" [1, 2] [1, 2] ['a'] []"#
+        );
+    }
+
+    #[test]
+    fn check_long_sequences() {
+        let bytes: Vec<(&str, &str)> = vec![("u8", "97"); MAX_RECORDED_ELEMENTS];
+        let mut values = sequence("__kani_fmt_arg_0_", "str", &bytes);
+        values[0] = int("__kani_fmt_arg_0_str", "40", 10);
+        let trace = fmt_trace(values);
+        let expected = format!("{}...", "a".repeat(MAX_RECORDED_ELEMENTS));
+        assert_eq!(render_failure_message(r#""{}", s"#, &trace).unwrap(), expected);
+        // A missing element can't be rendered.
+        let mut values = sequence("__kani_fmt_arg_0_", "slice", &[("u8", "1"), ("u8", "2")]);
+        values.remove(1);
+        assert_eq!(render_failure_message(r#""{:?}", v"#, &fmt_trace(values)), None);
+    }
+
+    #[test]
+    fn check_unrecorded_args() {
+        // Messages with any argument whose value wasn't recorded are reported as they are.
+        let trace = fmt_trace(vec![int("__kani_fmt_arg_0_u8", "1", 10)]);
+        for description in [
+            r#""{} {}", x, msg"#,
+            r#""{} {x}", y"#,
+            r#""{} {name}", y, name = n"#,
+            r#"internal error: entered unreachable code: "arg {} {}", a, f(a, b)"#,
+        ] {
+            assert_eq!(render_failure_message(description, &trace), None);
+        }
+    }
+
+    #[test]
+    fn check_unchanged_descriptions() {
+        for description in [
+            "assertion failed: x == 1",
+            r#""Fail with custom static message""#,
+            r#"assertion failed: s == "a""#,
+            r#"assertion failed: f("a", b)"#,
+            r#"concat! ("Panic: {} code: ", 10), msg"#,
+        ] {
+            assert_eq!(render_failure_message(description, &fmt_trace(vec![])), None);
+        }
+    }
+
+    #[test]
+    fn check_unmarked_descriptions() {
+        // A message that wasn't marked by our overrides (e.g., given to `kani::assert`) is
+        // reported as is, even if it looks like a format string with arguments.
+        let trace = vec![int("__kani_fmt_arg_0_u8", "1", 10), step("failure", 10)];
+        assert_eq!(render_failure_message(r#""{}", x"#, &trace), None);
+    }
+
+    #[test]
+    fn check_assert_operands() {
+        let trace = vec![
+            int("__kani_assert_operand_0_u8", "5", 10),
+            int("__kani_assert_operand_1_u8", "10", 10),
+            step("failure", 10),
+        ];
+        assert_eq!(
+            render_failure_message("assertion failed: x == 10", &trace).unwrap(),
+            "assertion failed: x == 10 (left: `5`, right: `10`)"
+        );
+        assert_eq!(render_failure_message("assertion failed: x == 10", &trace[1..]), None);
+        let mut trace = sequence("__kani_assert_operand_0_", "str", &[("u8", "97")]);
+        trace.extend(sequence("__kani_assert_operand_1_", "str", &[("u8", "98")]));
+        trace.push(step("failure", 10));
+        assert_eq!(
+            render_failure_message("assertion failed: s == \"b\"", &trace).unwrap(),
+            "assertion failed: s == \"b\" (left: `\"a\"`, right: `\"b\"`)"
+        );
+    }
+
+    #[test]
+    fn check_other_checks_values() {
+        // The values recorded for the checks at other locations don't belong to the failed check.
+        let mut trace = fmt_trace(vec![]);
+        trace.splice(
+            0..0,
+            [
+                int("__kani_fmt_arg_0_u8", "1", 5),
+                int("__kani_assert_operand_0_u8", "2", 5),
+                int("__kani_assert_operand_1_u8", "3", 5),
+            ],
+        );
+        assert_eq!(render_failure_message(r#""{}", s"#, &trace), None);
+        let last = trace.len() - 1;
+        trace[last] = step("failure", 20);
+        assert_eq!(render_failure_message("assertion failed: a == b", &trace), None);
+    }
+
+    #[test]
+    fn check_log_entries() {
+        let trace = vec![
+            int("__kani_fmt_arg_0_u8", "1", 1),
+            int("__kani_fmt_arg_1_u8", "2", 1),
+            log(r#""x = {}, y = {}", x, y"#),
+            log(r#""start""#),
+            int("__kani_fmt_arg_0_u8", "3", 1),
+            log(r#""x = {}, name = {}", x, name"#),
+        ];
+        let messages: Vec<_> = log_entries(&trace).into_iter().map(|entry| entry.message).collect();
+        assert_eq!(messages, ["x = 1, y = 2", "start", r#""x = {}, name = {}", x, name"#]);
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of `cargo kani fuzz-target <harness>`, which writes a
+//! [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz) target that runs a proof harness on the
+//! inputs generated by libFuzzer, and of `cargo kani fuzz-playback <harness> <input>`, which
+//! converts an input found by the fuzzer into a concrete playback unit test.
+//!
+//! With the `fuzzing` feature of the Kani library, `#[kani::proof]` defines an entry point named
+//! `kani_fuzz_<harness>` when the package is built for fuzzing (i.e., with `--cfg fuzzing`). The
+//! fuzz target declares and calls this entry point, so the harness doesn't need to be reachable
+//! from the root of the crate. It only needs to be compiled when fuzzing (e.g., with
+//! `#[cfg(any(kani, fuzzing))]` instead of `#[cfg(kani)]`), in the library of a package that
+//! depends on the Kani library when it's built for fuzzing.
+
+use crate::args::ConcretePlaybackMode;
+use crate::concrete_playback::concrete_vals_extractor::ConcreteVal;
+use crate::project;
+use crate::session::KaniSession;
+use anyhow::{bail, Context, Result};
+use cargo_metadata::{DependencyKind, MetadataCommand, Package};
+use kani_metadata::HarnessMetadata;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// The directory of the fuzzing crate, relative to the package root. This is where `cargo fuzz`
+/// expects it.
+const FUZZ_DIR: &str = "fuzz";
+
+/// Environment variable with the file where `kani::fuzz_run` writes the values it returned from
+/// `kani::any()`, one per line, with their bytes in decimal and separated by spaces.
+const CONCRETE_VALS_VAR: &str = "KANI_FUZZ_CONCRETE_VALS";
+
+pub(crate) fn cargokani_fuzz_target_main(mut session: KaniSession, harness: String) -> Result<()> {
+    let (package, harness) = find_harness(&mut session, harness)?;
+    let package_dir = package.manifest_path.parent().unwrap().as_std_path();
+
+    let fuzz_dir = package_dir.join(FUZZ_DIR);
+    let target_name = fuzz_target_name(&harness);
+    let target_file = fuzz_dir.join("fuzz_targets").join(format!("{target_name}.rs"));
+    fs::create_dir_all(target_file.parent().unwrap())?;
+    fs::write(&target_file, fuzz_target_source(&harness))?;
+
+    let manifest = fuzz_dir.join("Cargo.toml");
+    if manifest.exists() {
+        let contents = fs::read_to_string(&manifest)?;
+        if !contents.contains(&format!("name = \"{target_name}\"")) {
+            fs::write(&manifest, contents + &bin_section(&target_name))?;
+        }
+    } else {
+        let kani = kani_dependency(&package)?;
+        fs::write(&manifest, fuzz_manifest(&package.name, &kani) + &bin_section(&target_name))?;
+    }
+
+    println!("Wrote fuzz target `{target_name}` to {}", target_file.display());
+    if !session.args.quiet {
+        println!("Run it with `cargo fuzz run {target_name}` from {}", package_dir.display());
+        println!(
+            "Convert an input that makes it fail into a concrete playback unit test with \
+            `cargo kani --enable-unstable fuzz-playback {} <input>`",
+            harness.pretty_name
+        );
+    }
+    Ok(())
+}
+
+/// Runs the fuzz target of `harness` on `input` (e.g., a crash artifact of `cargo fuzz`), and
+/// prints or adds to the source code (with `--concrete-playback=inplace`) a concrete playback unit
+/// test that gives the harness the same values for `kani::any()`.
+pub(crate) fn cargokani_fuzz_playback_main(
+    mut session: KaniSession,
+    harness: String,
+    input: PathBuf,
+) -> Result<()> {
+    let input = input
+        .canonicalize()
+        .with_context(|| format!("Failed to find the fuzzer input `{}`", input.display()))?;
+    let (package, harness) = find_harness(&mut session, harness)?;
+    let package_dir = package.manifest_path.parent().unwrap().as_std_path();
+    let target_name = fuzz_target_name(&harness);
+    if !package_dir.join(FUZZ_DIR).join("fuzz_targets").join(format!("{target_name}.rs")).exists() {
+        bail!(
+            "There is no fuzz target for `{}`. Generate it with \
+            `cargo kani --enable-unstable fuzz-target {}`.",
+            harness.pretty_name,
+            harness.pretty_name
+        );
+    }
+
+    let vals_file = package_dir.join(FUZZ_DIR).join(format!("{target_name}.concrete_vals"));
+    let log_file = vals_file.with_extension("log");
+    session.record_temporary_files(&[&vals_file, &log_file]);
+    let _ = fs::remove_file(&vals_file);
+    let mut cmd = Command::new("cargo");
+    cmd.args(["fuzz", "run", &target_name])
+        .arg(&input)
+        .env(CONCRETE_VALS_VAR, &vals_file)
+        .current_dir(package_dir);
+    // The input is expected to make the harness fail, so we only check that it ran.
+    session.run_redirect(cmd, &log_file)?;
+    let contents = fs::read_to_string(&vals_file).with_context(|| {
+        format!(
+            "The fuzz target `{target_name}` didn't run `{}` on `{}`",
+            harness.pretty_name,
+            input.display()
+        )
+    })?;
+    let concrete_vals = parse_concrete_vals(&contents)?;
+    let playback_mode = session.args.concrete_playback.unwrap_or(ConcretePlaybackMode::Print);
+    session.add_concrete_playback(&harness, playback_mode, &concrete_vals)
+}
+
+/// Finds the package in the current directory and the harness to fuzz in it, after checking that
+/// the generated fuzz target can call the harness.
+fn find_harness(session: &mut KaniSession, harness: String) -> Result<(Package, HarnessMetadata)> {
+    let metadata =
+        MetadataCommand::new().no_deps().exec().context("Failed to get cargo metadata.")?;
+    let package = metadata
+        .root_package()
+        .context("`cargo kani fuzz-target` must be run from a package directory")?
+        .clone();
+    if !package.targets.iter().any(|target| target.kind.iter().any(|kind| kind.ends_with("lib"))) {
+        bail!(
+            "`{}` doesn't have a library. The fuzz target links to the library of the package \
+            to run its harnesses.",
+            package.name
+        );
+    }
+    kani_dependency(&package)?;
+
+    // Build the project to find the harness, like `cargo kani --harness <harness>` does.
+    session.args.harness = Some(harness);
+    session.args.only_codegen = true;
+    let project = project::cargo_project(session)?;
+    let all_harnesses = project.get_all_harnesses();
+    let harness = session.determine_targets(&all_harnesses)?.remove(0);
+    check_unique_entry_point(&harness, &all_harnesses)?;
+    Ok((package, harness))
+}
+
+/// The entry points for fuzzing are named after the harnesses, without their module, so they
+/// clash if another harness of the crate has the same name.
+fn check_unique_entry_point(
+    harness: &HarnessMetadata,
+    all_harnesses: &[&HarnessMetadata],
+) -> Result<()> {
+    let name = harness.get_harness_name_unqualified();
+    let clash = all_harnesses.iter().find(|other| {
+        other.crate_name == harness.crate_name
+            && other.pretty_name != harness.pretty_name
+            && other.get_harness_name_unqualified() == name
+    });
+    if let Some(other) = clash {
+        bail!(
+            "`{}` can't be fuzzed because `{}` has the same name. The harnesses of a crate must \
+            have distinct names to be fuzzed.",
+            harness.pretty_name,
+            other.pretty_name
+        );
+    }
+    Ok(())
+}
+
+/// The dependency of the fuzzing crate on the Kani library, which enables its `fuzzing` feature.
+/// It has the same source as the dependency of the package, which its harnesses need to be
+/// compiled for fuzzing.
+fn kani_dependency(package: &Package) -> Result<String> {
+    let Some(kani) = package
+        .dependencies
+        .iter()
+        .find(|dep| dep.name == "kani" && dep.kind == DependencyKind::Normal)
+    else {
+        bail!(
+            "`{}` doesn't depend on the Kani library, which its harnesses need to be compiled \
+            for fuzzing. Add it to the dependencies of the package for fuzzing, e.g.:\n\n\
+            [target.'cfg(fuzzing)'.dependencies]\n\
+            kani = {{ path = \"<path to Kani>/library/kani\" }}",
+            package.name
+        );
+    };
+    Ok(match &kani.path {
+        Some(path) => format!("kani = {{ path = {:?}, features = [\"fuzzing\"] }}", path.as_str()),
+        None => format!("kani = {{ version = \"{}\", features = [\"fuzzing\"] }}", kani.req),
+    })
+}
+
+fn fuzz_target_name(harness: &HarnessMetadata) -> String {
+    format!("kani_{}", harness.pretty_name.replace("::", "_"))
+}
+
+fn fuzz_target_source(harness: &HarnessMetadata) -> String {
+    format!(
+        "#![no_main]
+// Generated by `cargo kani fuzz-target`.
+use libfuzzer_sys::fuzz_target;
+// Link the package, whose harnesses define their entry points when it's built for fuzzing.
+use {crate_name} as _;
+
+extern \"Rust\" {{
+    fn kani_fuzz_{name}(data: &[u8]);
+}}
+
+fuzz_target!(|data: &[u8]| {{
+    // SAFETY: `#[kani::proof]` defines the entry point of `{pretty_name}` with this signature.
+    unsafe {{ kani_fuzz_{name}(data) }}
+}});
+",
+        crate_name = harness.crate_name,
+        name = harness.get_harness_name_unqualified(),
+        pretty_name = harness.pretty_name
+    )
+}
+
+fn fuzz_manifest(package_name: &str, kani_dependency: &str) -> String {
+    format!(
+        "[package]
+name = \"{package_name}-fuzz\"
+version = \"0.0.0\"
+publish = false
+edition = \"2021\"
+
+[package.metadata]
+cargo-fuzz = true
+
+[dependencies]
+libfuzzer-sys = \"0.4\"
+{kani_dependency}
+
+[dependencies.{package_name}]
+path = \"..\"
+
+# Prevent this from interfering with workspaces
+[workspace]
+members = [\".\"]
+"
+    )
+}
+
+fn bin_section(target_name: &str) -> String {
+    format!(
+        "
+[[bin]]
+name = \"{target_name}\"
+path = \"fuzz_targets/{target_name}.rs\"
+test = false
+doc = false
+"
+    )
+}
+
+/// Parses the values written by `kani::fuzz_run`. The fuzzer doesn't know their types, so they
+/// have no interpretation.
+fn parse_concrete_vals(contents: &str) -> Result<Vec<ConcreteVal>> {
+    contents
+        .lines()
+        .map(|line| {
+            let byte_arr = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<u8>, _>>()
+                .with_context(|| format!("Invalid value of the fuzzer input: `{line}`"))?;
+            Ok(ConcreteVal { byte_arr, interp_val: String::new() })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn harness(pretty_name: &str) -> HarnessMetadata {
+        HarnessMetadata {
+            pretty_name: pretty_name.to_string(),
+            mangled_name: pretty_name.to_string(),
+            crate_name: "my_crate".to_string(),
+            original_file: "src/lib.rs".to_string(),
+            original_start_line: 1,
+            original_end_line: 5,
+            unwind_value: None,
+            goto_file: None,
+        }
+    }
+
+    #[test]
+    fn check_fuzz_target_source() {
+        let harness = harness("proofs::check_parse");
+        assert_eq!(fuzz_target_name(&harness), "kani_proofs_check_parse");
+        let source = fuzz_target_source(&harness);
+        assert!(source.contains("use my_crate as _;"));
+        assert!(
+            source.contains("extern \"Rust\" {\n    fn kani_fuzz_check_parse(data: &[u8]);\n}")
+        );
+        assert!(source.contains("unsafe { kani_fuzz_check_parse(data) }"));
+    }
+
+    #[test]
+    fn check_fuzz_manifest() {
+        let kani = "kani = { path = \"/kani/library/kani\", features = [\"fuzzing\"] }";
+        let manifest = fuzz_manifest("my-crate", kani);
+        assert!(manifest.contains("name = \"my-crate-fuzz\""));
+        assert!(manifest.contains("[dependencies.my-crate]"));
+        assert!(manifest.contains(&format!("libfuzzer-sys = \"0.4\"\n{kani}\n")));
+        assert!(bin_section("kani_check").contains("path = \"fuzz_targets/kani_check.rs\""));
+    }
+
+    #[test]
+    fn check_unique_entry_points() {
+        let first = harness("a::check");
+        let second = harness("b::check");
+        let other = harness("b::check_other");
+        assert!(check_unique_entry_point(&first, &[&first, &other]).is_ok());
+        let err = check_unique_entry_point(&first, &[&first, &second, &other]).unwrap_err();
+        assert!(err.to_string().contains("`b::check` has the same name"));
+        let mut other_crate = harness("b::check");
+        other_crate.crate_name = "other_crate".to_string();
+        assert!(check_unique_entry_point(&first, &[&first, &other_crate]).is_ok());
+    }
+
+    #[test]
+    fn check_parse_concrete_vals() {
+        let vals = parse_concrete_vals("1 0\n\n255\n").unwrap();
+        let bytes: Vec<_> = vals.iter().map(|val| val.byte_arr.clone()).collect();
+        assert_eq!(bytes, vec![vec![1, 0], vec![], vec![255]]);
+        assert!(vals.iter().all(|val| val.interp_val.is_empty()));
+        assert!(parse_concrete_vals("1 256\n").is_err());
+    }
+}
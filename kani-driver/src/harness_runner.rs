@@ -8,6 +8,7 @@
 
 use crate::args::OutputFormat;
 use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::counterexamples::CounterexampleSearch;
 use crate::project::Project;
 use crate::session::KaniSession;
 use crate::util::specialized_harness_name;
@@ -62,9 +63,17 @@ pub(crate) fn check_all_harnesses<'a>(
                         &specialized_obj,
                         &self.project,
                         &harness,
+                        &[],
                     )?;
 
-                    let result = self.sess.check_harness(&specialized_obj, &report_dir, harness)?;
+                    let search = CounterexampleSearch {
+                        session: self.sess,
+                        project: &self.project,
+                        harness,
+                        goto_file,
+                        specialized_obj: &specialized_obj,
+                    };
+                    let result = self.sess.check_harness(&search, &report_dir)?;
                     Ok(HarnessResult { harness, result })
                 })
                 .collect::<Result<Vec<_>>>()
@@ -75,13 +84,15 @@ pub(crate) fn check_all_harnesses<'a>(
 }
 
 impl KaniSession {
-    /// Run the verification process for a single harness
+    /// Run the verification process for a single harness.
+    /// The model of the harness is `search.specialized_obj`.
     pub(crate) fn check_harness(
         &self,
-        binary: &Path,
+        search: &CounterexampleSearch<'_>,
         report_dir: &Path,
-        harness: &HarnessMetadata,
     ) -> Result<VerificationResult> {
+        let binary = search.specialized_obj;
+        let harness = search.harness;
         if !self.args.quiet {
             println!("Checking harness {}...", harness.pretty_name);
         }
@@ -91,7 +102,10 @@ pub(crate) fn check_harness(
             // Strictly speaking, we're faking success here. This is more "no error"
             Ok(VerificationResult::mock_success())
         } else {
-            let result = self.with_timer(|| self.run_cbmc(binary, harness), "run_cmbc")?;
+            let mut result = self.with_timer(|| self.run_cbmc(binary, harness), "run_cmbc")?;
+            if self.args.minimize_counterexample && result.status == VerificationStatus::Failure {
+                result = search.minimize("counterexample-1", &[], result)?;
+            }
 
             // When quiet, we don't want to print anything at all.
             // When output is old, we also don't have real results to print.
@@ -99,6 +113,11 @@ pub(crate) fn check_harness(
                 println!("{}", result.render(&self.args.output_format));
             }
 
+            self.gen_and_add_concrete_playback(harness, &result)?;
+            if self.args.counterexamples.is_some() && result.status == VerificationStatus::Failure {
+                search.enumerate(&result)?;
+            }
+
             Ok(result)
         }
     }
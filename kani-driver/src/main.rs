@@ -15,6 +15,7 @@
 use clap::Parser;
 use tracing::debug;
 
+mod aliasing;
 mod args;
 mod args_toml;
 mod assess;
@@ -27,6 +28,9 @@
 mod cbmc_output_parser;
 mod cbmc_property_renderer;
 mod concrete_playback;
+mod counterexamples;
+mod fmt_args;
+mod fuzz_target;
 mod harness_runner;
 mod metadata;
 mod project;
@@ -57,6 +61,12 @@ fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
         // Run cargo assess.
         return assess::cargokani_assess_main(session);
     }
+    if let Some(CargoKaniSubcommand::FuzzTarget { harness }) = args.command {
+        return fuzz_target::cargokani_fuzz_target_main(session, harness);
+    }
+    if let Some(CargoKaniSubcommand::FuzzPlayback { harness, input }) = args.command {
+        return fuzz_target::cargokani_fuzz_playback_main(session, harness, input);
+    }
 
     let project = project::cargo_project(&session)?;
     if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
@@ -19,6 +19,7 @@
 //! allows the rest of the driver to handle a function under verification the same way it handle
 //! other harnesses.
 
+use crate::call_cargo::glob;
 use crate::metadata::{from_json, merge_kani_metadata, mock_proof_harness};
 use crate::session::KaniSession;
 use crate::util::{crate_name, guess_rlib_name};
@@ -249,6 +250,12 @@ fn build(self) -> Result<Project> {
         self.session.record_temporary_files(&[&rlib_path]);
         self.session.record_temporary_files(&self.artifacts.values().collect::<Vec<_>>());
 
+        // Remove the models of other stub sets left by a previous run, so they are not mistaken
+        // for the ones of this build.
+        for stale in self.stub_set_artifacts()? {
+            std::fs::remove_file(stale)?;
+        }
+
         // Build and link the artifacts.
         debug!(krate=?self.crate_name, input=?self.input, ?rlib_path, "build compile");
         self.session.compile_single_rust_file(&self.input, &self.crate_name, &self.outdir)?;
@@ -260,6 +267,23 @@ fn build(self) -> Result<Project> {
             self.session.link_goto_binary(&[symtab_out.to_path_buf()], goto)?;
         }
 
+        // With stubbing, the compiler generates an extra model for each additional stub set.
+        let mut stub_set_metadata = vec![];
+        let mut stub_set_artifacts = vec![];
+        for meta_file in
+            glob(&self.outdir.join(format!("{}.stubs-*.{}", self.crate_name, &*Metadata)))?
+        {
+            let meta_file = meta_file.canonicalize()?;
+            let symtab_out = convert_type(&meta_file, Metadata, SymTabGoto);
+            let goto = convert_type(&meta_file, Metadata, Goto);
+            self.session.record_temporary_files(&[&goto]);
+            self.session.link_goto_binary(&[symtab_out], &goto)?;
+            stub_set_artifacts
+                .extend(BUILD_ARTIFACTS.iter().filter_map(|typ| cargo_artifact(&meta_file, *typ)));
+            stub_set_metadata.push(from_json(&meta_file)?);
+        }
+        self.session.record_temporary_files(&stub_set_artifacts.iter().collect::<Vec<_>>());
+
         // Create the project with the artifacts built by the compiler.
         let metadata_path = self.artifact(Metadata);
         let metadata = if metadata_path.exists() {
@@ -276,11 +300,12 @@ fn build(self) -> Result<Project> {
 
         Ok(Project {
             outdir: self.outdir,
-            metadata: vec![metadata],
+            metadata: [metadata].into_iter().chain(stub_set_metadata).collect(),
             artifacts: self
                 .artifacts
                 .into_values()
                 .filter(|artifact| artifact.path.exists())
+                .chain(stub_set_artifacts)
                 .collect(),
             merged_artifacts: false,
         })
@@ -289,6 +314,12 @@ fn build(self) -> Result<Project> {
     fn artifact(&self, typ: ArtifactType) -> &Path {
         &self.artifacts.get(&typ).unwrap().path
     }
+
+    /// Find all the artifacts of the extra models (one per additional stub set) in the output
+    /// directory.
+    fn stub_set_artifacts(&self) -> Result<Vec<PathBuf>> {
+        glob(&self.outdir.join(format!("{}.stubs-*.*", self.crate_name)))
+    }
 }
 
 /// Generate a `KaniMetadata` by extending the original metadata to contain the function under
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The descriptions of the checks that the compiler instruments the code with, and the names of
+//! the variables it records their details in, which the driver looks up in the verification
+//! results.
+
+/// The description of the check of the aliasing rules (`--aliasing-checks`).
+pub const ALIASING_VIOLATION_DESC: &str = "aliasing violation: use of an invalidated borrow";
+/// The variable assigned the ID of every instrumented aliasing event (a borrow or an access), at
+/// the location of the event.
+pub const ALIASING_EVENT_VAR: &str = "__kani_aliasing_event";
+/// The variable assigned the ID of the event that created the borrow used by a violation.
+pub const ALIASING_BORROW_VAR: &str = "__kani_aliasing_borrow";
+/// The variable assigned the ID of the event that invalidated the borrow used by a violation.
+pub const ALIASING_CONFLICT_VAR: &str = "__kani_aliasing_conflict";
+
+/// The descriptions of the vacuity checks, which cover the end of each harness, and the
+/// assumptions of user code (`--vacuity-checks`). The vacuity checks of the assertions of user
+/// code keep the description of their reachability check.
+pub const VACUITY_HARNESS_END_DESC: &str = "end of the harness is reachable";
+pub const VACUITY_ASSUMPTION_DESC: &str = "assumption is reachable";
+pub const VACUITY_ASSUMPTION_HOLDS_DESC: &str = "assumption is satisfiable";
+
+/// The number of elements of a string or slice argument of a failed assertion or panic message
+/// that the compiler records. The driver renders the rest as `...`.
+pub const MAX_RECORDED_ELEMENTS: usize = 32;
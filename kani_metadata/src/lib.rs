@@ -8,6 +8,7 @@
 pub use vtable::*;
 
 pub mod artifact;
+pub mod checks;
 mod harness;
 mod vtable;
 
@@ -29,6 +29,429 @@ extern struct Unit VoidUnit;
 // Check that the input is either a power of 2, or 0. Algorithm from Hackers Delight.
 bool __KANI_is_nonzero_power_of_two(size_t i) { return (i != 0) && (i & (i - 1)) == 0; }
 
+// Shadow initialization state used by `--uninit-checks`.
+// Instead of keeping a state for every byte, we keep the state of a single byte, which is chosen
+// nondeterministically the first time it's needed. Verification then covers every byte. With
+// `--uninit-checks`, the compiler inserts calls to the functions below: the bytes of a place
+// become uninitialized when its storage is (re)allocated or deinitialized, and initialized when
+// it's written. Every typed read asserts that the bytes it reads are initialized. The compiler
+// enables the checks at the start of the harnesses, so the allocation functions below only
+// track the state when they are enabled.
+static bool __KANI_uninit_enabled = 0;
+static const uint8_t *__KANI_uninit_byte;
+static bool __KANI_uninit_byte_chosen = 0;
+static bool __KANI_uninit_byte_init = 1;
+
+// When the tracked byte is in a value passed to a function, or returned from it, it's in transit
+// until the callee (or the caller) receives the value. `slot` is the index of the parameter (or 0
+// for the return value) and `offset` is the offset of the byte in the value.
+static bool __KANI_uninit_in_transit = 0;
+static size_t __KANI_uninit_transit_slot;
+static size_t __KANI_uninit_transit_offset;
+
+void __KANI_uninit_enable(void) { __KANI_uninit_enabled = 1; }
+
+// Whether the tracked byte is one of the `size` bytes at `ptr`.
+static bool __KANI_uninit_tracks(const uint8_t *ptr, size_t size)
+{
+    if (!__KANI_uninit_byte_chosen) {
+        const uint8_t *byte;
+        __KANI_uninit_byte = byte;
+        __KANI_uninit_byte_chosen = 1;
+    }
+    return !__KANI_uninit_in_transit &&
+           __CPROVER_POINTER_OBJECT(__KANI_uninit_byte) == __CPROVER_POINTER_OBJECT(ptr) &&
+           __CPROVER_POINTER_OFFSET(__KANI_uninit_byte) >= __CPROVER_POINTER_OFFSET(ptr) &&
+           (size_t)(__CPROVER_POINTER_OFFSET(__KANI_uninit_byte) - __CPROVER_POINTER_OFFSET(ptr)) < size;
+}
+
+void __KANI_uninit_mark_init(const uint8_t *ptr, size_t size)
+{
+    if (__KANI_uninit_tracks(ptr, size)) {
+        __KANI_uninit_byte_init = 1;
+    }
+}
+
+void __KANI_uninit_mark_uninit(const uint8_t *ptr, size_t size)
+{
+    if (__KANI_uninit_tracks(ptr, size)) {
+        __KANI_uninit_byte_init = 0;
+    }
+}
+
+bool __KANI_uninit_is_init(const uint8_t *ptr, size_t size)
+{
+    return !__KANI_uninit_tracks(ptr, size) || __KANI_uninit_byte_init;
+}
+
+// Copies the state of the `size` bytes at `src` to the bytes at `dst`. If the tracked byte is in
+// `src`, we may (nondeterministically) track the corresponding byte of `dst` from now on.
+// Otherwise, the state of the bytes of `dst` is unknown, and we consider them initialized.
+void __KANI_uninit_copy(const uint8_t *dst, const uint8_t *src, size_t size)
+{
+    if (__KANI_uninit_tracks(src, size)) {
+        bool follow;
+        if (follow) {
+            __KANI_uninit_byte =
+                dst + (__CPROVER_POINTER_OFFSET(__KANI_uninit_byte) - __CPROVER_POINTER_OFFSET(src));
+            return;
+        }
+    }
+    __KANI_uninit_mark_init(dst, size);
+}
+
+// Passes the state of the `size` bytes at `src` to the given slot. Like with a copy, we may
+// (nondeterministically) follow the tracked byte.
+void __KANI_uninit_pass(size_t slot, const uint8_t *src, size_t size)
+{
+    if (__KANI_uninit_tracks(src, size)) {
+        bool follow;
+        if (follow) {
+            __KANI_uninit_transit_offset =
+                __CPROVER_POINTER_OFFSET(__KANI_uninit_byte) - __CPROVER_POINTER_OFFSET(src);
+            __KANI_uninit_transit_slot = slot;
+            __KANI_uninit_in_transit = 1;
+        }
+    }
+}
+
+// Receives the state of the `size` bytes at `dst` from the given slot.
+void __KANI_uninit_receive(size_t slot, const uint8_t *dst, size_t size)
+{
+    if (__KANI_uninit_in_transit && __KANI_uninit_transit_slot == slot) {
+        __KANI_uninit_byte = dst + __KANI_uninit_transit_offset;
+        __KANI_uninit_in_transit = 0;
+    } else {
+        __KANI_uninit_mark_init(dst, size);
+    }
+}
+
+// Called before passing the arguments of a call. If the tracked byte is still in transit, the
+// function that it was passed to (e.g., an intrinsic) didn't receive it, so we stop tracking it.
+void __KANI_uninit_clear_transit(void)
+{
+    if (__KANI_uninit_in_transit) {
+        __KANI_uninit_byte = 0;
+        __KANI_uninit_in_transit = 0;
+    }
+}
+
+// Shadow borrow stack used by `--aliasing-checks`, which follows the model of Stacked Borrows.
+// Like for `--uninit-checks`, we only keep the borrow stack of a single byte, chosen
+// nondeterministically. Pointers are identified by a tag: the owner of the memory (e.g., a local
+// variable) uses the base tag, every retag creates a fresh tag, and pointers whose provenance we
+// don't track (e.g., pointers received from other functions) use a wildcard tag. The stack holds
+// the tags that may access the byte: an access through a tag removes the items above it that it
+// conflicts with, and an access through a tag that was removed is an aliasing violation.
+// Every borrow and access is an event with a fresh ID, which the compiler records at the location
+// of the event, so that a violation can report where the borrow was created and invalidated.
+// The loops over the stack are unrolled, so that they don't depend on the unwinding bound.
+#define __KANI_SB_UNIQUE 0
+#define __KANI_SB_SHARED_RW 1
+#define __KANI_SB_SHARED_RO 2
+#define __KANI_SB_BASE_TAG 0u
+#define __KANI_SB_WILDCARD_TAG 0xFFFFFFFFu
+#define __KANI_SB_DEPTH 8
+#define __KANI_SB_UNROLL(BODY)                                                      \
+    do {                                                                            \
+        size_t i;                                                                   \
+        i = 0; BODY; i = 1; BODY; i = 2; BODY; i = 3; BODY;                         \
+        i = 4; BODY; i = 5; BODY; i = 6; BODY; i = 7; BODY;                         \
+    } while (0)
+#if __KANI_SB_DEPTH != 8
+#error "__KANI_SB_UNROLL must visit every item of the borrow stack"
+#endif
+
+static const uint8_t *__KANI_sb_byte;
+static bool __KANI_sb_byte_chosen = 0;
+static uint32_t __KANI_sb_next_tag = 1;
+static uint32_t __KANI_sb_next_event = 1;
+
+// The items of the stack: their tag, their permission and the event of the retag that created
+// them. The bottom item is the owner of the memory.
+static size_t __KANI_sb_len = 1;
+static uint32_t __KANI_sb_tag[__KANI_SB_DEPTH] = {__KANI_SB_BASE_TAG};
+static uint8_t __KANI_sb_perm[__KANI_SB_DEPTH] = {__KANI_SB_UNIQUE};
+static uint32_t __KANI_sb_event[__KANI_SB_DEPTH];
+
+// The last items that were removed from the stack, with the event that removed them.
+static size_t __KANI_sb_removed_count = 0;
+static uint32_t __KANI_sb_removed_tag[__KANI_SB_DEPTH];
+static uint32_t __KANI_sb_removed_event[__KANI_SB_DEPTH];
+static uint32_t __KANI_sb_removed_by[__KANI_SB_DEPTH];
+
+// The result of the last operation, and the events of the two conflicting borrow sites if it was
+// a violation.
+static bool __KANI_sb_last_ok = 1;
+static uint32_t __KANI_sb_violation_borrow;
+static uint32_t __KANI_sb_violation_conflict;
+
+// Whether the model ran out of room: a retag found the stack full, after which the byte is no
+// longer tracked, or a violation used a removed item that was dropped from the log.
+static bool __KANI_sb_overflow = 0;
+
+// Whether the tracked byte is one of the `size` bytes at `ptr`.
+static bool __KANI_sb_tracks(const uint8_t *ptr, size_t size)
+{
+    if (!__KANI_sb_byte_chosen) {
+        const uint8_t *byte;
+        __KANI_sb_byte = byte;
+        __KANI_sb_byte_chosen = 1;
+    }
+    return __CPROVER_POINTER_OBJECT(__KANI_sb_byte) == __CPROVER_POINTER_OBJECT(ptr) &&
+           __CPROVER_POINTER_OFFSET(__KANI_sb_byte) >= __CPROVER_POINTER_OFFSET(ptr) &&
+           (size_t)(__CPROVER_POINTER_OFFSET(__KANI_sb_byte) - __CPROVER_POINTER_OFFSET(ptr)) < size;
+}
+
+// Uses `tag` to read or write the tracked byte in the given event.
+static void __KANI_sb_use(uint32_t tag, bool write, uint32_t event)
+{
+    if (tag == __KANI_SB_WILDCARD_TAG) {
+        // We don't know which item grants the access, so we don't remove any.
+        return;
+    }
+    // The topmost item that grants the access.
+    size_t granting = 0;
+    __KANI_SB_UNROLL(if (i < __KANI_sb_len && __KANI_sb_tag[i] == tag &&
+                         (!write || __KANI_sb_perm[i] != __KANI_SB_SHARED_RO)) granting = i + 1);
+    if (granting == 0) {
+        __KANI_sb_last_ok = 0;
+        __KANI_sb_violation_borrow = 0;
+        __KANI_sb_violation_conflict = 0;
+        __KANI_SB_UNROLL(if (i < __KANI_sb_removed_count && __KANI_sb_removed_tag[i] == tag) {
+            __KANI_sb_violation_borrow = __KANI_sb_removed_event[i];
+            __KANI_sb_violation_conflict = __KANI_sb_removed_by[i];
+        });
+        if (__KANI_sb_violation_borrow == 0 && __KANI_sb_removed_count > __KANI_SB_DEPTH) {
+            // The log wrapped around, so the item may have been dropped from it.
+            __KANI_sb_overflow = 1;
+        }
+        return;
+    }
+    // A write removes all the items above the granting one, and a read removes the unique ones.
+    size_t len = granting;
+    __KANI_SB_UNROLL(if (i >= granting && i < __KANI_sb_len) {
+        if (write || __KANI_sb_perm[i] == __KANI_SB_UNIQUE) {
+            size_t removed = __KANI_sb_removed_count % __KANI_SB_DEPTH;
+            __KANI_sb_removed_tag[removed] = __KANI_sb_tag[i];
+            __KANI_sb_removed_event[removed] = __KANI_sb_event[i];
+            __KANI_sb_removed_by[removed] = event;
+            __KANI_sb_removed_count++;
+        } else {
+            __KANI_sb_tag[len] = __KANI_sb_tag[i];
+            __KANI_sb_perm[len] = __KANI_sb_perm[i];
+            __KANI_sb_event[len] = __KANI_sb_event[i];
+            len++;
+        }
+    });
+    __KANI_sb_len = len;
+}
+
+// Retags the pointer to the `size` bytes at `ptr` whose tag is stored in `tag`: the pointer gets
+// a fresh tag with the given permission, which is derived from its previous tag. Returns the ID
+// of the event.
+uint32_t __KANI_sb_retag(uint32_t *tag, const uint8_t *ptr, size_t size, uint8_t perm)
+{
+    uint32_t event = __KANI_sb_next_event++;
+    __KANI_sb_last_ok = 1;
+    uint32_t parent = *tag;
+    *tag = __KANI_sb_next_tag++;
+    if (!__KANI_sb_tracks(ptr, size)) {
+        return event;
+    }
+    __KANI_sb_use(parent, perm != __KANI_SB_SHARED_RO, event);
+    if (!__KANI_sb_last_ok) {
+        return event;
+    }
+    if (__KANI_sb_len == __KANI_SB_DEPTH) {
+        // The stack is full, so we stop tracking the byte (see `__KANI_sb_stack_overflow`).
+        __KANI_sb_byte = 0;
+        __KANI_sb_overflow = 1;
+        return event;
+    }
+    __KANI_sb_tag[__KANI_sb_len] = *tag;
+    __KANI_sb_perm[__KANI_sb_len] = perm;
+    __KANI_sb_event[__KANI_sb_len] = event;
+    __KANI_sb_len++;
+    return event;
+}
+
+// Reads or writes the `size` bytes at `ptr` through a pointer with the given tag. Returns the ID
+// of the event.
+uint32_t __KANI_sb_access(uint32_t tag, const uint8_t *ptr, size_t size, bool write)
+{
+    uint32_t event = __KANI_sb_next_event++;
+    __KANI_sb_last_ok = 1;
+    if (__KANI_sb_tracks(ptr, size)) {
+        __KANI_sb_use(tag, write, event);
+    }
+    return event;
+}
+
+// Resets the stack of the `size` bytes at `ptr` when their storage is (re)allocated.
+void __KANI_sb_reset(const uint8_t *ptr, size_t size)
+{
+    __KANI_sb_last_ok = 1;
+    if (__KANI_sb_tracks(ptr, size)) {
+        __KANI_sb_len = 1;
+        __KANI_sb_removed_count = 0;
+    }
+}
+
+bool __KANI_sb_ok(void) { return __KANI_sb_last_ok; }
+
+uint32_t __KANI_sb_violation_borrow_event(void) { return __KANI_sb_violation_borrow; }
+
+uint32_t __KANI_sb_violation_conflict_event(void) { return __KANI_sb_violation_conflict; }
+
+// Whether a borrow stack or the log of removed items outgrew `__KANI_SB_DEPTH` items, so the later
+// uses of the memory are not checked, or a violation can't be reported with its borrow sites.
+bool __KANI_sb_stack_overflow(void) { return __KANI_sb_overflow; }
+
+// The width of the UTF-8 sequence that starts with `byte`, or 0 if no sequence starts with it
+// (i.e., it's a continuation byte, or it can't appear in UTF-8).
+static size_t __KANI_utf8_width(uint8_t byte)
+{
+    if (byte < 0x80) {
+        return 1;
+    } else if (byte >= 0xC2 && byte <= 0xDF) {
+        return 2;
+    } else if (byte >= 0xE0 && byte <= 0xEF) {
+        return 3;
+    } else if (byte >= 0xF0 && byte <= 0xF4) {
+        return 4;
+    }
+    return 0;
+}
+
+static bool __KANI_utf8_is_continuation(uint8_t byte) { return byte >= 0x80 && byte <= 0xBF; }
+
+// Whether the `len` bytes at `ptr` are valid UTF-8, which is a safety precondition of functions
+// like `str::from_utf8_unchecked`. Overlong encodings, surrogates and code points above
+// U+10FFFF are rejected, as in `str::from_utf8`.
+// Instead of looping over the bytes, which would depend on the unwinding bound, we check the
+// byte at a nondeterministic position: every sequence that starts there must be complete, and a
+// continuation byte must belong to a sequence that starts at most 3 bytes before. The bytes are
+// valid UTF-8 if and only if this holds at every position, so verification covers all of them.
+bool __KANI_is_utf8(const uint8_t *ptr, size_t len)
+{
+    size_t i;
+    if (i >= len) {
+        return 1;
+    }
+    uint8_t byte = ptr[i];
+    if (__KANI_utf8_is_continuation(byte)) {
+        // The nearest preceding byte that is not a continuation must start a sequence that is
+        // long enough to include this byte.
+        if (i >= 1 && !__KANI_utf8_is_continuation(ptr[i - 1])) {
+            return __KANI_utf8_width(ptr[i - 1]) > 1;
+        }
+        if (i >= 2 && !__KANI_utf8_is_continuation(ptr[i - 2])) {
+            return __KANI_utf8_width(ptr[i - 2]) > 2;
+        }
+        if (i >= 3 && !__KANI_utf8_is_continuation(ptr[i - 3])) {
+            return __KANI_utf8_width(ptr[i - 3]) > 3;
+        }
+        return 0;
+    }
+    size_t width = __KANI_utf8_width(byte);
+    if (width == 0 || len - i < width) {
+        return 0;
+    }
+    if (width == 1) {
+        return 1;
+    }
+    uint8_t second_min = 0x80;
+    uint8_t second_max = 0xBF;
+    if (byte == 0xE0) {
+        second_min = 0xA0;
+    } else if (byte == 0xED) {
+        second_max = 0x9F;
+    } else if (byte == 0xF0) {
+        second_min = 0x90;
+    } else if (byte == 0xF4) {
+        second_max = 0x8F;
+    }
+    if (ptr[i + 1] < second_min || ptr[i + 1] > second_max) {
+        return 0;
+    }
+    return (width < 3 || __KANI_utf8_is_continuation(ptr[i + 2])) &&
+           (width < 4 || __KANI_utf8_is_continuation(ptr[i + 3]));
+}
+
+// Nondeterministic allocation failure used by `--nondet-alloc-failure` and
+// `#[kani::nondet_alloc_failure]`. The compiler enables it at the start of the harnesses. When it's
+// restricted to some crates, the compiler also encloses the calls from these crates into the
+// standard library, which performs their allocations, in a scope.
+static bool __KANI_alloc_failure_enabled = 0;
+static size_t __KANI_alloc_failure_min_size = 0;
+static bool __KANI_alloc_failure_all_crates = 1;
+static size_t __KANI_alloc_failure_scope = 0;
+
+void __KANI_alloc_failure_enable(size_t min_size, bool all_crates)
+{
+    __KANI_alloc_failure_enabled = 1;
+    __KANI_alloc_failure_min_size = min_size;
+    __KANI_alloc_failure_all_crates = all_crates;
+}
+
+void __KANI_alloc_failure_enter(void) { __KANI_alloc_failure_scope++; }
+
+void __KANI_alloc_failure_exit(void) { __KANI_alloc_failure_scope--; }
+
+// Whether an allocation of `size` bytes fails.
+bool __KANI_alloc_fails(size_t size)
+{
+    bool fails;
+    return __KANI_alloc_failure_enabled && size >= __KANI_alloc_failure_min_size &&
+           (__KANI_alloc_failure_all_crates || __KANI_alloc_failure_scope > 0) && fails;
+}
+
+// Memory leak detection used by `--leak-check`. Like CBMC's `--memory-leak-check`, we track one
+// live allocation that is chosen nondeterministically, so a leak is detected if there is a trace
+// where the tracked allocation is never freed. We also record the allocation site, which is the
+// last call from user code into the standard library (the compiler numbers these calls), so the
+// leak can be reported at that call.
+static bool __KANI_leak_check_enabled = 0;
+static size_t __KANI_leak_site = 0;
+static void *__KANI_leak_ptr = 0;
+static size_t __KANI_leak_ptr_site = 0;
+
+void __KANI_leak_check_enable(void) { __KANI_leak_check_enabled = 1; }
+
+// Sets the current allocation site and returns the previous one, which must be restored with
+// `__KANI_leak_site_exit`.
+size_t __KANI_leak_site_enter(size_t site)
+{
+    size_t previous = __KANI_leak_site;
+    __KANI_leak_site = site;
+    return previous;
+}
+
+void __KANI_leak_site_exit(size_t previous) { __KANI_leak_site = previous; }
+
+void __KANI_leak_track(void *ptr)
+{
+    bool track;
+    if (__KANI_leak_check_enabled && ptr != 0 && track) {
+        __KANI_leak_ptr = ptr;
+        __KANI_leak_ptr_site = __KANI_leak_site;
+    }
+}
+
+void __KANI_leak_untrack(void *ptr)
+{
+    if (ptr == __KANI_leak_ptr) {
+        __KANI_leak_ptr = 0;
+    }
+}
+
+// Whether the tracked allocation, which was allocated at `site`, has not been freed.
+bool __KANI_leaked(size_t site) { return __KANI_leak_ptr != 0 && __KANI_leak_ptr_site == site; }
+
+// Makes the `len` bytes at `ptr` nondeterministic, for `kani::havoc_slice` and `kani::havoc_raw`.
+void __KANI_havoc(uint8_t *ptr, size_t len) { __CPROVER_havoc_slice(ptr, len); }
+
 // This is a C implementation of the __rust_alloc function.
 // https://stdrs.dev/nightly/x86_64-unknown-linux-gnu/alloc/alloc/fn.__rust_alloc.html
 // It has the following Rust signature:
@@ -45,7 +468,15 @@ uint8_t *__rust_alloc(size_t size, size_t align)
     // TODO: Ensure we are doing the right thing with align
     // https://github.com/model-checking/kani/issues/1168
     __KANI_assert(__KANI_is_nonzero_power_of_two(align), "Alignment is power of two");
-    return malloc(size);
+    if (__KANI_alloc_fails(size)) {
+        return 0;
+    }
+    uint8_t *result = malloc(size);
+    if (__KANI_uninit_enabled) {
+        __KANI_uninit_mark_uninit(result, size);
+    }
+    __KANI_leak_track(result);
+    return result;
 }
 
 // This is a C implementation of the __rust_alloc_zeroed function.
@@ -64,7 +495,12 @@ uint8_t *__rust_alloc_zeroed(size_t size, size_t align)
     // TODO: Ensure we are doing the right thing with align
     // https://github.com/model-checking/kani/issues/1168
     __KANI_assert(__KANI_is_nonzero_power_of_two(align), "Alignment is power of two");
-    return calloc(1, size);
+    if (__KANI_alloc_fails(size)) {
+        return 0;
+    }
+    uint8_t *result = calloc(1, size);
+    __KANI_leak_track(result);
+    return result;
 }
 
 // This is a C implementation of the __rust_dealloc function.
@@ -85,6 +521,7 @@ struct Unit __rust_dealloc(uint8_t *ptr, size_t size, size_t align)
 
     __KANI_assert(__CPROVER_OBJECT_SIZE(ptr) == size,
                   "rust_dealloc must be called on an object whose allocated size matches its layout");
+    __KANI_leak_untrack(ptr);
     free(ptr);
     return VoidUnit;
 }
@@ -109,12 +546,33 @@ uint8_t *__rust_realloc(uint8_t *ptr, size_t old_size, size_t align, size_t new_
     // https://github.com/model-checking/kani/issues/1168
     __KANI_assert(__KANI_is_nonzero_power_of_two(align), "Alignment is power of two");
 
+    // On failure, the original allocation is left untouched.
+    if (__KANI_alloc_fails(new_size)) {
+        return 0;
+    }
+
     uint8_t *result = malloc(new_size);
     if (result) {
         size_t bytes_to_copy = new_size < old_size ? new_size : old_size;
         memcpy(result, ptr, bytes_to_copy);
+        if (__KANI_uninit_enabled) {
+            __KANI_uninit_copy(result, ptr, bytes_to_copy);
+            if (new_size > old_size) {
+                __KANI_uninit_mark_uninit(result + old_size, new_size - old_size);
+            }
+        }
+        if (ptr == __KANI_leak_ptr) {
+            // The reallocation keeps the allocation site of the original allocation.
+            __KANI_leak_ptr = result;
+        }
         free(ptr);
     }
 
     return result;
 }
+
+// With `--record-nondet`, the compiler calls this function with the bytes of every value
+// produced by `kani::any()`. It is defined as a weak symbol that does nothing, so that the
+// driver can link a strong definition into the model of a harness. The driver uses this to
+// exclude previously found inputs when searching for more counterexamples.
+__attribute__((weak)) void __KANI_record_nondet(const uint8_t *bytes, size_t size) {}
@@ -0,0 +1,166 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Helper code for running proof harnesses as fuzz targets.
+//!
+//! With the `fuzzing` feature, `kani::any()` consumes bytes from the input provided by a fuzzer
+//! (e.g. libFuzzer or AFL) instead of the concrete playback values. `cargo kani fuzz-target`
+//! generates a fuzz target that calls [fuzz_run], and `cargo kani fuzz-playback` runs it on a
+//! crash artifact to convert it into a concrete playback unit test.
+
+use crate::rejection;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::panic;
+use std::path::Path;
+
+/// Environment variable with the file where [fuzz_run] writes the values returned by
+/// `kani::any()`, one per line, which `cargo kani fuzz-playback` turns into a unit test.
+const CONCRETE_VALS_VAR: &str = "KANI_FUZZ_CONCRETE_VALS";
+
+thread_local! {
+    /// The fuzzer input of the harness that is currently running on this thread, if any.
+    static FUZZ_INPUT: RefCell<Option<FuzzInput>> = RefCell::new(None);
+}
+
+struct FuzzInput {
+    data: Vec<u8>,
+    position: usize,
+    /// The values returned by `kani::any()` so far, in the concrete playback format.
+    concrete_vals: Vec<Vec<u8>>,
+}
+
+/// This function runs `proof_harness` with the values of `kani::any()` taken from `data`.
+///
+/// Once `data` is exhausted, `kani::any()` returns zeroed bytes. Inputs that violate a
+/// `kani::assume` are ignored. If the harness fails, a concrete playback unit test that
+/// reproduces the failure is printed before the panic is propagated, so that the fuzzer
+/// records the crash.
+///
+/// `harness_name` is the name of `proof_harness` in its module, which is used by the unit test.
+pub fn fuzz_run<F: Fn()>(harness_name: &str, data: &[u8], proof_harness: F) {
+    FUZZ_INPUT.with(|input| {
+        *input.borrow_mut() =
+            Some(FuzzInput { data: data.to_vec(), position: 0, concrete_vals: vec![] })
+    });
+    let result = rejection::run_harness(&proof_harness);
+    let input = FUZZ_INPUT.with(|input| input.borrow_mut().take()).unwrap();
+    if let Some(path) = std::env::var_os(CONCRETE_VALS_VAR) {
+        write_concrete_vals(Path::new(&path), &input.concrete_vals);
+    }
+    if let Err(payload) = result {
+        eprintln!(
+            "Concrete playback unit test for `{harness_name}`:\n```\n{}\n```",
+            format_unit_test(harness_name, &input.concrete_vals)
+        );
+        panic::resume_unwind(payload)
+    }
+}
+
+/// Whether a harness is currently running on a fuzzer input on this thread.
+pub(crate) fn is_running() -> bool {
+    FUZZ_INPUT.with(|input| input.borrow().is_some())
+}
+
+/// Fuzzing implementation of kani::any_raw_internal.
+///
+/// # Safety
+///
+/// The semantics of this function require that SIZE_T equals the size of type T.
+pub(crate) unsafe fn any_raw_internal<T, const SIZE_T: usize>() -> T {
+    let mut bytes_t = [0u8; SIZE_T];
+    FUZZ_INPUT.with(|input| {
+        let mut input = input.borrow_mut();
+        let input = input.as_mut().expect("kani::any() can only be used inside a proof harness");
+        let start = input.position.min(input.data.len());
+        let end = (start + SIZE_T).min(input.data.len());
+        bytes_t[..end - start].copy_from_slice(&input.data[start..end]);
+        input.position = start + SIZE_T;
+        input.concrete_vals.push(bytes_t.to_vec());
+    });
+    std::mem::transmute_copy::<[u8; SIZE_T], T>(&bytes_t)
+}
+
+/// Write the values returned by `kani::any()` to `path`, one per line, with their bytes in
+/// decimal and separated by spaces.
+fn write_concrete_vals(path: &Path, concrete_vals: &[Vec<u8>]) {
+    let lines: String = concrete_vals
+        .iter()
+        .map(|concrete_val| {
+            let bytes: Vec<String> = concrete_val.iter().map(u8::to_string).collect();
+            bytes.join(" ") + "\n"
+        })
+        .collect();
+    std::fs::write(path, lines).expect("Failed to write the values of the fuzzer input");
+}
+
+/// Generate a unit test in the same format as the concrete playback unit tests generated by
+/// Kani. The fuzzer doesn't know the types of the values, so they are only shown as bytes.
+fn format_unit_test(harness_name: &str, concrete_vals: &[Vec<u8>]) -> String {
+    let vecs_as_str = concrete_vals
+        .iter()
+        .map(|concrete_val| format!("        vec!{concrete_val:?}"))
+        .collect::<Vec<String>>()
+        .join(",\n");
+    let mut hasher = DefaultHasher::new();
+    harness_name.hash(&mut hasher);
+    vecs_as_str.hash(&mut hasher);
+    let hash = hasher.finish();
+    format!(
+        "#[test]
+fn kani_concrete_playback_{harness_name}_{hash}() {{
+    let concrete_vals: Vec<Vec<u8>> = vec![
+{vecs_as_str}
+    ];
+    kani::concrete_playback_run(concrete_vals, {harness_name});
+}}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn any_u16() -> u16 {
+        unsafe { any_raw_internal::<u16, 2>() }
+    }
+
+    #[test]
+    fn check_any_consumes_input() {
+        let values = RefCell::new(vec![]);
+        fuzz_run("consume", &[1, 0, 2, 1, 3], || {
+            for _ in 0..3 {
+                values.borrow_mut().push(any_u16());
+            }
+        });
+        // The last value only gets one byte of the input, and the padding is zeroed.
+        assert_eq!(values.into_inner(), vec![1, 0x0102, 3]);
+        assert!(!is_running());
+    }
+
+    #[test]
+    fn check_rejected_input() {
+        fuzz_run("reject", &[1, 0], || {
+            crate::assume(any_u16() % 2 == 0);
+            unreachable!("the input should have been rejected");
+        });
+    }
+
+    #[test]
+    fn check_concrete_vals_file() {
+        let path = std::env::temp_dir().join(format!("kani-fuzz-{}.vals", std::process::id()));
+        write_concrete_vals(&path, &[vec![1, 0], vec![], vec![255]]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 0\n\n255\n");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn check_unit_test() {
+        let unit_test = format_unit_test("check_parse", &[vec![1, 0], vec![7]]);
+        assert!(unit_test.starts_with("#[test]\nfn kani_concrete_playback_check_parse_"));
+        assert!(unit_test.contains("        vec![1, 0],\n        vec![7]\n"));
+        assert!(unit_test.contains("kani::concrete_playback_run(concrete_vals, check_parse);"));
+    }
+}
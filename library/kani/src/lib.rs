@@ -3,18 +3,42 @@
 
 // Used for rustc_diagnostic_item.
 #![feature(rustc_attrs)]
+// Used by the models of collections with allocators.
+#![feature(allocator_api)]
+// Used by the model of `File::read_buf`.
+#![feature(read_buf)]
 
 pub mod arbitrary;
 #[cfg(feature = "concrete_playback")]
 mod concrete_playback;
 pub mod futures;
+#[cfg(feature = "fuzzing")]
+mod fuzzing;
+pub mod models;
+#[cfg(feature = "random_testing")]
+mod random_testing;
+#[cfg(any(feature = "fuzzing", feature = "random_testing"))]
+mod rejection;
 pub mod slice;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "arbitrary")]
+mod unstructured;
 pub mod vec;
 
 pub use arbitrary::Arbitrary;
 #[cfg(feature = "concrete_playback")]
 pub use concrete_playback::concrete_playback_run;
 pub use futures::block_on;
+#[cfg(feature = "fuzzing")]
+pub use fuzzing::fuzz_run;
+pub use models::process::expect_exit_code;
+#[cfg(feature = "random_testing")]
+pub use random_testing::{random_testing_run, random_testing_run_should_panic};
+#[cfg(feature = "proptest")]
+pub use strategy::{any_via_strategy, any_via_strategy_sized};
+#[cfg(feature = "arbitrary")]
+pub use unstructured::{any_via_arbitrary, any_via_arbitrary_sized};
 
 /// Creates an assumption that will be valid after this statement run. Note that the assumption
 /// will only be applied for paths that follow the assumption. If the assumption doesn't hold, the
@@ -42,6 +66,10 @@
 #[inline(never)]
 #[rustc_diagnostic_item = "KaniAssume"]
 pub fn assume(_cond: bool) {
+    #[cfg(any(feature = "fuzzing", feature = "random_testing"))]
+    if !_cond {
+        rejection::reject();
+    }
     if cfg!(feature = "concrete_playback") {
         assert!(_cond, "kani::assume should always hold");
     }
@@ -59,7 +87,7 @@ pub fn assume(_cond: bool) {
 #[inline(never)]
 #[rustc_diagnostic_item = "KaniAssert"]
 pub const fn assert(_cond: bool, _msg: &'static str) {
-    if cfg!(feature = "concrete_playback") {
+    if cfg!(any(feature = "concrete_playback", feature = "fuzzing", feature = "random_testing")) {
         assert!(_cond, "{}", _msg);
     }
 }
@@ -122,6 +150,16 @@ pub fn any<T: Arbitrary>() -> T {
 /// Note that SIZE_T must be equal the size of type T in bytes.
 #[inline(never)]
 pub(crate) unsafe fn any_raw_internal<T, const SIZE_T: usize>() -> T {
+    #[cfg(feature = "fuzzing")]
+    if fuzzing::is_running() {
+        return fuzzing::any_raw_internal::<T, SIZE_T>();
+    }
+
+    #[cfg(feature = "random_testing")]
+    if random_testing::is_running() {
+        return random_testing::any_raw_internal::<T, SIZE_T>();
+    }
+
     #[cfg(feature = "concrete_playback")]
     return concrete_playback::any_raw_internal::<T, SIZE_T>();
 
@@ -138,6 +176,47 @@ fn any_raw_inner<T>() -> T {
     unimplemented!("Kani any_raw_inner");
 }
 
+/// Makes the value behind `place` nondeterministic, i.e., it can be any value of type `T` that
+/// satisfies the validity invariant of its scalars (e.g., `bool`, `char`, the tag of enums and
+/// non-null references). This is useful to model the effect of opaque code, e.g., a callback
+/// that may modify a struct arbitrarily, and it also works for types that don't implement
+/// [`Arbitrary`].
+///
+/// # Example:
+///
+/// ```rust
+/// let mut pair = (1u8, true);
+/// kani::havoc(&mut pair);
+/// // `pair` can be any `(u8, bool)` here.
+/// ```
+///
+/// Note: The pointers and references stored in the value are not guaranteed to be
+/// dereferenceable, so this should be used on values that don't own memory (e.g., not on a
+/// `Vec`). In concrete playback, this function leaves the value unchanged.
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniHavoc"]
+pub fn havoc<T>(_place: &mut T) {}
+
+/// Makes the elements of `slice` nondeterministic (see [`havoc`]).
+///
+/// Note: If the elements accept any bit pattern (e.g., integers), their bytes are havocked at
+/// once. Otherwise (e.g., `bool` or an enum), each element is havocked in a loop over the slice,
+/// so a slice with a symbolic length requires the loop to be unwound (e.g., with `--unwind`).
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniHavocSlice"]
+pub fn havoc_slice<T>(_slice: &mut [T]) {}
+
+/// Makes the `len` bytes at `ptr` nondeterministic.
+///
+/// # Safety
+///
+/// `ptr` must be valid for writes of `len` bytes. Any value stored in these bytes may be invalid
+/// afterwards, so the caller must ensure that they are only read at types that accept any bit
+/// pattern (e.g., integers).
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniHavocRaw"]
+pub unsafe fn havoc_raw(_ptr: *mut u8, _len: usize) {}
+
 /// Function used in tests for cases where the condition is not always true.
 #[inline(never)]
 #[rustc_diagnostic_item = "KaniExpectFail"]
@@ -160,6 +239,98 @@ pub const fn panic(message: &'static str) -> ! {
     panic!("{}", message)
 }
 
+/// Records the value of the positional argument `INDEX` of a failing assertion or panic message,
+/// or of a [`log!`] message.
+///
+/// During verification, values of primitive types (integers, `bool` and `char`), and strings and
+/// slices of primitive types, possibly behind references, are kept in the counterexample trace,
+/// from which the driver renders the message with the concrete values. Values of other types are
+/// not recorded. This function is used by
+/// [`log!`] and our standard library overrides.
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniRecordFmtArg"]
+#[doc(hidden)]
+pub fn record_fmt_arg<const INDEX: usize, T: ?Sized>(_value: &T) {}
+
+/// Records the value of the left (`INDEX == 0`) or right (`INDEX == 1`) operand of a failing
+/// `assert_eq!` or `assert_ne!`. See [`record_fmt_arg`] for the values that can be recorded.
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniRecordAssertOperand"]
+#[doc(hidden)]
+pub fn record_assert_operand<const INDEX: usize, T: ?Sized>(_value: &T) {}
+
+/// Marks the message of a failing assertion or panic as a stringified format string with
+/// arguments (recorded with [`record_fmt_arg`]), which the driver renders with their values.
+/// Other messages are reported as they are.
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniRecordFmtMessage"]
+#[doc(hidden)]
+pub fn record_fmt_message() {}
+
+/// Logs a message in the counterexample trace (see [`log!`]). The message is the stringified
+/// arguments of the macro, which the driver renders with the values recorded by
+/// [`record_fmt_arg`].
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniLog"]
+#[doc(hidden)]
+pub fn log_message(_msg: &'static str) {}
+
+/// Records the positional arguments of a message (the format string comes first) with
+/// [`record_fmt_arg`], so the driver can render the message with their values. Named arguments,
+/// which can only be followed by other named arguments, are not recorded.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __record_fmt_args {
+    (@record ($($index:tt)*)) => {};
+    (@record ($($index:tt)*) $name:ident = $($rest:tt)*) => {};
+    (@record ($($index:tt)*) $arg:expr $(, $($rest:tt)*)?) => {
+        $crate::record_fmt_arg::<{ $($index)* }, _>(&$arg);
+        $crate::__record_fmt_args!(@record ($($index)* + 1) $($($rest)*)?);
+    };
+    ($fmt:expr $(, $($arg:tt)*)?) => {
+        $crate::record_fmt_message();
+        $crate::__record_fmt_args!(@record (0) $($($arg)*)?);
+    };
+}
+
+/// A macro to log a message, with the same syntax as `println!`, in the counterexample trace.
+///
+/// Nothing is printed during verification (Kani compiles `print!` and `println!` away).
+/// Instead, when Kani reports a failed check or a satisfied cover property, it prints the
+/// messages logged along the trace that leads to it, in order. The messages are rendered with the
+/// values that their arguments have at the point of the call. Only the values of integers, `bool`
+/// and `char` arguments, and strings and slices of those (possibly behind references) are
+/// recorded. A message with other arguments is shown as the stringified arguments of the macro.
+///
+/// # Example:
+///
+/// ```rust
+/// let x: u8 = kani::any();
+/// let y = x.wrapping_mul(3);
+/// kani::log!("x = {}, y = {}", x, y);
+/// assert!(y >= x);
+/// ```
+///
+/// The failure of the assertion is reported with the log of the counterexample, e.g.:
+///
+/// ```text
+/// Failed Checks: assertion failed: y >= x
+///  File: "/home/user/src/main.rs", line 4, in check
+///  Log:
+///   [main.rs:3] x = 86, y = 2
+/// ```
+#[macro_export]
+macro_rules! log {
+    ($($arg:tt)+) => {{
+        $crate::__record_fmt_args!($($arg)+);
+        $crate::log_message(concat!(stringify!($($arg)+)));
+        // Check the format string and the arguments without formatting them.
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
 /// A macro to check if a condition is satisfiable at a specific location in the
 /// code.
 ///
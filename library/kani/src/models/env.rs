@@ -0,0 +1,226 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Models for the environment variables and the command-line arguments of [`std::env`].
+//!
+//! Like the file system of [`crate::models::fs`], the environment starts out unknown: the first
+//! time a variable is read, the models decide nondeterministically whether it is set, and if it
+//! is, its value (a string of at most [`MAX_VALUE_LEN`] arbitrary characters). The decision is
+//! recorded, so later reads are consistent with it. Similarly, the command-line arguments are
+//! decided the first time they are read, unless they are pre-seeded with [`seed_args`].
+//! Harnesses can also pre-seed variables with [`seed_var`] and [`seed_missing_var`].
+//!
+//! | Original | Model |
+//! | --- | --- |
+//! | `std::env::var` | [`var`] |
+//! | `std::env::var_os` | [`var_os`] |
+//! | `std::env::set_var` | [`set_var`] |
+//! | `std::env::remove_var` | [`remove_var`] |
+//! | `<std::env::Args as Iterator>::next` | [`args_next`] |
+//! | `<std::env::Args as Iterator>::size_hint` | [`args_size_hint`] |
+//! | `<std::env::Args as ExactSizeIterator>::len` | [`args_len`] |
+//! | `<std::env::Args as DoubleEndedIterator>::next_back` | [`args_next_back`] |
+//! | `<std::env::ArgsOs as Iterator>::next` | [`args_os_next`] |
+//! | `<std::env::ArgsOs as Iterator>::size_hint` | [`args_os_size_hint`] |
+//! | `<std::env::ArgsOs as ExactSizeIterator>::len` | [`args_os_len`] |
+//! | `<std::env::ArgsOs as DoubleEndedIterator>::next_back` | [`args_os_next_back`] |
+//!
+//! `std::env::args` and `std::env::args_os` themselves are not modeled: their iterators cannot
+//! be built outside of `std`, and under Kani they are empty. The models of the iterator methods
+//! read the arguments from the model instead.
+//!
+//! Assumptions:
+//! - Variables that are not seeded have values of at most [`MAX_VALUE_LEN`] characters, which
+//!   are valid Unicode (i.e., [`std::env::VarError::NotUnicode`] only happens for seeded values).
+//! - At most [`MAX_VARS`] variables are accessed (a failed assertion reports when the bound is
+//!   exceeded).
+//! - If they are not seeded, there are between 1 and [`MAX_ARGS`] arguments, of at most
+//!   [`MAX_ARG_LEN`] characters each, which are valid Unicode.
+//! - The arguments are iterated over once: all the `Args` and `ArgsOs` iterators share their
+//!   position in the arguments, so an iterator created after another one was advanced continues
+//!   where that one stopped. [`seed_args`] rewinds the position.
+
+use std::env::{Args, ArgsOs, VarError};
+use std::ffi::{OsStr, OsString};
+
+/// The maximum length (in characters) of the values of the variables that are not seeded.
+pub const MAX_VALUE_LEN: usize = 8;
+/// The maximum number of variables in the environment.
+pub const MAX_VARS: usize = 8;
+/// The maximum number of command-line arguments, if they are not seeded.
+pub const MAX_ARGS: usize = 4;
+/// The maximum length (in characters) of the command-line arguments, if they are not seeded.
+pub const MAX_ARG_LEN: usize = 8;
+
+/// A variable of the environment, with its value if it is set.
+struct Var {
+    key: OsString,
+    value: Option<OsString>,
+}
+
+const NO_VAR: Option<Var> = None;
+
+static mut VARS: [Option<Var>; MAX_VARS] = [NO_VAR; MAX_VARS];
+static mut ARGS: Option<Vec<OsString>> = None;
+/// The number of arguments consumed from the front and from the back of the arguments.
+static mut ARGS_CONSUMED: (usize, usize) = (0, 0);
+
+/// Sets the variable `key` to `value`.
+pub fn seed_var<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: V) {
+    set_entry(key.as_ref(), Some(value.as_ref().to_os_string()));
+}
+
+/// Unsets the variable `key`.
+pub fn seed_missing_var<K: AsRef<OsStr>>(key: K) {
+    set_entry(key.as_ref(), None);
+}
+
+/// Sets the command-line arguments (including the program name, which is usually the first one).
+pub fn seed_args<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(args: I) {
+    let args = args.into_iter().map(|arg| arg.as_ref().to_os_string()).collect();
+    unsafe {
+        ARGS = Some(args);
+        ARGS_CONSUMED = (0, 0);
+    }
+}
+
+/// Model for `std::env::var`.
+pub fn var<K: AsRef<OsStr>>(key: K) -> Result<String, VarError> {
+    match var_os(key) {
+        Some(value) => value.into_string().map_err(VarError::NotUnicode),
+        None => Err(VarError::NotPresent),
+    }
+}
+
+/// Model for `std::env::var_os`.
+pub fn var_os<K: AsRef<OsStr>>(key: K) -> Option<OsString> {
+    let key = key.as_ref();
+    let idx = match find_entry(key) {
+        Some(idx) => idx,
+        None => {
+            let value = crate::any::<bool>().then(|| any_string::<MAX_VALUE_LEN>().into());
+            set_entry(key, value)
+        }
+    };
+    unsafe { VARS[idx].as_ref().unwrap().value.clone() }
+}
+
+/// Model for `std::env::set_var`.
+pub fn set_var<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: V) {
+    seed_var(key, value);
+}
+
+/// Model for `std::env::remove_var`.
+pub fn remove_var<K: AsRef<OsStr>>(key: K) {
+    seed_missing_var(key);
+}
+
+/// Model for `<std::env::Args as Iterator>::next`.
+pub fn args_next(_args: &mut Args) -> Option<String> {
+    next_arg(false).map(|arg| arg.into_string().unwrap())
+}
+
+/// Model for `<std::env::Args as Iterator>::size_hint`.
+pub fn args_size_hint(_args: &Args) -> (usize, Option<usize>) {
+    let len = remaining_args();
+    (len, Some(len))
+}
+
+/// Model for `<std::env::Args as ExactSizeIterator>::len`.
+pub fn args_len(_args: &Args) -> usize {
+    remaining_args()
+}
+
+/// Model for `<std::env::Args as DoubleEndedIterator>::next_back`.
+pub fn args_next_back(_args: &mut Args) -> Option<String> {
+    next_arg(true).map(|arg| arg.into_string().unwrap())
+}
+
+/// Model for `<std::env::ArgsOs as Iterator>::next`.
+pub fn args_os_next(_args: &mut ArgsOs) -> Option<OsString> {
+    next_arg(false)
+}
+
+/// Model for `<std::env::ArgsOs as Iterator>::size_hint`.
+pub fn args_os_size_hint(_args: &ArgsOs) -> (usize, Option<usize>) {
+    let len = remaining_args();
+    (len, Some(len))
+}
+
+/// Model for `<std::env::ArgsOs as ExactSizeIterator>::len`.
+pub fn args_os_len(_args: &ArgsOs) -> usize {
+    remaining_args()
+}
+
+/// Model for `<std::env::ArgsOs as DoubleEndedIterator>::next_back`.
+pub fn args_os_next_back(_args: &mut ArgsOs) -> Option<OsString> {
+    next_arg(true)
+}
+
+fn find_entry(key: &OsStr) -> Option<usize> {
+    unsafe { VARS.iter().position(|var| matches!(var, Some(var) if var.key == key)) }
+}
+
+/// Sets the value of the variable `key`, adding it to the environment if needed.
+fn set_entry(key: &OsStr, value: Option<OsString>) -> usize {
+    let idx = find_entry(key).or_else(|| unsafe { VARS.iter().position(Option::is_none) });
+    assert!(idx.is_some(), "kani::models::env: more than `MAX_VARS` variables were accessed");
+    let idx = idx.unwrap();
+    unsafe { VARS[idx] = Some(Var { key: key.to_os_string(), value }) };
+    idx
+}
+
+/// Returns the command-line arguments, deciding them if they were not read or seeded before.
+fn current_args() -> &'static [OsString] {
+    unsafe {
+        ARGS.get_or_insert_with(|| {
+            let len: usize = crate::any();
+            crate::assume((1..=MAX_ARGS).contains(&len));
+            let mut args = Vec::with_capacity(MAX_ARGS);
+            for i in 0..MAX_ARGS {
+                if i < len {
+                    args.push(any_string::<MAX_ARG_LEN>().into());
+                }
+            }
+            args
+        })
+    }
+}
+
+/// Returns the number of arguments that were not consumed yet.
+fn remaining_args() -> usize {
+    let len = current_args().len();
+    let (front, back) = unsafe { ARGS_CONSUMED };
+    len - front - back
+}
+
+/// Consumes the next argument from the front (or from the back, if `back` is set).
+fn next_arg(back: bool) -> Option<OsString> {
+    if remaining_args() == 0 {
+        return None;
+    }
+    let args = current_args();
+    unsafe {
+        let (front, consumed_back) = &mut ARGS_CONSUMED;
+        if back {
+            *consumed_back += 1;
+            Some(args[args.len() - *consumed_back].clone())
+        } else {
+            *front += 1;
+            Some(args[*front - 1].clone())
+        }
+    }
+}
+
+/// Returns a string of at most `MAX_LEN` arbitrary characters.
+fn any_string<const MAX_LEN: usize>() -> String {
+    let len: usize = crate::any();
+    crate::assume(len <= MAX_LEN);
+    let mut string = String::new();
+    for i in 0..MAX_LEN {
+        if i < len {
+            string.push(crate::any::<char>());
+        }
+    }
+    string
+}
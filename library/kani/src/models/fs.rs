@@ -0,0 +1,276 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Models for [`std::fs`], backed by a bounded in-memory file system.
+//!
+//! The file system starts out unknown: the first time a path is accessed, the models decide
+//! nondeterministically whether the file exists, and if it does, its contents (of at most
+//! [`MAX_FILE_LEN`] arbitrary bytes). The decision is recorded, so later accesses to the same
+//! path are consistent with it. Harnesses can also pre-seed paths with [`seed_file`] and
+//! [`seed_missing_file`]. Besides, every operation may fail nondeterministically, like I/O
+//! operations can (see [`enable_failures`]).
+//!
+//! The models replace the functions of `std::fs` that take a path, and the methods of
+//! [`File`] that are used by [`Read`](std::io::Read) and [`Write`](std::io::Write):
+//!
+//! | Original | Model |
+//! | --- | --- |
+//! | `std::fs::read` | [`read`] |
+//! | `std::fs::read_to_string` | [`read_to_string`] |
+//! | `std::fs::write` | [`write`] |
+//! | `std::fs::remove_file` | [`remove_file`] |
+//! | `std::fs::File::open` | [`file_open`] |
+//! | `std::fs::File::create` | [`file_create`] |
+//! | `<std::fs::File as std::io::Read>::read` | [`file_read`] |
+//! | `<std::fs::File as std::io::Read>::read_buf` | [`file_read_buf`] |
+//! | `<std::fs::File as std::io::Read>::read_to_end` | [`file_read_to_end`] |
+//! | `<std::fs::File as std::io::Read>::read_to_string` | [`file_read_to_string`] |
+//! | `<std::fs::File as std::io::Write>::write` | [`file_write`] |
+//! | `<std::fs::File as std::io::Write>::flush` | [`file_flush`] |
+//!
+//! A [`File`] opened by the models must only be used through the models of its methods.
+//! Note that checking that file contents are valid UTF-8 requires a loop over the contents, so
+//! harnesses that read unknown files as strings need an unwinding bound of at least
+//! `MAX_FILE_LEN + 1`.
+//!
+//! Assumptions:
+//! - Files that are not seeded have at most [`MAX_FILE_LEN`] bytes.
+//! - At most [`MAX_FILES`] paths are accessed, and at most [`MAX_OPEN_FILES`] files are opened
+//!   (a failed assertion reports when a bound is exceeded).
+//! - Writes never write only part of the buffer (but they may fail).
+//! - Paths are compared as given, i.e., no two paths refer to the same file (e.g., through
+//!   symbolic links or relative paths).
+
+use std::fs::File;
+use std::io::{self, BorrowedCursor};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::path::{Path, PathBuf};
+
+/// The maximum length of the files that are not seeded.
+pub const MAX_FILE_LEN: usize = 16;
+/// The maximum number of paths in the file system.
+pub const MAX_FILES: usize = 8;
+/// The maximum number of files that can be opened.
+pub const MAX_OPEN_FILES: usize = 8;
+
+/// A path of the file system, with its contents if the file exists.
+struct Entry {
+    path: PathBuf,
+    contents: Option<Vec<u8>>,
+}
+
+/// A file opened with [`file_open`] or [`file_create`].
+struct OpenFile {
+    entry: usize,
+    pos: usize,
+    readable: bool,
+    writable: bool,
+}
+
+/// The file descriptor of the first open file. The descriptors of the open files are only
+/// used to find them in [`OPEN_FILES`].
+const FIRST_FD: RawFd = 1000;
+/// The error code for a file descriptor that does not support an operation.
+const EBADF: i32 = 9;
+
+const NO_ENTRY: Option<Entry> = None;
+const NO_OPEN_FILE: Option<OpenFile> = None;
+
+static mut ENTRIES: [Option<Entry>; MAX_FILES] = [NO_ENTRY; MAX_FILES];
+static mut OPEN_FILES: [Option<OpenFile>; MAX_OPEN_FILES] = [NO_OPEN_FILE; MAX_OPEN_FILES];
+static mut FAILURES: bool = true;
+
+/// Creates the file at `path` with the given contents, or replaces its contents.
+pub fn seed_file<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) {
+    set_entry(path.as_ref(), Some(contents.as_ref().to_vec()));
+}
+
+/// Removes the file at `path`, so that it does not exist until it is created.
+pub fn seed_missing_file<P: AsRef<Path>>(path: P) {
+    set_entry(path.as_ref(), None);
+}
+
+/// Sets whether operations may fail nondeterministically (they may by default).
+pub fn enable_failures(enable: bool) {
+    unsafe { FAILURES = enable };
+}
+
+/// Model for `std::fs::read`.
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    nondet_failure()?;
+    let entry = existing_entry(path.as_ref())?;
+    Ok(contents(entry).to_vec())
+}
+
+/// Model for `std::fs::read_to_string`.
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|_| io::Error::from(io::ErrorKind::InvalidData))
+}
+
+/// Model for `std::fs::write`.
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    nondet_failure()?;
+    set_entry(path.as_ref(), Some(contents.as_ref().to_vec()));
+    Ok(())
+}
+
+/// Model for `std::fs::remove_file`.
+pub fn remove_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    nondet_failure()?;
+    let entry = existing_entry(path.as_ref())?;
+    unsafe { ENTRIES[entry].as_mut().unwrap().contents = None };
+    Ok(())
+}
+
+/// Model for `std::fs::File::open`: opens the file for reading.
+pub fn file_open<P: AsRef<Path>>(path: P) -> io::Result<File> {
+    nondet_failure()?;
+    let entry = existing_entry(path.as_ref())?;
+    Ok(open_file(OpenFile { entry, pos: 0, readable: true, writable: false }))
+}
+
+/// Model for `std::fs::File::create`: creates or truncates the file, and opens it for writing.
+pub fn file_create<P: AsRef<Path>>(path: P) -> io::Result<File> {
+    nondet_failure()?;
+    let entry = set_entry(path.as_ref(), Some(vec![]));
+    Ok(open_file(OpenFile { entry, pos: 0, readable: false, writable: true }))
+}
+
+/// Model for `<File as Read>::read`: reads a nondeterministic number of bytes, which is only
+/// `0` at the end of the file.
+pub fn file_read(file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let unread = unread(file)?;
+    let max = buf.len().min(unread.len());
+    let len: usize = crate::any();
+    crate::assume(len <= max && (len > 0 || max == 0));
+    buf[..len].copy_from_slice(&unread[..len]);
+    advance(file, len);
+    Ok(len)
+}
+
+/// Model for `<File as Read>::read_buf`: reads like [`file_read`].
+pub fn file_read_buf(file: &mut File, mut cursor: BorrowedCursor<'_>) -> io::Result<()> {
+    let unread = unread(file)?;
+    let max = cursor.capacity().min(unread.len());
+    let len: usize = crate::any();
+    crate::assume(len <= max && (len > 0 || max == 0));
+    cursor.append(&unread[..len]);
+    advance(file, len);
+    Ok(())
+}
+
+/// Model for `<File as Read>::read_to_end`: reads the rest of the file at once.
+pub fn file_read_to_end(file: &mut File, buf: &mut Vec<u8>) -> io::Result<usize> {
+    let unread = unread(file)?;
+    buf.extend_from_slice(unread);
+    advance(file, unread.len());
+    Ok(unread.len())
+}
+
+/// Model for `<File as Read>::read_to_string`: reads the rest of the file at once.
+pub fn file_read_to_string(file: &mut File, buf: &mut String) -> io::Result<usize> {
+    let unread = unread(file)?;
+    let string =
+        std::str::from_utf8(unread).map_err(|_| io::Error::from(io::ErrorKind::InvalidData))?;
+    buf.push_str(string);
+    advance(file, unread.len());
+    Ok(unread.len())
+}
+
+/// Model for `<File as Write>::write`: writes the whole buffer.
+pub fn file_write(file: &mut File, buf: &[u8]) -> io::Result<usize> {
+    let open = open_file_mut(file);
+    if !open.writable {
+        return Err(io::Error::from_raw_os_error(EBADF));
+    }
+    nondet_failure()?;
+    let contents = unsafe { ENTRIES[open.entry].as_mut().unwrap().contents.get_or_insert(vec![]) };
+    // Overwrite the contents after the position, and append the rest.
+    let overwritten = contents.len().saturating_sub(open.pos).min(buf.len());
+    contents[open.pos..open.pos + overwritten].copy_from_slice(&buf[..overwritten]);
+    contents.extend_from_slice(&buf[overwritten..]);
+    open.pos += buf.len();
+    Ok(buf.len())
+}
+
+/// Model for `<File as Write>::flush`.
+pub fn file_flush(file: &mut File) -> io::Result<()> {
+    open_file_mut(file);
+    nondet_failure()
+}
+
+/// Returns an error nondeterministically, if failures are enabled.
+fn nondet_failure() -> io::Result<()> {
+    if unsafe { FAILURES } && crate::any() {
+        Err(io::Error::from(io::ErrorKind::Other))
+    } else {
+        Ok(())
+    }
+}
+
+fn find_entry(path: &Path) -> Option<usize> {
+    unsafe { ENTRIES.iter().position(|entry| matches!(entry, Some(entry) if entry.path == path)) }
+}
+
+/// Sets the contents of the file at `path`, adding the path to the file system if needed.
+fn set_entry(path: &Path, contents: Option<Vec<u8>>) -> usize {
+    let idx = find_entry(path).or_else(|| unsafe { ENTRIES.iter().position(Option::is_none) });
+    assert!(idx.is_some(), "kani::models::fs: more than `MAX_FILES` paths were accessed");
+    let idx = idx.unwrap();
+    unsafe { ENTRIES[idx] = Some(Entry { path: path.to_path_buf(), contents }) };
+    idx
+}
+
+/// Returns the index of the entry for `path` if the file exists, deciding whether it exists
+/// (and its contents) if the path was not accessed before.
+fn existing_entry(path: &Path) -> io::Result<usize> {
+    let idx = match find_entry(path) {
+        Some(idx) => idx,
+        None => {
+            let contents = crate::any::<bool>().then(crate::vec::any_vec::<u8, MAX_FILE_LEN>);
+            set_entry(path, contents)
+        }
+    };
+    match unsafe { &ENTRIES[idx] } {
+        Some(Entry { contents: Some(_), .. }) => Ok(idx),
+        _ => Err(io::Error::from(io::ErrorKind::NotFound)),
+    }
+}
+
+/// Returns the contents of an entry, which are empty if the file was removed.
+fn contents(entry: usize) -> &'static [u8] {
+    unsafe { ENTRIES[entry].as_ref().unwrap().contents.as_deref().unwrap_or_default() }
+}
+
+fn open_file(open: OpenFile) -> File {
+    let slot = unsafe { OPEN_FILES.iter().position(Option::is_none) };
+    assert!(slot.is_some(), "kani::models::fs: more than `MAX_OPEN_FILES` files were opened");
+    let slot = slot.unwrap();
+    unsafe { OPEN_FILES[slot] = Some(open) };
+    // SAFETY: the file descriptor is only used to find the open file by the models.
+    unsafe { File::from_raw_fd(FIRST_FD + slot as RawFd) }
+}
+
+fn open_file_mut(file: &File) -> &'static mut OpenFile {
+    let open = usize::try_from(file.as_raw_fd() - FIRST_FD)
+        .ok()
+        .and_then(|slot| unsafe { OPEN_FILES.get_mut(slot) })
+        .and_then(Option::as_mut);
+    assert!(open.is_some(), "kani::models::fs: the file was not opened by the models");
+    open.unwrap()
+}
+
+/// Returns the contents of an open file after its position, or an error (nondeterministically,
+/// or if the file is not readable).
+fn unread(file: &File) -> io::Result<&'static [u8]> {
+    let open = open_file_mut(file);
+    if !open.readable {
+        return Err(io::Error::from_raw_os_error(EBADF));
+    }
+    nondet_failure()?;
+    Ok(contents(open.entry).get(open.pos..).unwrap_or_default())
+}
+
+fn advance(file: &File, len: usize) {
+    open_file_mut(file).pos += len;
+}
@@ -0,0 +1,64 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Models for the hashing used by [`HashMap`](std::collections::HashMap) and
+//! [`HashSet`](std::collections::HashSet).
+//!
+//! By default, both collections seed their hasher with random keys that are read from the OS
+//! and cached in thread-local storage, and hash every key with SipHash, whose rounds loop over
+//! the bytes of the key. The models below replace [`RandomState::new`] and the methods of
+//! [`DefaultHasher`], so that creating a map or hashing a key involves no loops.
+//!
+//! A harness can use these models for a map or set created with `new` or `default` by stubbing
+//! all of:
+//!
+//! ```ignore
+//! #[kani::stub(std::collections::hash_map::RandomState::new, kani::models::hash_map::random_state_new)]
+//! #[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::write, kani::models::hash_map::write)]
+//! #[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::write_str, kani::models::hash_map::write_str)]
+//! #[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::finish, kani::models::hash_map::finish)]
+//! ```
+
+use std::collections::hash_map::{DefaultHasher, RandomState};
+
+/// Model for [`RandomState::new`]: creates a `RandomState` with fixed keys.
+///
+/// Assumptions: every map or set gets the same keys, so verification only covers one choice of
+/// keys. This has no effect on the hashes when the [`DefaultHasher`] models are used as well.
+pub fn random_state_new() -> RandomState {
+    // SAFETY: `RandomState` only contains the two `u64` keys of the hasher, for which zero is a
+    // valid value.
+    unsafe { std::mem::zeroed() }
+}
+
+/// Model for `<DefaultHasher as Hasher>::write`: ignores the bytes.
+///
+/// The other `write_*` methods of [`DefaultHasher`] (except for [`write_str`]) are implemented
+/// in terms of `write`, so this model also applies to them.
+///
+/// Assumptions: all the values hashed with a [`DefaultHasher`] get the same hash (see
+/// [`finish`]).
+pub fn write(_hasher: &mut DefaultHasher, _bytes: &[u8]) {}
+
+/// Model for `<DefaultHasher as Hasher>::write_str`: ignores the string.
+///
+/// Assumptions: all the values hashed with a [`DefaultHasher`] get the same hash (see
+/// [`finish`]).
+pub fn write_str(_hasher: &mut DefaultHasher, _s: &str) {}
+
+/// Model for `<DefaultHasher as Hasher>::finish`: returns the same hash for every value, so
+/// every key of a map or set collides with all the others.
+///
+/// Maps and sets remain correct, since they still compare colliding keys for equality: a lookup
+/// compares the key with each entry of the collection (with a loop bounded by its length)
+/// instead of hashing it. A hash that is nondeterministic per key would avoid the collisions, but
+/// the model can't remember which hash it chose for a key without hashing it.
+///
+/// Assumptions: every key collides, so verification doesn't cover the behavior of maps and sets
+/// whose keys have distinct hashes (e.g., the probing of hashbrown). The iteration order of a
+/// map or set only depends on the order of the insertions and removals, so properties that
+/// depend on the (otherwise random) iteration order are only verified for that order. Code that
+/// uses [`DefaultHasher`] directly also sees the same hash for every value.
+pub fn finish(_hasher: &DefaultHasher) -> u64 {
+    0
+}
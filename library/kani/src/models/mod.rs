@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Verification-friendly models of standard library APIs.
+//!
+//! Each model is a function with the same signature as the function/method of the standard
+//! library it replaces. Models are enabled per harness with the stubbing mechanism (which
+//! requires `--enable-unstable --enable-stubbing`), e.g.:
+//!
+//! ```ignore
+//! #[kani::proof]
+//! #[kani::stub(Vec::push, kani::models::vec::push)]
+//! #[kani::stub(Vec::shrink_to_fit, kani::models::vec::shrink_to_fit)]
+//! fn check_buffer() {
+//!     let mut buffer: Vec<u8> = Vec::new();
+//!     buffer.push(kani::any());
+//!     buffer.shrink_to_fit();
+//!     assert_eq!(buffer.len(), 1);
+//! }
+//! ```
+//!
+//! Stubs that every harness of a crate should use can instead be given in the
+//! `package.metadata.kani.stubs` table of `Cargo.toml`.
+//!
+//! A model may behave differently from the standard library where the latter leaves the
+//! behavior unspecified (e.g., the capacity of a collection, or the iteration order of a map).
+//! The documentation of each model lists the assumptions it adds, i.e., the behaviors of the
+//! standard library that verification no longer covers when the model is used.
+//!
+//! The models of the operating system (the file system of [`fs`], and the environment of
+//! [`env`]) keep their state in bounded tables, whose contents are nondeterministic unless a
+//! harness seeds them.
+//!
+//! Kani itself only replaces the `getrandom` crate (the source of randomness of `rand`, among
+//! others) by default: it fills the buffers passed to `getrandom::getrandom` with
+//! nondeterministic bytes, unless `--no-default-models` is given.
+//!
+//! The model of [`process::exit`] is always used: Kani's override of `std::process::exit`
+//! calls it.
+
+pub mod env;
+pub mod fs;
+pub mod hash_map;
+pub mod process;
+pub mod time;
+pub mod vec;
+pub mod vec_deque;
@@ -0,0 +1,78 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Model for [`std::process::exit`].
+//!
+//! Unlike the other models, this one is not a stub: Kani's override of `std::process::exit`
+//! always calls [`exit`], which ends the current execution path successfully. Paths that exit
+//! are not explored any further, so checks after a call to `exit` are only verified for the paths
+//! that don't call it.
+//!
+//! A harness can declare the exit codes it expects with [`expect_exit_code`], e.g., for a command
+//! line tool that exits with `0` on success and `2` on invalid arguments:
+//!
+//! ```ignore
+//! #[kani::proof]
+//! fn check_main() {
+//!     kani::expect_exit_code(0);
+//!     kani::expect_exit_code(2);
+//!     run(kani::any());
+//! }
+//! ```
+//!
+//! `std::process::abort` is still a failure.
+
+/// The maximum number of exit codes that a harness can expect.
+pub const MAX_EXPECTED_EXIT_CODES: usize = 8;
+
+static mut EXPECTED_EXIT_CODES: [i32; MAX_EXPECTED_EXIT_CODES] = [0; MAX_EXPECTED_EXIT_CODES];
+static mut NUM_EXPECTED_EXIT_CODES: usize = 0;
+
+/// Declares `code` as an expected exit code of the harness.
+///
+/// Once a harness expects an exit code, calling `std::process::exit` with a code that the harness
+/// doesn't expect is a failure. In addition, a cover property for each expected exit code checks
+/// that it is reachable: the `N`th call to `expect_exit_code` gets the cover property "the process
+/// exits with expected exit code #N".
+pub fn expect_exit_code(code: i32) {
+    unsafe {
+        crate::assert(
+            NUM_EXPECTED_EXIT_CODES < MAX_EXPECTED_EXIT_CODES,
+            "too many expected exit codes",
+        );
+        EXPECTED_EXIT_CODES[NUM_EXPECTED_EXIT_CODES] = code;
+        NUM_EXPECTED_EXIT_CODES += 1;
+    }
+}
+
+/// Checks that each expected exit code is reachable, with a cover property for each of the
+/// `MAX_EXPECTED_EXIT_CODES` slots (numbered from 1). Each cover property needs a call of its own,
+/// and only the slots in use are reachable.
+macro_rules! cover_expected_exit_codes {
+    ($code:expr, $($slot:literal),+) => {
+        $(
+            if $slot <= NUM_EXPECTED_EXIT_CODES {
+                crate::cover(
+                    $code == EXPECTED_EXIT_CODES[$slot - 1],
+                    concat!("the process exits with expected exit code #", $slot),
+                );
+            }
+        )+
+    };
+}
+
+/// Model for `std::process::exit`: ends the current execution path without a failure, after
+/// checking that `code` is expected (if the harness expects any exit code).
+pub fn exit(code: i32) -> ! {
+    unsafe {
+        if NUM_EXPECTED_EXIT_CODES > 0 {
+            cover_expected_exit_codes!(code, 1, 2, 3, 4, 5, 6, 7, 8);
+            let expected = EXPECTED_EXIT_CODES[..NUM_EXPECTED_EXIT_CODES].contains(&code);
+            crate::assert(expected, "the process exits with an unexpected exit code");
+        }
+    }
+    crate::assume(false);
+    // The assumption above blocks this path.
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
@@ -0,0 +1,64 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Models for the clocks of [`std::time`].
+//!
+//! Every reading of a clock returns the previous reading plus an arbitrary duration, so the
+//! readings are nondeterministic but never decrease. The first reading of [`Instant`] is an
+//! arbitrary instant, and the first reading of [`SystemTime`] is an arbitrary time after the
+//! Unix epoch.
+//!
+//! A harness that reads a clock can use these models by stubbing:
+//!
+//! | Original | Model |
+//! | --- | --- |
+//! | `std::time::Instant::now` | [`instant_now`] |
+//! | `std::time::Instant::elapsed` | [`instant_elapsed`] |
+//! | `std::time::SystemTime::now` | [`system_time_now`] |
+//! | `std::time::SystemTime::elapsed` | [`system_time_elapsed`] |
+//!
+//! Assumptions:
+//! - The clocks do not overflow, i.e., adding a duration to a reading fails only if the
+//!   result is beyond the range of the clock.
+//! - The system clock never goes backwards (which `std` does not guarantee, e.g., because the
+//!   clock may be adjusted), so [`SystemTime::elapsed`] only fails for times in the future.
+
+use std::time::{Duration, Instant, SystemTime, SystemTimeError};
+
+static mut LAST_INSTANT: Option<Instant> = None;
+static mut LAST_SYSTEM_TIME: Option<SystemTime> = None;
+
+/// Model for `std::time::Instant::now`.
+pub fn instant_now() -> Instant {
+    // SAFETY: `Instant` is a plain (platform-specific) timestamp, for which all zero bytes is a
+    // valid (if arbitrary) value.
+    let start: Instant = unsafe { std::mem::zeroed() };
+    unsafe { tick(&mut LAST_INSTANT, start, Instant::checked_add) }
+}
+
+/// Model for `std::time::Instant::elapsed`.
+pub fn instant_elapsed(instant: &Instant) -> Duration {
+    instant_now().saturating_duration_since(*instant)
+}
+
+/// Model for `std::time::SystemTime::now`.
+pub fn system_time_now() -> SystemTime {
+    unsafe { tick(&mut LAST_SYSTEM_TIME, SystemTime::UNIX_EPOCH, SystemTime::checked_add) }
+}
+
+/// Model for `std::time::SystemTime::elapsed`.
+pub fn system_time_elapsed(time: &SystemTime) -> Result<Duration, SystemTimeError> {
+    system_time_now().duration_since(*time)
+}
+
+/// Reads a clock: returns the last reading (or `start` for the first one) plus an arbitrary
+/// duration.
+fn tick<T: Copy>(last: &mut Option<T>, start: T, checked_add: fn(&T, Duration) -> Option<T>) -> T {
+    let nanos: u32 = crate::any();
+    crate::assume(nanos < 1_000_000_000);
+    let now = checked_add(&last.unwrap_or(start), Duration::new(crate::any(), nanos));
+    crate::assume(now.is_some());
+    let now = now.unwrap();
+    *last = Some(now);
+    now
+}
@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Models for [`Vec`].
+//!
+//! Growing a vector reallocates its buffer and moves its elements, which adds a lot of work to
+//! the `push`es that exceed the capacity. The model of [`push`](push()) bounds the length of
+//! vectors to [`CAPACITY`] instead: the first `push` that needs more space allocates room for
+//! [`CAPACITY`] elements at once, so a vector is reallocated at most once. Like with `std`,
+//! vectors don't allocate until they need space.
+//!
+//! Only `push` is modeled: the other methods that add elements (e.g., `insert` or `extend`) grow
+//! the vector as usual, and aren't bounded.
+
+use std::alloc::Allocator;
+
+/// The maximum number of elements of a vector with the model of [`push`](push()).
+pub const CAPACITY: usize = 1024;
+
+/// Model for [`Vec::push`]: appends an element, making room for [`CAPACITY`] elements if the
+/// vector is full.
+///
+/// Assumptions: vectors never hold more than [`CAPACITY`] elements. Pushing more fails an
+/// assertion, so verification doesn't cover the behavior of longer vectors.
+pub fn push<T, A: Allocator>(vec: &mut Vec<T, A>, value: T) {
+    let len = vec.len();
+    assert!(len < CAPACITY, "the model of `Vec::push` supports at most {CAPACITY} elements");
+    if len == vec.capacity() {
+        vec.reserve_exact(CAPACITY - len);
+    }
+    // We can't call `push`, which would call this model again.
+    // SAFETY: the vector has space for at least one more element.
+    unsafe {
+        vec.as_mut_ptr().add(len).write(value);
+        vec.set_len(len + 1);
+    }
+}
+
+/// Model for [`Vec::shrink_to_fit`]: keeps the current buffer, instead of reallocating it.
+///
+/// Assumptions: none, since `shrink_to_fit` may leave a capacity larger than the length.
+// Stubs must have the signature of the function they replace.
+#[allow(clippy::ptr_arg)]
+pub fn shrink_to_fit<T, A: Allocator>(_vec: &mut Vec<T, A>) {}
@@ -0,0 +1,50 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Models for [`VecDeque`].
+//!
+//! A `VecDeque` is a ring buffer: growing it reallocates the buffer and may copy a section of
+//! the elements to restore the ring order. The models of [`push_back`] and [`push_front`] bound
+//! the length of deques to [`CAPACITY`] instead: the first push that needs more space allocates
+//! room for [`CAPACITY`] elements at once, so a deque grows at most once. Like with `std`, deques
+//! don't allocate until they need space.
+
+use std::alloc::Allocator;
+use std::collections::VecDeque;
+
+/// The maximum number of elements of a deque with the models of [`push_back`] and
+/// [`push_front`].
+pub const CAPACITY: usize = 1024;
+
+/// Model for [`VecDeque::push_back`]: appends an element, making room for [`CAPACITY`] elements
+/// if the deque is full.
+///
+/// Assumptions: deques never hold more than [`CAPACITY`] elements. Pushing more fails an
+/// assertion, so verification doesn't cover the behavior of longer deques.
+pub fn push_back<T, A: Allocator>(deque: &mut VecDeque<T, A>, value: T) {
+    reserve_bounded(deque);
+    // `insert` doesn't call `push_back`, which would call this model again.
+    deque.insert(deque.len(), value);
+}
+
+/// Model for [`VecDeque::push_front`]: prepends an element, making room for [`CAPACITY`]
+/// elements if the deque is full.
+///
+/// Assumptions: the same as [`push_back`].
+pub fn push_front<T, A: Allocator>(deque: &mut VecDeque<T, A>, value: T) {
+    reserve_bounded(deque);
+    deque.insert(0, value);
+}
+
+/// Model for [`VecDeque::shrink_to_fit`]: keeps the current buffer, instead of reallocating it.
+///
+/// Assumptions: none, since `shrink_to_fit` may leave a capacity larger than the length.
+pub fn shrink_to_fit<T, A: Allocator>(_deque: &mut VecDeque<T, A>) {}
+
+fn reserve_bounded<T, A: Allocator>(deque: &mut VecDeque<T, A>) {
+    let len = deque.len();
+    assert!(len < CAPACITY, "the models of `VecDeque` pushes support at most {CAPACITY} elements");
+    if len == deque.capacity() {
+        deque.reserve_exact(CAPACITY - len);
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Helper code for running proof harnesses as randomized tests outside of Kani.
+//!
+//! With the `random_testing` feature, `#[kani::proof]` also generates a `#[test]` that calls
+//! [random_testing_run]. Each run of the harness is a *sample*: `kani::any()` returns values drawn
+//! from a pseudo-random generator seeded for that sample, and `kani::assume` rejects the sample
+//! if its condition doesn't hold.
+
+use crate::rejection;
+use std::cell::RefCell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::panic;
+
+/// Environment variable with the number of samples that should be accepted per harness.
+const ITERATIONS_VAR: &str = "KANI_RANDOM_ITERATIONS";
+/// Environment variable with the seed of a single sample to run, e.g. to reproduce a failure.
+const SEED_VAR: &str = "KANI_RANDOM_SEED";
+const DEFAULT_ITERATIONS: u64 = 256;
+/// How many samples may be rejected by `kani::assume` for each sample we want to accept.
+const MAX_REJECTIONS_PER_SAMPLE: u64 = 100;
+
+thread_local! {
+    /// The generator of the sample that is currently running on this thread, if any.
+    /// thread_local! allows the generated tests to run in parallel.
+    static RNG: RefCell<Option<SplitMix64>> = RefCell::new(None);
+}
+
+/// This function runs `proof_harness` with pseudo-random values for `kani::any()`.
+///
+/// By default, it runs until 256 samples were accepted, which can be changed with the
+/// `KANI_RANDOM_ITERATIONS` environment variable. If a sample fails, its seed is printed
+/// before the panic is propagated. Setting `KANI_RANDOM_SEED` to that seed runs that sample only.
+pub fn random_testing_run<F: Fn()>(harness_name: &str, proof_harness: F) {
+    run(harness_name, false, &proof_harness)
+}
+
+/// Like [random_testing_run], for harnesses marked with `#[should_panic]`: the test succeeds as
+/// soon as a sample panics, and fails if none of them does.
+pub fn random_testing_run_should_panic<F: Fn()>(harness_name: &str, proof_harness: F) {
+    run(harness_name, true, &proof_harness)
+}
+
+fn run<F: Fn()>(harness_name: &str, should_panic: bool, proof_harness: &F) {
+    if let Some(seed) = env_u64(SEED_VAR) {
+        match run_sample(harness_name, seed, should_panic, proof_harness) {
+            Sample::Rejected => println!(
+                "The sample with seed {seed} of `{harness_name}` was rejected by kani::assume"
+            ),
+            Sample::Accepted if should_panic => {
+                panic!("The sample with seed {seed} of `{harness_name}` did not panic")
+            }
+            Sample::Accepted | Sample::Panicked => {}
+        }
+        return;
+    }
+
+    let iterations = env_u64(ITERATIONS_VAR).unwrap_or(DEFAULT_ITERATIONS);
+    let mut seeds = SplitMix64(RandomState::new().build_hasher().finish());
+    let mut accepted = 0;
+    let mut attempts = 0;
+    while accepted < iterations && attempts < iterations * MAX_REJECTIONS_PER_SAMPLE {
+        attempts += 1;
+        match run_sample(harness_name, seeds.next_u64(), should_panic, proof_harness) {
+            Sample::Accepted => accepted += 1,
+            Sample::Rejected => {}
+            Sample::Panicked => return,
+        }
+    }
+    assert!(
+        iterations == 0 || accepted > 0,
+        "All {attempts} samples of `{harness_name}` were rejected by kani::assume"
+    );
+    assert!(
+        !should_panic,
+        "None of the {accepted} samples of `{harness_name}` panicked, but the harness is marked \
+        with `#[should_panic]`"
+    );
+}
+
+/// The outcome of a sample.
+#[derive(Debug, PartialEq, Eq)]
+enum Sample {
+    /// The harness finished.
+    Accepted,
+    /// The sample was rejected by `kani::assume`.
+    Rejected,
+    /// The harness panicked, which is only an outcome if it's expected to.
+    Panicked,
+}
+
+/// Run a single sample of the harness. A panic is propagated unless `should_panic` is set.
+fn run_sample<F: Fn()>(
+    harness_name: &str,
+    seed: u64,
+    should_panic: bool,
+    proof_harness: &F,
+) -> Sample {
+    RNG.with(|rng| *rng.borrow_mut() = Some(SplitMix64(seed)));
+    let result = rejection::run_harness(proof_harness);
+    RNG.with(|rng| *rng.borrow_mut() = None);
+    match result {
+        Ok(true) => Sample::Accepted,
+        Ok(false) => Sample::Rejected,
+        Err(_) if should_panic => Sample::Panicked,
+        Err(payload) => {
+            eprintln!(
+                "Harness `{harness_name}` failed with seed {seed}. \
+                Set the environment variable {SEED_VAR}={seed} to reproduce this failure."
+            );
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+/// Whether a sample is currently running on this thread.
+pub(crate) fn is_running() -> bool {
+    RNG.with(|rng| rng.borrow().is_some())
+}
+
+/// Random testing implementation of kani::any_raw_internal.
+///
+/// # Safety
+///
+/// The semantics of this function require that SIZE_T equals the size of type T.
+pub(crate) unsafe fn any_raw_internal<T, const SIZE_T: usize>() -> T {
+    let mut bytes_t = [0u8; SIZE_T];
+    RNG.with(|rng| {
+        rng.borrow_mut()
+            .as_mut()
+            .expect("kani::any() can only be used inside a proof harness")
+            .fill(&mut bytes_t)
+    });
+    std::mem::transmute_copy::<[u8; SIZE_T], T>(&bytes_t)
+}
+
+fn env_u64(var: &str) -> Option<u64> {
+    std::env::var(var).ok().map(|value| {
+        value
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("Expected an integer in {var}, found `{value}`"))
+    })
+}
+
+/// A small and fast generator (<https://prng.di.unimi.it/splitmix64.c>). We don't need
+/// statistical quality here, only reproducibility from a single `u64` seed.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Fill the bytes of a value. Uniformly random bytes rarely satisfy the assumptions made by
+    /// `Arbitrary` implementations (e.g. `bool` only accepts 0 and 1), nor do they hit boundary
+    /// cases. So most values are small integers or boundary values instead.
+    fn fill(&mut self, bytes: &mut [u8]) {
+        bytes.fill(0);
+        let Some(last) = bytes.len().checked_sub(1) else { return };
+        let (lsb, msb) = if cfg!(target_endian = "little") { (0, last) } else { (last, 0) };
+        match self.next_u64() % 8 {
+            // Zero or one.
+            0 => bytes[lsb] = (self.next_u64() % 2) as u8,
+            // Small non-negative integer.
+            1 => bytes[lsb] = self.next_u64() as u8,
+            // All bits set, i.e. `-1` or the maximum unsigned value.
+            2 => bytes.fill(u8::MAX),
+            // Maximum signed value.
+            3 => {
+                bytes.fill(u8::MAX);
+                bytes[msb] = i8::MAX as u8;
+            }
+            // Minimum signed value.
+            4 => bytes[msb] = i8::MIN as u8,
+            _ => bytes.iter_mut().for_each(|byte| *byte = self.next_u64() as u8),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn any_u64() -> u64 {
+        unsafe { any_raw_internal::<u64, 8>() }
+    }
+
+    #[test]
+    fn check_seed_reproduces_values() {
+        let values = |seed| {
+            let mut generator = SplitMix64(seed);
+            (0..16).map(|_| generator.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(values(42), values(42));
+        assert_ne!(values(42), values(43));
+    }
+
+    #[test]
+    fn check_sample_reproduces_any() {
+        let sample = |seed| {
+            let values = RefCell::new(vec![]);
+            let outcome =
+                run_sample("sample", seed, false, &|| values.borrow_mut().push(any_u64()));
+            assert_eq!(outcome, Sample::Accepted);
+            values.into_inner()
+        };
+        for seed in 0..32 {
+            assert_eq!(sample(seed), sample(seed));
+        }
+        assert!(!is_running());
+    }
+
+    #[test]
+    fn check_sample_outcomes() {
+        let assume_even = || crate::assume(any_u64() % 2 == 0);
+        let outcomes: Vec<_> =
+            (0..64).map(|seed| run_sample("assume", seed, false, &assume_even)).collect();
+        assert!(outcomes.contains(&Sample::Accepted));
+        assert!(outcomes.contains(&Sample::Rejected));
+        assert_eq!(run_sample("panic", 0, true, &|| panic!("expected")), Sample::Panicked);
+    }
+
+    #[test]
+    fn check_should_panic() {
+        random_testing_run_should_panic("should_panic", || assert!(any_u64() != u64::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "marked with `#[should_panic]`")]
+    fn check_should_panic_without_panic() {
+        random_testing_run_should_panic("no_panic", || {
+            any_u64();
+        });
+    }
+
+    #[test]
+    fn check_fill_boundary_values() {
+        let mut generator = SplitMix64(0);
+        let mut bytes = [0u8; 4];
+        let mut seen_max = false;
+        let mut seen_zero = false;
+        for _ in 0..256 {
+            generator.fill(&mut bytes);
+            seen_max |= bytes == [u8::MAX; 4];
+            seen_zero |= bytes == [0; 4];
+        }
+        assert!(seen_max && seen_zero);
+        generator.fill(&mut []);
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for `kani::assume` in concrete executions that explore many inputs, such as random
+//! testing and fuzzing. An input that violates an assumption isn't a failure, so the execution
+//! of the harness is aborted by unwinding with a dedicated payload.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+    /// Whether a harness is being run by [run_harness] on this thread.
+    static RUNNING: Cell<bool> = Cell::new(false);
+}
+
+/// Panic payload used to abort an execution that was rejected by `kani::assume`.
+struct Rejected;
+
+/// Run `proof_harness` once. Returns `Ok(true)` if it finished, `Ok(false)` if it was rejected
+/// by `kani::assume`, and the panic payload if it failed.
+pub(crate) fn run_harness<F: Fn()>(proof_harness: &F) -> Result<bool, Box<dyn Any + Send>> {
+    install_panic_hook();
+    RUNNING.with(|running| running.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(proof_harness));
+    RUNNING.with(|running| running.set(false));
+    match result {
+        Ok(()) => Ok(true),
+        Err(payload) if payload.is::<Rejected>() => Ok(false),
+        Err(payload) => Err(payload),
+    }
+}
+
+/// Abort the current execution if it was started by [run_harness]. Used by `kani::assume`.
+pub(crate) fn reject() {
+    if RUNNING.with(|running| running.get()) {
+        panic::panic_any(Rejected)
+    }
+}
+
+/// Rejected executions unwind through the panic machinery. Don't let the default hook print a
+/// message for each of them.
+fn install_panic_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !info.payload().is::<Rejected>() {
+                default_hook(info)
+            }
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_run_harness() {
+        assert!(matches!(run_harness(&|| {}), Ok(true)));
+        assert!(matches!(run_harness(&reject), Ok(false)));
+        let payload = run_harness(&|| panic!("failure")).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"failure"));
+    }
+
+    #[test]
+    fn check_reject_outside_harness() {
+        // Outside of `run_harness`, `kani::assume` doesn't abort the execution.
+        reject();
+        assert!(matches!(run_harness(&reject), Ok(false)));
+        assert!(!RUNNING.with(|running| running.get()));
+        reject();
+    }
+}
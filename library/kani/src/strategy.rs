@@ -0,0 +1,200 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for generating symbolic values from [proptest](https://docs.rs/proptest) strategies.
+//!
+//! Strategies that implement [SymbolicStrategy] generate their values directly with
+//! [kani::any](crate::any) and [kani::assume](crate::assume). These are integer ranges, `Just`,
+//! and the [vec] and [one_of] strategies of this module, which can also be used in proptest tests
+//! in place of `proptest::collection::vec` and `prop_oneof!`.
+//!
+//! Other strategies can be wrapped with [sampled]. Proptest strategies draw all of their random
+//! choices from the `TestRng` of a `TestRunner`, so we run them with an rng that simply returns
+//! nondeterministic bytes. This is much harder to verify, since it goes through proptest's
+//! configuration and sampling loops.
+use crate::{any, assume};
+use ::proptest::collection::{SizeRange, VecValueTree};
+use ::proptest::strategy::{Just, NewTree, Strategy, UnionValueTree, ValueTree};
+use ::proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
+use std::fmt::Debug;
+use std::ops::{Range, RangeInclusive};
+
+/// The number of nondeterministic bytes used by [sampled].
+const DEFAULT_INPUT_LENGTH: usize = 64;
+
+/// A proptest strategy that Kani can generate symbolic values for.
+pub trait SymbolicStrategy: Strategy {
+    /// Returns a symbolic value among the ones this strategy can generate.
+    fn any_value(&self) -> Self::Value;
+}
+
+macro_rules! symbolic_range {
+    ( $( $type: ty ),* ) => {
+        $(
+            impl SymbolicStrategy for Range<$type> {
+                fn any_value(&self) -> $type {
+                    let value: $type = any();
+                    assume(self.contains(&value));
+                    value
+                }
+            }
+
+            impl SymbolicStrategy for RangeInclusive<$type> {
+                fn any_value(&self) -> $type {
+                    let value: $type = any();
+                    assume(self.contains(&value));
+                    value
+                }
+            }
+        )*
+    };
+}
+
+symbolic_range! { u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize }
+
+impl<T: Clone + Debug> SymbolicStrategy for Just<T> {
+    fn any_value(&self) -> T {
+        self.0.clone()
+    }
+}
+
+/// Strategy to create `Vec`s with a length in a certain range, created by [vec].
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct VecStrategy<S> {
+    element: S,
+    size: SizeRange,
+}
+
+/// Creates a strategy to generate `Vec`s containing elements drawn from `element`, with a
+/// length in `size`. It generates the same values as `proptest::collection::vec`.
+pub fn vec<S: Strategy>(element: S, size: impl Into<SizeRange>) -> VecStrategy<S> {
+    VecStrategy { element, size: size.into() }
+}
+
+impl<S: Strategy + Clone> Strategy for VecStrategy<S> {
+    type Tree = VecValueTree<S::Tree>;
+    type Value = Vec<S::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        ::proptest::collection::vec(self.element.clone(), self.size.clone()).new_tree(runner)
+    }
+}
+
+impl<S: SymbolicStrategy + Clone> SymbolicStrategy for VecStrategy<S> {
+    fn any_value(&self) -> Self::Value {
+        let (min, max) = self.size.start_end_incl();
+        let len: usize = any();
+        assume((min..=max).contains(&len));
+        let mut values = Vec::with_capacity(max);
+        for _ in 0..len {
+            values.push(self.element.any_value());
+        }
+        values
+    }
+}
+
+/// Strategy that picks one of several strategies of the same type, created by [one_of].
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct OneOf<S> {
+    options: Vec<S>,
+}
+
+/// Creates a strategy that generates the values of any of `options`. Like `prop_oneof!`, but the
+/// options must have the same type.
+pub fn one_of<S: Strategy>(options: impl IntoIterator<Item = S>) -> OneOf<S> {
+    let options: Vec<S> = options.into_iter().collect();
+    assert!(!options.is_empty(), "one_of requires at least one option");
+    OneOf { options }
+}
+
+impl<S: Strategy + Clone> Strategy for OneOf<S> {
+    type Tree = UnionValueTree<S>;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        ::proptest::strategy::Union::new(self.options.clone()).new_tree(runner)
+    }
+}
+
+impl<S: SymbolicStrategy + Clone> SymbolicStrategy for OneOf<S> {
+    fn any_value(&self) -> Self::Value {
+        let index: usize = any();
+        assume(index < self.options.len());
+        self.options[index].any_value()
+    }
+}
+
+/// Strategy that generates symbolic values by sampling another strategy, created by [sampled].
+#[must_use = "strategies do nothing unless used"]
+#[derive(Clone, Debug)]
+pub struct Sampled<S>(S);
+
+/// Wraps a strategy that doesn't implement [SymbolicStrategy], so that Kani generates its values
+/// by running it on 64 nondeterministic bytes (see [any_via_strategy_sized]).
+pub fn sampled<S: Strategy>(strategy: S) -> Sampled<S> {
+    Sampled(strategy)
+}
+
+impl<S: Strategy> Strategy for Sampled<S> {
+    type Tree = S::Tree;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner)
+    }
+}
+
+impl<S: Strategy> SymbolicStrategy for Sampled<S> {
+    fn any_value(&self) -> Self::Value {
+        any_via_strategy_sized::<_, DEFAULT_INPUT_LENGTH>(&self.0)
+    }
+}
+
+/// Generates a symbolic value from a proptest strategy.
+///
+/// # Example:
+///
+/// ```rust
+/// let v = kani::any_via_strategy(kani::strategy::vec(0..10u8, 0..3));
+/// assert!(v.len() < 3 && v.iter().all(|x| *x < 10));
+/// ```
+pub fn any_via_strategy<S: SymbolicStrategy>(strategy: S) -> S::Value {
+    strategy.any_value()
+}
+
+/// Generates a symbolic value from any proptest strategy, using INPUT_LENGTH nondeterministic
+/// bytes as the source of randomness. Once they are consumed, the rng only returns zeros, so all
+/// further choices are concrete. Prefer [any_via_strategy] for the strategies it supports.
+///
+/// Values rejected by the strategy, e.g. by `prop_filter`, are excluded with [assume].
+pub fn any_via_strategy_sized<S: Strategy, const INPUT_LENGTH: usize>(strategy: S) -> S::Value {
+    let input: [u8; INPUT_LENGTH] = any();
+    let rng = TestRng::from_seed(RngAlgorithm::PassThrough, &input);
+    let mut runner = TestRunner::new_with_rng(Config::default(), rng);
+    match strategy.new_tree(&mut runner) {
+        Ok(tree) => tree.current(),
+        Err(_) => {
+            assume(false);
+            unreachable!("the strategy rejected an input that should have been excluded")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The strategies of this module must also work as regular proptest strategies.
+    #[test]
+    fn check_proptest_values() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = vec(one_of([1u8..2, 5u8..7]), 1..4);
+        for _ in 0..100 {
+            let value = strategy.new_tree(&mut runner).unwrap().current();
+            assert!((1..4).contains(&value.len()));
+            assert!(value.iter().all(|x| [1, 5, 6].contains(x)));
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for generating symbolic values from implementations of the `Arbitrary` trait of the
+//! [arbitrary](https://docs.rs/arbitrary) crate, which are commonly written for fuzzing.
+use crate::{any, assume};
+use ::arbitrary::{Arbitrary, Unstructured};
+
+/// The number of nondeterministic bytes used by [any_via_arbitrary].
+const DEFAULT_INPUT_LENGTH: usize = 64;
+
+/// Generates a symbolic value of type `T` with its `arbitrary::Arbitrary` implementation, from an
+/// `Unstructured` of 64 nondeterministic bytes.
+///
+/// Inputs for which `T::arbitrary` fails are excluded with [assume].
+pub fn any_via_arbitrary<T>() -> T
+where
+    T: for<'a> Arbitrary<'a>,
+{
+    any_via_arbitrary_sized::<T, DEFAULT_INPUT_LENGTH>()
+}
+
+/// Generates a symbolic value of type `T` with its `arbitrary::Arbitrary` implementation, from an
+/// `Unstructured` of INPUT_LENGTH nondeterministic bytes.
+///
+/// Note that the length of the input bounds the size of the values that can be generated,
+/// e.g. the number of elements of collections, but also how often loops inside of `T::arbitrary`
+/// may need to be unwound.
+pub fn any_via_arbitrary_sized<T, const INPUT_LENGTH: usize>() -> T
+where
+    T: for<'a> Arbitrary<'a>,
+{
+    let input: [u8; INPUT_LENGTH] = any();
+    match T::arbitrary(&mut Unstructured::new(&input)) {
+        Ok(value) => value,
+        Err(_) => {
+            assume(false);
+            unreachable!("`T::arbitrary` failed on an input that should have been excluded")
+        }
+    }
+}
@@ -1,7 +0,0 @@
-// Copyright Kani Contributors
-// SPDX-License-Identifier: Apache-2.0 OR MIT
-
-pub mod c_vec;
-pub mod kani_vec;
-pub mod noback_vec;
-pub mod utils;
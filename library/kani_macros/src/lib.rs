@@ -10,13 +10,15 @@
 
 // proc_macro::quote is nightly-only, so we'll cobble things together instead
 use proc_macro::TokenStream;
-#[cfg(kani)]
+#[cfg(all(not(kani), any(feature = "random_testing", feature = "fuzzing")))]
+use quote::format_ident;
+#[cfg(any(kani, feature = "random_testing", feature = "fuzzing"))]
 use {
     quote::quote,
     syn::{parse_macro_input, ItemFn},
 };
 
-#[cfg(not(kani))]
+#[cfg(all(not(kani), not(any(feature = "random_testing", feature = "fuzzing"))))]
 #[proc_macro_attribute]
 pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // Leave the code intact, so it can be easily be edited in an IDE,
@@ -32,6 +34,72 @@ pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // )
 }
 
+/// Outside Kani with the `random_testing` feature, the harness is also run as a `#[test]`.
+/// The test calls the harness many times with `kani::any()` returning pseudo-random values
+/// (see `kani::random_testing_run`). If the harness is marked with `#[should_panic]`, the test
+/// expects one of the samples to panic instead.
+///
+/// With the `fuzzing` feature, builds for fuzzing (i.e., with `--cfg fuzzing`) also get an entry
+/// point named `kani_fuzz_<harness>` that runs the harness on a fuzzer input (see
+/// `kani::fuzz_run`). It's exported with that symbol name, so that the fuzz targets generated by
+/// `cargo kani fuzz-target` can call it wherever the harness is defined.
+#[cfg(all(not(kani), any(feature = "random_testing", feature = "fuzzing")))]
+#[proc_macro_attribute]
+pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut fn_item = parse_macro_input!(item as ItemFn);
+    let fn_name = fn_item.sig.ident.clone();
+    let harness = if fn_item.sig.asyncness.is_none() {
+        quote!(#fn_name)
+    } else {
+        quote!(|| kani::block_on(#fn_name()))
+    };
+
+    let random_test = if cfg!(feature = "random_testing") {
+        let attr_count = fn_item.attrs.len();
+        fn_item.attrs.retain(|attr| {
+            attr.path.segments.last().map_or(true, |segment| segment.ident != "should_panic")
+        });
+        let run = if fn_item.attrs.len() == attr_count {
+            quote!(kani::random_testing_run)
+        } else {
+            quote!(kani::random_testing_run_should_panic)
+        };
+        let test_name = format_ident!("kani_random_testing_{}", fn_name);
+        quote!(
+            #[test]
+            fn #test_name() {
+                #run(concat!(module_path!(), "::", stringify!(#fn_name)), #harness);
+            }
+        )
+    } else {
+        quote!()
+    };
+
+    let fuzz_entry = if cfg!(feature = "fuzzing") {
+        let entry_name = format_ident!("kani_fuzz_{}", fn_name);
+        quote!(
+            #[cfg(fuzzing)]
+            #[doc(hidden)]
+            #[no_mangle]
+            pub fn #entry_name(data: &[u8]) {
+                kani::fuzz_run(stringify!(#fn_name), data, #harness);
+            }
+        )
+    } else {
+        quote!()
+    };
+
+    quote!(
+        #[allow(dead_code)]
+        #fn_item
+
+        #random_test
+
+        #fuzz_entry
+    )
+    .into()
+}
+
 /// Marks a Kani proof harness
 ///
 /// For async harnesses, this will call [`kani::block_on`] (see its documentation for more information).
@@ -131,7 +199,9 @@ pub fn stub(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Specify a function/method stub pair to use for proof harness
 ///
-/// The attribute `#[kani::stub(original, replacement)]` can only be used alongside `#[kani::proof]`.
+/// The attribute `#[kani::stub(original, replacement)]` can only be used alongside `#[kani::proof]`,
+/// or as a crate-level attribute `#![kani::stub(original, replacement)]`, which applies the stub
+/// to all the harnesses of the crate.
 ///
 /// # Arguments
 /// * `original` - The function or method to replace, specified as a path.
@@ -141,10 +211,88 @@ pub fn stub(_attr: TokenStream, item: TokenStream) -> TokenStream {
 pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut result = TokenStream::new();
 
-    // Translate #[kani::stub(original, replacement)] to #[kanitool::stub(original, replacement)]
-    let insert_string = "#[kanitool::stub(".to_owned() + &attr.to_string() + ")]";
+    // Translate #[kani::stub(original, replacement)] to #[kanitool::stub(original, replacement)].
+    // A crate-level attribute is applied to the contents of the whole crate, which (unless it
+    // consists of a single item) isn't an item, and is translated to an inner attribute.
+    let is_crate = syn::parse::<syn::Item>(item.clone()).is_err();
+    let prefix = if is_crate { "#![kanitool::stub(" } else { "#[kanitool::stub(" };
+    let insert_string = prefix.to_owned() + &attr.to_string() + ")]";
     result.extend(insert_string.parse::<TokenStream>().unwrap());
 
     result.extend(item);
     result
 }
+
+#[cfg(not(kani))]
+#[proc_macro_attribute]
+pub fn unstub(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    // When the config is not kani, we should leave the function alone
+    item
+}
+
+/// Disable the stubs of a function/method that apply to all harnesses for a proof harness
+///
+/// The attribute `#[kani::unstub(original)]` can only be used alongside `#[kani::proof]`.
+///
+/// # Arguments
+/// * `original` - The function or method that should not be replaced, specified as a path.
+#[cfg(kani)]
+#[proc_macro_attribute]
+pub fn unstub(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut result = TokenStream::new();
+
+    // Translate #[kani::unstub(original)] to #[kanitool::unstub(original)]
+    let insert_string = "#[kanitool::unstub(".to_owned() + &attr.to_string() + ")]";
+    result.extend(insert_string.parse::<TokenStream>().unwrap());
+
+    result.extend(item);
+    result
+}
+
+#[cfg(not(kani))]
+#[proc_macro_attribute]
+pub fn no_conversion_checks(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    // When the config is not kani, we should leave the function alone
+    item
+}
+
+/// Disable the checks of lossy `as` conversions (`--conversion-checks`) in a function
+///
+/// The attribute `#[kani::no_conversion_checks]` can be used on any function of the crate,
+/// and it also applies to the closures defined in that function.
+#[cfg(kani)]
+#[proc_macro_attribute]
+pub fn no_conversion_checks(attr: TokenStream, item: TokenStream) -> TokenStream {
+    assert!(attr.is_empty(), "#[kani::no_conversion_checks] does not take any arguments");
+    let mut result = TokenStream::new();
+
+    // Translate #[kani::no_conversion_checks] to #[kanitool::no_conversion_checks]
+    result.extend("#[kanitool::no_conversion_checks]".parse::<TokenStream>().unwrap());
+
+    result.extend(item);
+    result
+}
+
+#[cfg(not(kani))]
+#[proc_macro_attribute]
+pub fn nondet_alloc_failure(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    // When the config is not kani, we should leave the function alone
+    item
+}
+
+/// Let the allocations of a proof harness nondeterministically fail
+///
+/// The attribute `#[kani::nondet_alloc_failure]` enables `--nondet-alloc-failure` for a single
+/// harness. The size threshold and crate filters of the command line still apply.
+#[cfg(kani)]
+#[proc_macro_attribute]
+pub fn nondet_alloc_failure(attr: TokenStream, item: TokenStream) -> TokenStream {
+    assert!(attr.is_empty(), "#[kani::nondet_alloc_failure] does not take any arguments");
+    let mut result = TokenStream::new();
+
+    // Translate #[kani::nondet_alloc_failure] to #[kanitool::nondet_alloc_failure]
+    result.extend("#[kanitool::nondet_alloc_failure]".parse::<TokenStream>().unwrap());
+
+    result.extend(item);
+    result
+}
@@ -41,13 +41,20 @@
 /// ```
 /// the assert message will be:
 /// "The sum of {} and {} is {}", a, b, c
+/// If the assertion fails, the values of the arguments are recorded, and the driver
+/// also reports the message with their values, e.g.:
+/// The sum of 1 and 2 is 4
 #[macro_export]
 macro_rules! assert {
     ($cond:expr $(,)?) => {
         kani::assert($cond, concat!("assertion failed: ", stringify!($cond)));
     };
     ($cond:expr, $($arg:tt)+) => {{
-        kani::assert($cond, concat!(stringify!($($arg)+)));
+        let cond: bool = $cond;
+        if !cond {
+            kani::__record_fmt_args!($($arg)+);
+        }
+        kani::assert(cond, concat!(stringify!($($arg)+)));
         // Process the arguments of the assert inside an unreachable block. This
         // is to make sure errors in the arguments (e.g. an unknown variable or
         // an argument that does not implement the Display or Debug traits) are
@@ -76,27 +83,63 @@ macro_rules! assert {
 //    (see https://github.com/model-checking/kani/issues/13)
 // 3. Call kani::assert so that any instrumentation that it does (e.g. injecting
 //    reachability checks) is done for assert_eq and assert_ne
+// 4. Record the values of both operands when the assertion fails, so that the
+//    driver can report them
 #[macro_export]
 macro_rules! assert_eq {
     ($left:expr, $right:expr $(,)?) => ({
-        // Add parentheses around the operands to avoid a "comparison operators
-        // cannot be chained" error, but exclude the parentheses in the message
-        kani::assert(($left) == ($right), concat!("assertion failed: ", stringify!($left == $right)));
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                let cond = *left_val == *right_val;
+                if !cond {
+                    kani::record_assert_operand::<0, _>(left_val);
+                    kani::record_assert_operand::<1, _>(right_val);
+                }
+                // Exclude the parentheses around the operands in the message
+                kani::assert(cond, concat!("assertion failed: ", stringify!($left == $right)));
+            }
+        }
     });
     ($left:expr, $right:expr, $($arg:tt)+) => ({
-        assert!(($left) == ($right), $($arg)+);
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                let cond = *left_val == *right_val;
+                if !cond {
+                    kani::record_assert_operand::<0, _>(left_val);
+                    kani::record_assert_operand::<1, _>(right_val);
+                }
+                assert!(cond, $($arg)+);
+            }
+        }
     });
 }
 
 #[macro_export]
 macro_rules! assert_ne {
     ($left:expr, $right:expr $(,)?) => ({
-        // Add parentheses around the operands to avoid a "comparison operators
-        // cannot be chained" error, but exclude the parentheses in the message
-        kani::assert(($left) != ($right), concat!("assertion failed: ", stringify!($left != $right)));
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                let cond = *left_val != *right_val;
+                if !cond {
+                    kani::record_assert_operand::<0, _>(left_val);
+                    kani::record_assert_operand::<1, _>(right_val);
+                }
+                // Exclude the parentheses around the operands in the message
+                kani::assert(cond, concat!("assertion failed: ", stringify!($left != $right)));
+            }
+        }
     });
     ($left:expr, $right:expr, $($arg:tt)+) => ({
-        assert!(($left) != ($right), $($arg)+);
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                let cond = *left_val != *right_val;
+                if !cond {
+                    kani::record_assert_operand::<0, _>(left_val);
+                    kani::record_assert_operand::<1, _>(right_val);
+                }
+                assert!(cond, $($arg)+);
+            }
+        }
     });
 }
 
@@ -166,6 +209,7 @@ macro_rules! unreachable {
         if false {
             __kani__workaround_core_assert!(true, $fmt, $($arg)+);
         }
+        kani::__record_fmt_args!($fmt, $($arg)*);
         kani::panic(concat!("internal error: entered unreachable code: ",
         stringify!($fmt, $($arg)*)))}};
 }
@@ -194,10 +238,12 @@ macro_rules! panic {
     });
     // All other cases, e.g.:
     // `panic!("Error: {}", code);`
+    // The values of the arguments are recorded so the driver can report them.
     ($($arg:tt)+) => {{
         if false {
             __kani__workaround_core_assert!(true, $($arg)+);
         }
+        kani::__record_fmt_args!($($arg)+);
         kani::panic(stringify!($($arg)+));
     }};
 }
@@ -46,6 +46,11 @@ echo "Check GotoC output file generation"
 time "$KANI_DIR"/tests/output-files/check-output.sh
 echo ""
 
+# Check that proof harnesses run as randomized tests (random_testing feature)
+echo "Check random testing"
+time "$KANI_DIR"/tests/random-testing/check-random-testing.sh
+echo ""
+
 # Declare testing suite information (suite and mode)
 TESTS=(
     "kani kani"
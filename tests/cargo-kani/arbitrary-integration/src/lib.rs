@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This tests that `kani::any_via_arbitrary_sized` generates symbolic values with the
+//! `arbitrary::Arbitrary` implementation of a type.
+
+use arbitrary::Arbitrary;
+
+#[derive(Arbitrary, Debug, PartialEq)]
+enum Command {
+    Stop,
+    Move { x: u8, y: u8 },
+}
+
+#[kani::proof]
+fn check_derived_arbitrary() {
+    let command: Command = kani::any_via_arbitrary_sized::<Command, 4>();
+    if let Command::Move { x, y } = command {
+        kani::cover!(x == 3 && y == 7);
+    } else {
+        kani::cover!(command == Command::Stop);
+    }
+}
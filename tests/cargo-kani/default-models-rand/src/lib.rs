@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This tests that the buffers filled by the `getrandom` crate (directly, or
+//! through `rand::rngs::OsRng`) get nondeterministic bytes by default.
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+#[kani::proof]
+fn check_getrandom() {
+    let mut buf = [0u8; 4];
+    getrandom::getrandom(&mut buf).unwrap();
+    kani::cover!(buf == [1, 2, 3, 4]);
+}
+
+#[kani::proof]
+fn check_os_rng() {
+    let value = OsRng.next_u32();
+    kani::cover!(value == 42);
+}
@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This tests that Kani interprets proptest strategies symbolically: the values are within the
+//! strategy, and every choice of the strategy can be made.
+
+use kani::strategy::{one_of, vec};
+use proptest::prelude::*;
+
+#[kani::proof]
+fn check_range_strategy() {
+    let value = kani::any_via_strategy(10u32..20);
+    assert!((10..20).contains(&value));
+    kani::cover!(value == 19);
+}
+
+#[kani::proof]
+fn check_oneof_strategy() {
+    let value = kani::any_via_strategy(one_of([Just(1u8), Just(5), Just(6)]));
+    assert!(value == 1 || value == 5 || value == 6);
+    kani::cover!(value == 6);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_vec_strategy() {
+    let value = kani::any_via_strategy(vec(0u8..10, 1..=3));
+    assert!(!value.is_empty() && value.len() <= 3 && value.iter().all(|x| *x < 10));
+    kani::cover!(value.len() == 3 && value[2] == 9);
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_sampled_strategy() {
+    let value = kani::any_via_strategy_sized::<_, 16>(prop_oneof![Just(1u8), 5u8..7]);
+    assert!(value == 1 || value == 5 || value == 6);
+    kani::cover!(value == 6);
+}
@@ -0,0 +1,78 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This tests stubs that apply to all harnesses, which are given in
+//! `Cargo.toml` and by a crate-level attribute, and how harnesses can override
+//! or disable them.
+
+#![cfg_attr(kani, kani::stub(net::send, mocks::send_all))]
+
+mod net {
+    pub struct Socket;
+
+    pub trait Transport {
+        fn close(&self) -> bool;
+    }
+
+    impl Transport for Socket {
+        fn close(&self) -> bool {
+            false
+        }
+    }
+
+    pub fn connect() -> Option<Socket> {
+        None
+    }
+
+    pub fn send(_socket: &Socket, _data: &[u8]) -> usize {
+        0
+    }
+}
+
+mod mocks {
+    use crate::net::Socket;
+
+    pub fn connect() -> Option<Socket> {
+        Some(Socket)
+    }
+
+    pub fn connect_fails() -> Option<Socket> {
+        None
+    }
+
+    pub fn send(_socket: &Socket, _data: &[u8]) -> usize {
+        1
+    }
+
+    pub fn send_all(_socket: &Socket, data: &[u8]) -> usize {
+        data.len()
+    }
+
+    pub fn close(_socket: &Socket) -> bool {
+        true
+    }
+}
+
+use net::Transport;
+
+#[kani::proof]
+fn main() {
+    let socket = net::connect().unwrap();
+    assert_eq!(net::send(&socket, &[1, 2, 3]), 3);
+    assert!(socket.close());
+}
+
+#[kani::proof]
+#[kani::stub(net::connect, mocks::connect_fails)]
+fn overridden() {
+    assert!(net::connect().is_none());
+}
+
+#[kani::proof]
+#[kani::unstub(net::send)]
+#[kani::unstub(<net::Socket as net::Transport>::close)]
+fn unstubbed() {
+    let socket = net::connect().unwrap();
+    assert_eq!(net::send(&socket, &[1, 2, 3]), 0);
+    assert!(!socket.close());
+}
@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This tests that `cargo kani --enable-stubbing` verifies all the harnesses of
+//! a crate, each one with its own stubs.
+
+fn main() {}
+
+mod clock {
+    pub fn now() -> u64 {
+        1_000
+    }
+}
+
+mod mocks {
+    pub fn zero() -> u64 {
+        0
+    }
+
+    pub fn any() -> u64 {
+        kani::any()
+    }
+}
+
+#[kani::proof]
+fn real_clock() {
+    assert_eq!(clock::now(), 1_000);
+}
+
+#[kani::proof]
+#[kani::stub(clock::now, mocks::zero)]
+fn zero_clock() {
+    assert_eq!(clock::now(), 0);
+}
+
+#[kani::proof]
+#[kani::stub(clock::now, mocks::any)]
+fn any_clock() {
+    let now = clock::now();
+    kani::assume(now > 5);
+    assert!(now > 0);
+}
+
+#[kani::proof]
+#[kani::stub(clock::now, mocks::zero)]
+fn zero_clock_twice() {
+    assert_eq!(clock::now() + clock::now(), 0);
+}
@@ -0,0 +1,64 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --aliasing-checks
+//
+// Check that `--aliasing-checks` reports uses of pointers whose borrow was
+// invalidated by a conflicting borrow or access, with the lines of both, and
+// accepts uses that follow the aliasing rules. Borrow stacks that outgrow the
+// model are reported as unsupported.
+
+#[kani::proof]
+fn check_raw_invalidated_by_borrow() {
+    let mut x = 0;
+    let p = &mut x as *mut i32;
+    let r = &mut x;
+    unsafe { *p = 1 };
+    *r = 2;
+}
+
+#[kani::proof]
+fn check_two_mutable_borrows() {
+    let mut x = 0;
+    let p = &mut x as *mut i32;
+    let a = unsafe { &mut *p };
+    let b = unsafe { &mut *p };
+    *a = 1;
+    *b = 2;
+}
+
+#[kani::proof]
+fn check_write_while_shared() {
+    let mut x = 0;
+    let p = &mut x as *mut i32;
+    let s = unsafe { &*p };
+    unsafe { *p = 1 };
+    assert!(*s == 1);
+}
+
+#[kani::proof]
+fn check_valid_reborrows() {
+    let mut x = 0;
+    let p = &mut x as *mut i32;
+    let a = unsafe { &mut *p };
+    *a = 1;
+    let b = unsafe { &mut *p };
+    *b += 1;
+    unsafe { *p += 1 };
+    assert!(x == 3);
+}
+
+#[kani::proof]
+fn check_deep_borrow_stack() {
+    let x = 0;
+    let r0 = &x;
+    let r1 = &*r0;
+    let r2 = &*r1;
+    let r3 = &*r2;
+    let r4 = &*r3;
+    let r5 = &*r4;
+    let r6 = &*r5;
+    let r7 = &*r6;
+    let r8 = &*r7;
+    assert!(*r8 == 0);
+}
@@ -0,0 +1,67 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that Kani reports accesses through misaligned raw pointers, and that
+// taking the address of a place behind a misaligned pointer is accepted.
+
+#[repr(C)]
+struct Pair {
+    first: u32,
+    second: u32,
+}
+
+fn misaligned_ptr(buffer: &mut [u32; 2]) -> *mut u32 {
+    (buffer.as_mut_ptr() as *mut u8).wrapping_add(1) as *mut u32
+}
+
+#[kani::proof]
+fn check_deref_misaligned() {
+    let mut buffer = [0u32; 2];
+    let ptr = misaligned_ptr(&mut buffer);
+    let value = unsafe { *ptr };
+    assert!(value == 0);
+}
+
+#[kani::proof]
+fn check_read_misaligned() {
+    let mut buffer = [0u32; 2];
+    let ptr = misaligned_ptr(&mut buffer);
+    let value = unsafe { std::ptr::read(ptr) };
+    assert!(value == 0);
+}
+
+#[kani::proof]
+fn check_write_misaligned() {
+    let mut buffer = [0u32; 2];
+    let ptr = misaligned_ptr(&mut buffer);
+    unsafe { std::ptr::write(ptr, 1) };
+}
+
+#[kani::proof]
+fn check_method_write_misaligned() {
+    let mut buffer = [0u32; 2];
+    let ptr = misaligned_ptr(&mut buffer);
+    unsafe { ptr.write(1) };
+}
+
+#[kani::proof]
+fn check_from_raw_parts_misaligned() {
+    let mut buffer = [0u32; 2];
+    let ptr = misaligned_ptr(&mut buffer);
+    let slice = unsafe { std::slice::from_raw_parts(ptr as *const u32, 1) };
+    assert!(slice.len() == 1);
+}
+
+#[kani::proof]
+fn check_aligned_accesses() {
+    let mut buffer = [0u32; 3];
+    let pair = buffer.as_mut_ptr() as *mut Pair;
+    unsafe {
+        (*pair).second = 2;
+        assert!(std::ptr::read(&(*pair).first) == 0);
+        assert!(std::slice::from_raw_parts(buffer.as_ptr(), 3)[1] == 2);
+    }
+    let misaligned = (buffer.as_mut_ptr() as *mut u8).wrapping_add(1) as *mut Pair;
+    let field = unsafe { std::ptr::addr_of!((*misaligned).second) };
+    assert!(unsafe { field.read_unaligned() } == 0);
+}
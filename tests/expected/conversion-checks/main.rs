@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --conversion-checks truncation,sign-change,float-to-int,int-to-float
+//
+// Check that Kani reports the `as` conversions that don't preserve their value,
+// and that the checks can be disabled for a function.
+
+#[kani::proof]
+fn check_truncation() {
+    let x: u64 = kani::any();
+    let y = x as u32;
+    assert!(y as u64 <= x);
+}
+
+#[kani::proof]
+fn check_sign_change() {
+    let x: i32 = kani::any();
+    let y = x as u32;
+    assert!(y as i32 == x);
+}
+
+#[kani::proof]
+fn check_float_to_int() {
+    let x: f64 = kani::any();
+    kani::assume(!x.is_nan());
+    let y = x as i32;
+    assert!(y <= i32::MAX);
+}
+
+#[kani::proof]
+fn check_int_to_float() {
+    let x: u32 = kani::any();
+    let y = x as f32;
+    assert!(y >= 0.0);
+}
+
+#[kani::proof]
+fn check_lossless_conversions() {
+    let x: u32 = kani::any();
+    kani::assume(x < 1 << 24);
+    assert!(x as f32 as u32 == x);
+    assert!(x as u64 as u32 == x);
+    assert!(x as i32 as u32 == x);
+    let f = x as f64 + 0.5;
+    assert!(f as u32 == x);
+    let s: i8 = kani::any();
+    assert!(s as i64 as i8 == s);
+}
+
+#[kani::no_conversion_checks]
+fn low_bits(x: u64) -> u32 {
+    let mask = |v: u64| v as u16 as u32;
+    mask(x) | (x as u32 & 0xFFFF_0000)
+}
+
+#[kani::proof]
+fn check_disabled_conversion_checks() {
+    let x: u64 = kani::any();
+    assert!(low_bits(x) as u64 == x & 0xFFFF_FFFF);
+}
@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that Kani reports dereferences of pointers to locals whose storage
+// is dead, and that a local gets a fresh object every time its storage
+// becomes live again.
+
+fn escape_local() -> *const u32 {
+    let x: u32 = kani::any();
+    &x as *const u32
+}
+
+#[kani::proof]
+fn check_escaped_local() {
+    let ptr = escape_local();
+    let _v = unsafe { *ptr };
+}
+
+#[kani::proof]
+fn check_out_of_scope() {
+    let ptr: *const u8;
+    {
+        let y = 10u8;
+        ptr = &y;
+    }
+    let _v = unsafe { *ptr };
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_loop_local() {
+    let mut sum = 0u32;
+    for i in 0..3u32 {
+        let x = i;
+        let ptr = &x as *const u32;
+        sum += unsafe { *ptr };
+    }
+    assert!(sum == 3);
+}
@@ -0,0 +1,62 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the messages of failed assertions and panics are reported with
+//! the values that their arguments have in the counterexample.
+
+#[kani::proof]
+fn check_panic_args() {
+    let v = [1, 2, 3];
+    let i: usize = kani::any();
+    kani::assume(i == 5);
+    let name = "v";
+    if i >= v.len() {
+        panic!("index {} out of range for {} of length {}", i, name, v.len());
+    }
+}
+
+#[kani::proof]
+fn check_assert_args() {
+    let x: u8 = kani::any();
+    kani::assume(x == 255);
+    let r = &x;
+    assert!(x < 100, "{} ({:#x}) is too large for {:?}", r, x, 'k');
+}
+
+#[kani::proof]
+fn check_slice_args() {
+    let bytes: [u8; 3] = kani::any();
+    kani::assume(bytes == [1, 2, 3]);
+    let slice = &bytes[..];
+    let s = std::str::from_utf8(b"abc").unwrap();
+    assert!(bytes[0] == 0, "{:?} in {:?} ({})", slice, s, s);
+}
+
+#[kani::proof]
+fn check_unreachable_args() {
+    let x: i64 = kani::any();
+    kani::assume(x == -3);
+    if x < 0 {
+        unreachable!("negative value {}", x);
+    }
+}
+
+#[kani::proof]
+fn check_unrecorded_args() {
+    let x: u8 = kani::any();
+    let values = vec![x];
+    assert!(values[0] == 0, "{} in {:?}", x, values);
+}
+
+#[kani::proof]
+fn check_assert_eq() {
+    let x: i32 = kani::any();
+    kani::assume(x == -7);
+    assert_eq!(x, 7);
+}
+
+#[kani::proof]
+fn check_assert_ne() {
+    let c = 'a';
+    assert_ne!(c, 'a', "{} is not allowed", c);
+}
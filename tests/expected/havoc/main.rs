@@ -0,0 +1,53 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::havoc`, `kani::havoc_slice` and `kani::havoc_raw` make values
+//! nondeterministic, and that the values of types with a validity invariant stay valid.
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Read,
+    Write,
+}
+
+struct Config {
+    mode: Mode,
+    enabled: bool,
+    retries: u8,
+}
+
+#[kani::proof]
+fn check_havoc_struct() {
+    let mut config = Config { mode: Mode::Read, enabled: false, retries: 3 };
+    kani::havoc(&mut config);
+    assert!(config.mode == Mode::Read || config.mode == Mode::Write);
+    assert!(config.enabled as u8 <= 1);
+    kani::cover!(config.mode == Mode::Write && config.enabled);
+    assert!(config.retries == 3, "havoc may change the value");
+}
+
+#[kani::proof]
+fn check_havoc_slice() {
+    let mut buffer = [0u32; 4];
+    kani::havoc_slice(&mut buffer[1..3]);
+    assert_eq!(buffer[0], 0);
+    assert_eq!(buffer[3], 0);
+    assert!(buffer[1] == 0, "havoc_slice may change the elements");
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_havoc_slice_valid() {
+    let mut flags = [Some(false); 3];
+    kani::havoc_slice(&mut flags);
+    kani::cover!(flags[2].is_none());
+    assert!(flags.iter().all(|flag| matches!(flag, None | Some(true) | Some(false))));
+}
+
+#[kani::proof]
+fn check_havoc_raw() {
+    let mut value: u64 = 0;
+    unsafe { kani::havoc_raw(&mut value as *mut u64 as *mut u8, 4) };
+    assert!(value <= u32::MAX as u64 || cfg!(target_endian = "big"));
+    kani::cover!(value == 42);
+}
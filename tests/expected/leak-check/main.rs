@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: --enable-unstable --leak-check
+
+//! Check that `--leak-check` reports the memory leaked by a harness at its allocation site in
+//! user code.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[kani::proof]
+fn check_no_leak() {
+    let b = Box::new(5u32);
+    let v: Vec<u32> = vec![1, 2, 3];
+    assert_eq!(*b + v[0], 6);
+}
+
+#[kani::proof]
+fn check_box_leak() {
+    let b = Box::new(5u32);
+    let r = Box::leak(b);
+    assert_eq!(*r, 5);
+}
+
+#[kani::proof]
+fn check_forget() {
+    let v: Vec<u8> = Vec::with_capacity(4);
+    std::mem::forget(v);
+}
+
+struct Node {
+    next: RefCell<Option<Rc<Node>>>,
+}
+
+#[kani::proof]
+fn check_rc_cycle() {
+    let a = Rc::new(Node { next: RefCell::new(None) });
+    let b = Rc::new(Node { next: RefCell::new(Some(a.clone())) });
+    *a.next.borrow_mut() = Some(b);
+}
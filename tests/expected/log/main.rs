@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the messages logged with `kani::log!` are reported in order, with
+//! the values of their arguments, for failed checks and satisfied covers.
+
+#[kani::proof]
+fn check_log_failure() {
+    let x: u8 = kani::any();
+    kani::assume(x == 86);
+    let y = x.wrapping_mul(3);
+    kani::log!("x = {}, y = {}", x, y);
+    for i in 0..2u8 {
+        kani::log!("iteration {}", i);
+    }
+    kani::log!("done");
+    assert!(y >= x);
+}
+
+#[kani::proof]
+fn check_log_cover() {
+    let c: char = kani::any();
+    kani::assume(c == 'z');
+    kani::log!("c = {:?}", c);
+    kani::cover!(c == 'z');
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+//
+//! This tests that the clocks return nondeterministic readings when a harness
+//! uses the models of `kani::models::time`.
+
+use std::time::{Duration, Instant};
+
+#[kani::proof]
+#[kani::stub(std::time::Instant::now, kani::models::time::instant_now)]
+#[kani::stub(std::time::Instant::elapsed, kani::models::time::instant_elapsed)]
+fn check_timeout() {
+    let start = Instant::now();
+    kani::cover!(start.elapsed() > Duration::from_secs(5), "the timeout may expire");
+    kani::cover!(start.elapsed() == Duration::ZERO, "no time may pass");
+}
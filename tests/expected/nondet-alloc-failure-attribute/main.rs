@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: --enable-unstable --nondet-alloc-failure-crate main
+
+//! Check that `#[kani::nondet_alloc_failure]` lets the allocations performed by the standard
+//! library on behalf of the selected crates fail, only in the annotated harnesses.
+
+#[kani::proof]
+fn check_without_attribute() {
+    let mut v: Vec<u8> = Vec::new();
+    assert!(v.try_reserve_exact(8).is_ok(), "reservation failed without attribute");
+}
+
+#[kani::proof]
+#[kani::nondet_alloc_failure]
+fn check_with_attribute() {
+    let mut v: Vec<u8> = Vec::new();
+    assert!(v.try_reserve_exact(8).is_ok(), "reservation failed with attribute");
+}
+
+#[kani::proof]
+#[kani::nondet_alloc_failure]
+fn check_push() {
+    let mut v: Vec<u8> = Vec::new();
+    v.push(1);
+    assert_eq!(v.len(), 1);
+}
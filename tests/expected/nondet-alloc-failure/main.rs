@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: --enable-unstable --nondet-alloc-failure --nondet-alloc-failure-min-size 16
+
+//! Check that allocations of at least 16 bytes may fail with `--nondet-alloc-failure`.
+
+#[kani::proof]
+fn check_small_reserve() {
+    let mut v: Vec<u8> = Vec::new();
+    assert!(v.try_reserve_exact(8).is_ok(), "small reservation failed");
+}
+
+#[kani::proof]
+fn check_large_reserve() {
+    let mut v: Vec<u8> = Vec::new();
+    assert!(v.try_reserve_exact(32).is_ok(), "large reservation failed");
+}
+
+#[kani::proof]
+fn check_reserve_error_handled() {
+    let mut v: Vec<u8> = Vec::new();
+    match v.try_reserve_exact(32) {
+        Ok(()) => assert!(v.capacity() >= 32),
+        Err(_) => assert_eq!(v.capacity(), 0),
+    }
+}
+
+#[kani::proof]
+fn check_box() {
+    let b = Box::new([0u8; 32]);
+    assert_eq!(b[0], 0);
+}
@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Test that `std::process::exit` ends the execution path successfully, and
+//! that harnesses can check the exit codes with `kani::expect_exit_code`.
+
+use std::process;
+
+/// A command line tool that exits with code 2 on invalid arguments.
+fn run(arg: u8) -> u8 {
+    if arg > 100 {
+        eprintln!("invalid argument {}", arg);
+        process::exit(2);
+    }
+    if arg == 0 {
+        process::exit(0);
+    }
+    arg * 2
+}
+
+#[kani::proof]
+fn check_exit_terminates() {
+    let arg: u8 = kani::any();
+    let result = run(arg);
+    assert!(arg != 0 && arg <= 100, "only reachable without exit");
+    assert_eq!(result / 2, arg);
+}
+
+#[kani::proof]
+fn check_expected_exit_codes() {
+    kani::expect_exit_code(0);
+    kani::expect_exit_code(2);
+    run(kani::any());
+}
+
+#[kani::proof]
+fn check_unexpected_exit_code() {
+    kani::expect_exit_code(0);
+    run(kani::any());
+}
+
+#[kani::proof]
+fn check_unreachable_exit_code() {
+    kani::expect_exit_code(0);
+    kani::expect_exit_code(3);
+    let arg: u8 = kani::any();
+    kani::assume(arg <= 100);
+    run(arg);
+}
@@ -0,0 +1,89 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that Kani reports the violations of the safety preconditions of
+// `unsafe fn`s of the standard library at their call site.
+
+use std::num::NonZeroU8;
+use std::ptr::NonNull;
+
+#[kani::proof]
+fn check_get_unchecked() {
+    let array = [1u8, 2, 3];
+    let index: usize = kani::any();
+    let value = unsafe { *array.get_unchecked(index) };
+    assert!(value <= 3);
+}
+
+#[kani::proof]
+fn check_get_unchecked_range() {
+    let array = [1u8, 2, 3];
+    let end: usize = kani::any();
+    kani::assume(end < 10);
+    let slice = unsafe { array.get_unchecked(1..end) };
+    assert!(slice.len() < 10);
+}
+
+#[kani::proof]
+fn check_from_raw_parts() {
+    let ptr: *const u32 = std::ptr::null();
+    let slice = unsafe { std::slice::from_raw_parts(ptr, 0) };
+    assert!(slice.is_empty());
+}
+
+#[kani::proof]
+fn check_get_unchecked_range_inclusive() {
+    let array = [1u8, 2, 3];
+    let end: usize = kani::any();
+    kani::assume(end < 10);
+    let slice = unsafe { array.get_unchecked(..=end) };
+    assert!(slice.len() <= 10);
+}
+
+#[kani::proof]
+fn check_get_unchecked_range_from() {
+    let array = [1u8, 2, 3];
+    let start: usize = kani::any();
+    kani::assume(start < 10);
+    let slice = unsafe { array.get_unchecked(start..) };
+    assert!(slice.len() <= 3);
+}
+
+#[kani::proof]
+fn check_from_utf8_unchecked() {
+    let bytes = [0xC3u8, kani::any()];
+    let s = unsafe { std::str::from_utf8_unchecked(&bytes) };
+    assert!(s.len() == 2);
+}
+
+#[kani::proof]
+fn check_non_null_new_unchecked() {
+    let mut byte = 0u8;
+    let ptr: *mut u8 = if kani::any() { std::ptr::null_mut() } else { &mut byte };
+    let non_null = unsafe { NonNull::new_unchecked(ptr) };
+    assert!(!non_null.as_ptr().is_null());
+}
+
+#[kani::proof]
+fn check_non_zero_new_unchecked() {
+    let value: u8 = kani::any();
+    let non_zero = unsafe { NonZeroU8::new_unchecked(value) };
+    assert!(non_zero.get() == value);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_valid_calls() {
+    let array = [1u8, 2, 3];
+    let index: usize = kani::any();
+    kani::assume(index < array.len());
+    assert!(unsafe { *array.get_unchecked(index) } == index as u8 + 1);
+    assert!(unsafe { array.get_unchecked(..index) }.len() == index);
+    assert!(unsafe { array.get_unchecked(index..=2) }.len() == 3 - index);
+    let slice = unsafe { std::slice::from_raw_parts(array.as_ptr(), 2) };
+    assert!(slice[1] == 2);
+    let s = unsafe { std::str::from_utf8_unchecked(&[0xC3, 0xA9]) };
+    assert!(s == "é");
+    let c = unsafe { char::from_u32_unchecked(0x41) };
+    assert!(c == 'A');
+}
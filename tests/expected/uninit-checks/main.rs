@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --uninit-checks
+//
+// Check that `--uninit-checks` detects reads of uninitialized memory, and
+// doesn't report reads of memory that was initialized.
+
+use std::alloc::{alloc, dealloc, Layout};
+use std::mem::MaybeUninit;
+
+#[kani::proof]
+fn check_assume_init() {
+    let x: u32 = unsafe { MaybeUninit::uninit().assume_init() };
+    assert!(x == x);
+}
+
+#[kani::proof]
+fn check_assume_init_after_write() {
+    let mut x = MaybeUninit::<u32>::uninit();
+    x.write(10);
+    let x = unsafe { x.assume_init() };
+    assert!(x == 10);
+}
+
+#[kani::proof]
+fn check_uninit_alloc() {
+    let layout = Layout::new::<u16>();
+    unsafe {
+        let ptr = alloc(layout) as *mut u16;
+        let partial = ptr as *mut u8;
+        *partial = 1;
+        let value = *ptr;
+        assert!(value != 0);
+        dealloc(ptr as *mut u8, layout);
+    }
+}
+
+#[kani::proof]
+fn check_init_alloc() {
+    let layout = Layout::new::<u16>();
+    unsafe {
+        let ptr = alloc(layout) as *mut u16;
+        ptr.write(0x100);
+        let value = *ptr;
+        assert!(value == 0x100);
+        dealloc(ptr as *mut u8, layout);
+    }
+}
+
+#[kani::proof]
+fn check_padding_not_checked() {
+    #[derive(Clone, Copy)]
+    struct Padded {
+        a: u8,
+        b: u32,
+    }
+    let p = Padded { a: 1, b: 2 };
+    let q = p;
+    assert!(q.a == 1 && q.b == 2);
+}
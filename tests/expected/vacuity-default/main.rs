@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani warns by default about harnesses whose end is unreachable, or none of whose
+//! assertions is reachable, without `--vacuity-checks`.
+
+#[kani::proof]
+fn check_contradictory_assumptions() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    kani::assume(x < 5);
+    assert!(x != 7);
+}
+
+#[kani::proof]
+fn check_unreachable_assertion() {
+    let x: u8 = kani::any();
+    kani::assume(x < 100);
+    if x > 200 {
+        assert!(x != 0);
+    }
+    assert!(x < 100);
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: --enable-unstable --vacuity-checks
+
+//! Check that with `--vacuity-checks`, Kani also warns about the assumptions that make the
+//! remaining path infeasible, and about the assertions of user code that are unreachable.
+
+#[kani::proof]
+fn check_contradictory_assumptions() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    kani::assume(x < 5);
+    assert!(x != 7);
+}
+
+#[kani::proof]
+fn check_unreachable_assertion() {
+    let x: u8 = kani::any();
+    kani::assume(x < 100);
+    if x > 200 {
+        assert!(x != 0);
+    }
+    assert!(x < 100);
+}
+
+#[kani::proof]
+fn check_not_vacuous() {
+    let x: u8 = kani::any();
+    kani::assume(x < 100);
+    assert!(x < 100);
+}
@@ -0,0 +1,68 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that Kani reports values that violate the validity invariant of their
+// type when they are produced by `transmute`, `ptr::read` or a read through a
+// raw pointer, and that valid values are accepted.
+
+use std::num::NonZeroU32;
+
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+enum Direction {
+    Left = 1,
+    Right = 2,
+}
+
+#[kani::proof]
+fn check_transmute_bool() {
+    let byte: u8 = kani::any();
+    let b: bool = unsafe { std::mem::transmute(byte) };
+    assert!(b || !b);
+}
+
+#[kani::proof]
+fn check_transmute_valid_bool() {
+    let byte: u8 = kani::any();
+    kani::assume(byte <= 1);
+    let b: bool = unsafe { std::mem::transmute(byte) };
+    assert!(b == (byte == 1));
+}
+
+#[kani::proof]
+fn check_transmute_char() {
+    let c: char = unsafe { std::mem::transmute(0xD800u32) };
+    assert!(c != 'a');
+}
+
+#[kani::proof]
+fn check_transmute_null_ref() {
+    let r: &u8 = unsafe { std::mem::transmute(0usize) };
+    assert!(*r == 0);
+}
+
+#[kani::proof]
+fn check_read_enum() {
+    let byte: u8 = 3;
+    let d: Direction = unsafe { std::ptr::read(&byte as *const u8 as *const Direction) };
+    assert!(matches!(d, Direction::Left | Direction::Right));
+}
+
+#[kani::proof]
+fn check_deref_non_zero() {
+    let value: u32 = 0;
+    let ptr = &value as *const u32 as *const NonZeroU32;
+    let nz = unsafe { *ptr };
+    assert!(nz.get() == 0);
+}
+
+#[kani::proof]
+fn check_deref_valid_values() {
+    let value: u32 = kani::any();
+    kani::assume(value != 0);
+    let nz = unsafe { *(&value as *const u32 as *const NonZeroU32) };
+    assert!(nz.get() == value);
+    let byte: u8 = 2;
+    let d = unsafe { *(&byte as *const u8 as *const Direction) };
+    assert!(matches!(d, Direction::Right));
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This test makes sure the arguments of the messages of Kani's overridden
+//! assert macros are only evaluated when the assertion fails (like with the
+//! standard library macros), and that they are borrowed, not moved.
+
+#[kani::proof]
+fn check_args_on_success() {
+    let mut count = 0;
+    let mut next = || {
+        count += 1;
+        count
+    };
+    let s = String::from("not moved");
+    assert!(true, "{} {}", next(), s);
+    assert_eq!(1, 1, "{} {}", next(), s);
+    assert_ne!(1, 2, "{} {}", next(), s);
+    assert_eq!(count, 0);
+    assert_eq!(s.len(), 9);
+}
@@ -0,0 +1,65 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+//
+//! This tests the models of the environment in `kani::models::env`.
+
+use std::env::{Args, ArgsOs, VarError};
+
+#[kani::proof]
+#[kani::stub(std::env::var, kani::models::env::var)]
+fn check_seeded_var() {
+    kani::models::env::seed_var("LOG_LEVEL", "debug");
+    kani::models::env::seed_missing_var("HOME");
+    assert_eq!(std::env::var("LOG_LEVEL").as_deref(), Ok("debug"));
+    assert_eq!(std::env::var("HOME"), Err(VarError::NotPresent));
+}
+
+#[kani::proof]
+#[kani::stub(std::env::var_os, kani::models::env::var_os)]
+fn check_unknown_var() {
+    let first = std::env::var_os("PORT");
+    // Later reads see the same value.
+    assert_eq!(std::env::var_os("PORT"), first);
+}
+
+#[kani::proof]
+#[kani::stub(std::env::var, kani::models::env::var)]
+#[kani::stub(std::env::set_var, kani::models::env::set_var)]
+#[kani::stub(std::env::remove_var, kani::models::env::remove_var)]
+fn check_set_remove_var() {
+    std::env::set_var("PORT", "8080");
+    assert_eq!(std::env::var("PORT").as_deref(), Ok("8080"));
+    std::env::remove_var("PORT");
+    assert_eq!(std::env::var("PORT"), Err(VarError::NotPresent));
+}
+
+#[kani::proof]
+#[kani::stub(<Args as Iterator>::next, kani::models::env::args_next)]
+#[kani::stub(<Args as Iterator>::size_hint, kani::models::env::args_size_hint)]
+fn check_seeded_args() {
+    kani::models::env::seed_args(["prog", "--verbose"]);
+    let args: Vec<String> = std::env::args().collect();
+    assert_eq!(args, ["prog", "--verbose"]);
+}
+
+#[kani::proof]
+#[kani::stub(<ArgsOs as Iterator>::next, kani::models::env::args_os_next)]
+#[kani::stub(<ArgsOs as ExactSizeIterator>::len, kani::models::env::args_os_len)]
+#[kani::stub(<ArgsOs as DoubleEndedIterator>::next_back, kani::models::env::args_os_next_back)]
+fn check_unknown_args() {
+    let mut args = std::env::args_os();
+    let len = args.len();
+    assert!(len >= 1 && len <= kani::models::env::MAX_ARGS);
+    // Both ends consume the same arguments.
+    let first = args.next();
+    assert!(first.is_some());
+    assert_eq!(args.len(), len - 1);
+    if len == 1 {
+        assert_eq!(args.next_back(), None);
+    } else {
+        assert!(args.next_back().is_some());
+        assert_eq!(args.len(), len - 2);
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+//
+//! This tests the in-memory file system of `kani::models::fs`.
+
+use std::fs::File;
+use std::io::{ErrorKind, Read, Write};
+
+#[kani::proof]
+#[kani::stub(std::fs::read_to_string, kani::models::fs::read_to_string)]
+fn check_seeded_file() {
+    kani::models::fs::enable_failures(false);
+    kani::models::fs::seed_file("config.toml", "debug = true");
+    assert_eq!(std::fs::read_to_string("config.toml").unwrap(), "debug = true");
+}
+
+#[kani::proof]
+#[kani::stub(std::fs::read, kani::models::fs::read)]
+fn check_missing_file() {
+    kani::models::fs::seed_missing_file("config.toml");
+    assert_eq!(std::fs::read("config.toml").unwrap_err().kind(), ErrorKind::NotFound);
+}
+
+#[kani::proof]
+#[kani::stub(std::fs::read, kani::models::fs::read)]
+fn check_unknown_file() {
+    kani::models::fs::enable_failures(false);
+    let first = std::fs::read("data.bin");
+    assert!(first.as_ref().map_or(true, |data| data.len() <= kani::models::fs::MAX_FILE_LEN));
+    // Later reads see the same file.
+    let second = std::fs::read("data.bin");
+    assert_eq!(first.ok(), second.ok());
+}
+
+#[kani::proof]
+#[kani::stub(std::fs::read, kani::models::fs::read)]
+#[kani::stub(std::fs::write, kani::models::fs::write)]
+#[kani::stub(std::fs::remove_file, kani::models::fs::remove_file)]
+fn check_write_remove() {
+    if std::fs::write("out.txt", [1, 2, 3]).is_ok() {
+        if let Ok(data) = std::fs::read("out.txt") {
+            assert_eq!(data, [1, 2, 3]);
+        }
+        if std::fs::remove_file("out.txt").is_ok() {
+            assert_eq!(std::fs::read("out.txt").unwrap_err().kind(), ErrorKind::NotFound);
+        }
+    }
+}
+
+#[kani::proof]
+#[kani::stub(File::create, kani::models::fs::file_create)]
+#[kani::stub(File::open, kani::models::fs::file_open)]
+#[kani::stub(<File as Read>::read, kani::models::fs::file_read)]
+#[kani::stub(<File as Read>::read_to_string, kani::models::fs::file_read_to_string)]
+#[kani::stub(<File as Write>::write, kani::models::fs::file_write)]
+#[kani::stub(<File as Write>::flush, kani::models::fs::file_flush)]
+fn check_file() {
+    kani::models::fs::enable_failures(false);
+    let mut file = File::create("log.txt").unwrap();
+    file.write_all(b"hello").unwrap();
+    file.flush().unwrap();
+    // A file created for writing cannot be read.
+    assert!(file.read(&mut [0; 1]).is_err());
+    let mut contents = String::new();
+    File::open("log.txt").unwrap().read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, "hello");
+}
+
+#[kani::proof]
+#[kani::stub(File::open, kani::models::fs::file_open)]
+#[kani::stub(<File as Read>::read, kani::models::fs::file_read)]
+fn check_short_reads() {
+    kani::models::fs::enable_failures(false);
+    kani::models::fs::seed_file("data.bin", [1, 2, 3, 4]);
+    let mut file = File::open("data.bin").unwrap();
+    let mut buf = [0; 4];
+    file.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [1, 2, 3, 4]);
+    assert_eq!(file.read(&mut buf).unwrap(), 0);
+}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+// kani-verify-fail
+//
+//! This checks that the models of `kani::models::fs` fail nondeterministically
+//! by default, even for seeded files.
+
+#[kani::proof]
+#[kani::stub(std::fs::read, kani::models::fs::read)]
+fn check_read_may_fail() {
+    kani::models::fs::seed_file("config.toml", "debug = true");
+    assert!(std::fs::read("config.toml").is_ok());
+}
@@ -0,0 +1,55 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+//
+//! This tests the hashing models in `kani::models::hash_map` with maps and
+//! sets.
+
+use std::collections::{HashMap, HashSet};
+
+#[kani::proof]
+#[kani::stub(
+    std::collections::hash_map::RandomState::new,
+    kani::models::hash_map::random_state_new
+)]
+#[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::write, kani::models::hash_map::write)]
+#[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::write_str, kani::models::hash_map::write_str)]
+#[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::finish, kani::models::hash_map::finish)]
+fn check_set() {
+    let mut set: HashSet<u16> = HashSet::new();
+    let a: u16 = kani::any();
+    let b: u16 = kani::any();
+    let c: u16 = kani::any();
+    kani::assume(a != b && a != c && b != c);
+
+    assert!(set.insert(a));
+    assert!(set.contains(&a));
+    assert!(!set.contains(&b));
+    assert!(set.remove(&a));
+    assert!(!set.contains(&a));
+    assert!(set.insert(b));
+    assert!(set.insert(c));
+    assert!(!set.insert(c));
+    assert_eq!(set.len(), 2);
+}
+
+#[kani::proof]
+#[kani::stub(
+    std::collections::hash_map::RandomState::new,
+    kani::models::hash_map::random_state_new
+)]
+#[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::write, kani::models::hash_map::write)]
+#[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::write_str, kani::models::hash_map::write_str)]
+#[kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::finish, kani::models::hash_map::finish)]
+fn check_map() {
+    let mut map = HashMap::new();
+    map.insert("one", 1);
+    map.insert("two", 2);
+    assert_eq!(map.insert("one", 10), Some(1));
+    assert_eq!(map.get("one"), Some(&10));
+    assert_eq!(map.get("two"), Some(&2));
+    assert_eq!(map.get("three"), None);
+    assert_eq!(map.remove("two"), Some(2));
+    assert_eq!(map.len(), 1);
+}
@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+//
+//! This tests the models of the clocks and of the hashing of `HashMap` (see
+//! `kani::models::time` and `kani::models::hash_map`), which this crate
+//! enables for all of its harnesses.
+
+#![kani::stub(std::time::Instant::now, kani::models::time::instant_now)]
+#![kani::stub(std::time::Instant::elapsed, kani::models::time::instant_elapsed)]
+#![kani::stub(std::time::SystemTime::now, kani::models::time::system_time_now)]
+#![kani::stub(std::time::SystemTime::elapsed, kani::models::time::system_time_elapsed)]
+#![kani::stub(std::collections::hash_map::RandomState::new, kani::models::hash_map::random_state_new)]
+#![kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::write, kani::models::hash_map::write)]
+#![kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::write_str, kani::models::hash_map::write_str)]
+#![kani::stub(<std::collections::hash_map::DefaultHasher as std::hash::Hasher>::finish, kani::models::hash_map::finish)]
+
+use std::collections::HashMap;
+use std::time::{Instant, SystemTime};
+
+#[kani::proof]
+fn check_instant() {
+    let first = Instant::now();
+    let second = Instant::now();
+    assert!(second >= first);
+    assert!(first.elapsed() >= second - first);
+}
+
+#[kani::proof]
+fn check_system_time() {
+    let now = SystemTime::now();
+    assert!(now.duration_since(SystemTime::UNIX_EPOCH).is_ok());
+    assert!(now.elapsed().is_ok());
+}
+
+#[kani::proof]
+fn check_hash_map() {
+    let mut map = HashMap::new();
+    map.insert(1, 'a');
+    map.insert(2, 'b');
+    assert_eq!(map.insert(1, 'c'), Some('a'));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&2), Some(&'b'));
+}
@@ -0,0 +1,73 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+//
+//! This tests the models of `Vec` in `kani::models::vec`, with some of the
+//! examples of the `Vec` documentation.
+
+#[kani::proof]
+#[kani::stub(Vec::push, kani::models::vec::push)]
+fn check_push() {
+    let mut vec: Vec<u32> = Vec::new();
+    assert_eq!(vec.capacity(), 0);
+    let elem = kani::any();
+    vec.push(elem);
+    assert_eq!(vec.capacity(), kani::models::vec::CAPACITY);
+    vec.push(2);
+    assert_eq!(vec.pop(), Some(2));
+    assert_eq!(vec, [elem]);
+    assert_eq!(vec.capacity(), kani::models::vec::CAPACITY);
+}
+
+#[kani::proof]
+#[kani::stub(Vec::push, kani::models::vec::push)]
+fn check_push_with_capacity() {
+    let mut vec = Vec::with_capacity(10);
+    for i in 0..10 {
+        vec.push(i);
+    }
+    assert_eq!(vec.capacity(), 10);
+    vec.push(10);
+    assert_eq!(vec.capacity(), kani::models::vec::CAPACITY);
+    vec.insert(1, 42);
+    assert_eq!(vec.len(), 12);
+    assert_eq!(vec.remove(1), 42);
+    assert_eq!(vec.split_off(8), [8, 9, 10]);
+    assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[kani::proof]
+#[kani::stub(Vec::push, kani::models::vec::push)]
+#[kani::stub(Vec::shrink_to_fit, kani::models::vec::shrink_to_fit)]
+fn check_shrink_to_fit() {
+    let mut vec = Vec::new();
+    vec.push(1);
+    vec.extend_from_slice(&[2, 3]);
+    vec.shrink_to_fit();
+    assert!(vec.capacity() >= 3);
+    assert_eq!(vec, [1, 2, 3]);
+}
+
+static mut DROPPED: u32 = 0;
+
+struct Droppable;
+
+impl Drop for Droppable {
+    fn drop(&mut self) {
+        unsafe { DROPPED += 1 };
+    }
+}
+
+#[kani::proof]
+#[kani::stub(Vec::push, kani::models::vec::push)]
+fn check_drop() {
+    {
+        let mut vec = Vec::new();
+        vec.push(Droppable);
+        vec.push(Droppable);
+        vec.truncate(1);
+        assert_eq!(unsafe { DROPPED }, 1);
+    }
+    assert_eq!(unsafe { DROPPED }, 2);
+}
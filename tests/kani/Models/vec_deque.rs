@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+//
+//! This tests the models of `VecDeque` in `kani::models::vec_deque`.
+
+use std::collections::VecDeque;
+
+#[kani::proof]
+#[kani::stub(VecDeque::push_back, kani::models::vec_deque::push_back)]
+#[kani::stub(VecDeque::push_front, kani::models::vec_deque::push_front)]
+fn check_push() {
+    let mut deque: VecDeque<u8> = VecDeque::new();
+    let front = kani::any();
+    deque.push_back(1);
+    assert!(deque.capacity() >= kani::models::vec_deque::CAPACITY);
+    deque.push_back(2);
+    deque.push_front(front);
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.pop_front(), Some(front));
+    assert_eq!(deque.pop_back(), Some(2));
+    assert_eq!(deque, [1]);
+}
+
+#[kani::proof]
+#[kani::stub(VecDeque::push_front, kani::models::vec_deque::push_front)]
+#[kani::stub(VecDeque::shrink_to_fit, kani::models::vec_deque::shrink_to_fit)]
+fn check_shrink_to_fit() {
+    let mut deque = VecDeque::with_capacity(4);
+    for i in 0..4 {
+        deque.push_front(i);
+    }
+    deque.shrink_to_fit();
+    assert!(deque.capacity() >= 4);
+    assert_eq!(deque, [3, 2, 1, 0]);
+}
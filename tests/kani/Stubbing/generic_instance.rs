@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness main --enable-unstable --enable-stubbing
+//
+//! This tests stubbing some instances of a generic function/method, selected by
+//! the generic arguments in the path.
+
+fn size_of_val<T>(_x: &T) -> usize {
+    std::mem::size_of::<T>()
+}
+
+fn zero(_x: &u8) -> usize {
+    0
+}
+
+fn len_stub(_v: &Vec<u8>) -> usize {
+    100
+}
+
+#[kani::proof]
+#[kani::stub(size_of_val::<u8>, zero)]
+#[kani::stub(Vec::<u8>::len, len_stub)]
+fn main() {
+    assert_eq!(size_of_val(&1u8), 0);
+    assert_eq!(size_of_val(&1u32), 4);
+    assert_eq!(vec![1u8, 2, 3].len(), 100);
+    assert_eq!(vec![1u16, 2, 3].len(), 3);
+}
@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --enable-unstable --enable-stubbing
+//
+//! This tests that harnesses with different stubs can be verified together,
+//! i.e., without selecting one of them with `--harness`.
+
+fn magic_number() -> u32 {
+    42
+}
+
+fn one() -> u32 {
+    1
+}
+
+fn two() -> u32 {
+    2
+}
+
+#[kani::proof]
+fn unstubbed() {
+    assert_eq!(magic_number(), 42);
+}
+
+#[kani::proof]
+#[kani::stub(magic_number, one)]
+fn stub_one() {
+    assert_eq!(magic_number(), 1);
+}
+
+#[kani::proof]
+#[kani::stub(magic_number, two)]
+fn stub_two() {
+    assert_eq!(magic_number(), 2);
+}
+
+#[kani::proof]
+#[kani::stub(magic_number, two)]
+fn stub_two_again() {
+    assert_eq!(magic_number() + magic_number(), 4);
+}
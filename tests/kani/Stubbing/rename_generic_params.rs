@@ -3,9 +3,9 @@
 //
 // kani-flags: --enable-unstable --enable-stubbing --harness main
 //
-// We currently require a stub and the original/function method to have the same
-// names for generic parameters; instead, we should allow for renaming.
-// See <https://github.com/model-checking/kani/issues/1953> for more information.
+//! This tests that a stub may name its generic parameters differently than the
+//! original function/method.
+//! See <https://github.com/model-checking/kani/issues/1953> for more information.
 
 fn foo<T>(_x: T) -> bool {
     false
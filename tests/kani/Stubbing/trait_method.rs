@@ -0,0 +1,50 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness main --enable-unstable --enable-stubbing
+//
+//! This tests stubbing trait methods: the implementation of a trait for a
+//! type, using a path with a qualified self type, and a default method of a
+//! trait.
+
+trait Clock {
+    fn now(&self) -> u64;
+
+    fn elapsed(&self, start: u64) -> u64 {
+        self.now() - start
+    }
+}
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        1000
+    }
+}
+
+struct OtherClock;
+
+impl Clock for OtherClock {
+    fn now(&self) -> u64 {
+        2000
+    }
+}
+
+fn fixed_now(_clock: &SystemClock) -> u64 {
+    42
+}
+
+fn no_elapsed<C: Clock>(_clock: &C, _start: u64) -> u64 {
+    0
+}
+
+#[kani::proof]
+#[kani::stub(<SystemClock as Clock>::now, fixed_now)]
+#[kani::stub(Clock::elapsed, no_elapsed)]
+fn main() {
+    assert_eq!(SystemClock.now(), 42);
+    assert_eq!(OtherClock.now(), 2000);
+    assert_eq!(SystemClock.elapsed(10), 0);
+    assert_eq!(OtherClock.elapsed(10), 0);
+}
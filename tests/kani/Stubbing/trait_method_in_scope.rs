@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness main --enable-unstable --enable-stubbing
+//
+//! This tests that a path like `Type::method` only considers the traits in
+//! scope: `Reader::read` is not ambiguous, since the other `read` method is
+//! provided by a trait that isn't imported in this module.
+
+mod traits {
+    pub trait Reader {
+        fn read(&self) -> u32;
+    }
+
+    pub trait Loader {
+        fn read(&self) -> u32;
+    }
+}
+
+use traits::Reader;
+
+struct Device;
+
+impl traits::Reader for Device {
+    fn read(&self) -> u32 {
+        1
+    }
+}
+
+impl traits::Loader for Device {
+    fn read(&self) -> u32 {
+        2
+    }
+}
+
+fn read_stub(_device: &Device) -> u32 {
+    42
+}
+
+#[kani::proof]
+#[kani::stub(Device::read, read_stub)]
+fn main() {
+    assert_eq!(Reader::read(&Device), 42);
+    assert_eq!(traits::Loader::read(&Device), 2);
+}
@@ -0,0 +1,62 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Check that `#[kani::proof]` generates randomized tests with the `random_testing` feature of the
+# Kani library, and that the seed printed for a failure reproduces it.
+
+set -eu
+
+echo
+echo "Starting random testing check..."
+echo
+
+cd $(dirname $0)/harnesses
+rm -rf build
+CARGO_TEST="cargo test --target-dir build --features kani/random_testing"
+
+echo "Running the passing harnesses..."
+if ! output=$(${CARGO_TEST} -- --skip failing 2>&1); then
+    echo "${output}"
+    echo "Error: the tests of the passing harnesses failed."
+    exit 1
+fi
+for test in kani_random_testing_check_assume_rejects kani_random_testing_check_should_panic; do
+    if ! grep -Fq "test proofs::${test} ... ok" <<< "${output}"; then
+        echo "${output}"
+        echo "Error: the test ${test} was not generated, or it failed."
+        exit 1
+    fi
+done
+
+echo "Running the failing harness..."
+if output=$(${CARGO_TEST} failing_parity 2>&1); then
+    echo "${output}"
+    echo "Error: the test of failing_parity should fail."
+    exit 1
+fi
+seed=$(sed -n 's/.*KANI_RANDOM_SEED=\([0-9]*\).*/\1/p' <<< "${output}" | head -n 1)
+if [[ -z "${seed}" ]]; then
+    echo "${output}"
+    echo "Error: the failure of failing_parity didn't print its seed."
+    exit 1
+fi
+# The harness prints `x` before the assertion, so the last value printed is the one that failed.
+value=$(grep -o "x = [0-9]*" <<< "${output}" | tail -n 1)
+
+echo "Reproducing the failure with seed ${seed}..."
+if output=$(KANI_RANDOM_SEED=${seed} ${CARGO_TEST} failing_parity 2>&1); then
+    echo "${output}"
+    echo "Error: the seed ${seed} didn't reproduce the failure of failing_parity."
+    exit 1
+fi
+values=$(grep -o "x = [0-9]*" <<< "${output}")
+if [[ "${values}" != "${value}" ]]; then
+    echo "${output}"
+    echo "Error: the seed ${seed} should only run the sample with ${value}, but it ran: ${values}"
+    exit 1
+fi
+
+rm -rf build
+echo "Finished random testing check successfully."
+echo
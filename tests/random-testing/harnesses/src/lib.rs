@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Proof harnesses that `check-random-testing.sh` runs as randomized tests with
+//! `cargo test --features kani/random_testing`. The harnesses whose name starts with `failing`
+//! are expected to fail, so the script runs them separately.
+
+pub fn halve(x: u32) -> u32 {
+    x / 2
+}
+
+#[cfg(any(kani, test))]
+mod proofs {
+    use super::*;
+
+    /// Only succeeds if the samples with an odd `x` are rejected.
+    #[kani::proof]
+    fn check_assume_rejects() {
+        let x: u32 = kani::any();
+        kani::assume(x % 2 == 0);
+        assert_eq!(halve(x) * 2, x);
+    }
+
+    #[kani::proof]
+    #[should_panic]
+    fn check_should_panic() {
+        let x: u32 = kani::any();
+        assert_eq!(halve(x) * 2, x);
+    }
+
+    /// Fails for the first sample with an even `x`, which is printed so that the script can check
+    /// that its seed reproduces it.
+    #[kani::proof]
+    fn failing_parity() {
+        let x: u32 = kani::any();
+        println!("x = {x}");
+        assert_ne!(x % 2, 0);
+    }
+}
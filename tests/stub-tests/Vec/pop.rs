@@ -1,12 +0,0 @@
-// Copyright Kani Contributors
-// SPDX-License-Identifier: Apache-2.0 OR MIT
-// kani-flags: --use-abs --abs-type kani
-fn main() {
-    fn pop_test() {
-        let mut vec = kani_vec![1, 2, 3];
-        assert_eq!(vec.pop(), Some(3));
-        assert_eq!(vec, [1, 2]);
-    }
-
-    pop_test();
-}
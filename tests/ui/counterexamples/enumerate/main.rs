@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: --enable-unstable --counterexamples 3 --concrete-playback=print
+
+//! Check that Kani reports every distinct input that violates the assertion, and stops once
+//! there are no more.
+
+#[kani::proof]
+pub fn harness() {
+    let x: u8 = kani::any();
+    assert!(x != 1 && x != 2);
+}
@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: --enable-unstable --minimize-counterexample --concrete-playback=print
+
+//! Check that the counterexample used for the playback test is the smallest failing input.
+
+#[kani::proof]
+pub fn harness() {
+    let x: u32 = kani::any();
+    let y: i16 = kani::any();
+    assert!(x < 1000 || y > -5);
+}
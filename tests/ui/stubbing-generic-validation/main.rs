@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness harness --enable-unstable --enable-stubbing
+//
+//! This tests that we report invalid generic and trait-qualified paths in
+//! stubbing attributes.
+
+trait Clock {
+    fn now(&self) -> u64;
+}
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        0
+    }
+}
+
+struct OtherClock;
+
+fn foo<T>(_x: T) -> bool {
+    false
+}
+
+fn bar<T>(_x: T) -> bool {
+    true
+}
+
+#[kani::proof]
+#[kani::stub(foo::<u8, u16>, bar)]
+#[kani::stub(foo::<u8>, bar::<u8>)]
+#[kani::stub(Clock::now, bar)]
+#[kani::stub(<OtherClock as Clock>::now, bar)]
+#[kani::stub(<SystemClock as foo>::now, bar)]
+fn harness() {}
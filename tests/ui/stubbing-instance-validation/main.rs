@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness harness --enable-unstable --enable-stubbing
+//
+//! This tests that we catch type mismatches between a stub and the instance of
+//! a generic function/method it replaces.
+
+fn size<T>(_x: &T) -> usize {
+    std::mem::size_of::<T>()
+}
+
+fn wrong_param(_x: &u16) -> usize {
+    0
+}
+
+fn wrong_generics<S, T>(_x: &S) -> usize {
+    0
+}
+
+struct Wrapper<T>(T);
+
+impl<T> Wrapper<T> {
+    fn get(&self) -> &T {
+        &self.0
+    }
+}
+
+fn wrong_return(_w: &Wrapper<u8>) -> &u16 {
+    &0
+}
+
+#[kani::proof]
+#[kani::stub(size::<u8>, wrong_param)]
+#[kani::stub(size::<u32>, wrong_generics)]
+#[kani::stub(Wrapper::<u8>::get, wrong_return)]
+fn harness() {
+    size(&0u8);
+    size(&0u32);
+    Wrapper(0u8).get();
+}
@@ -20,7 +20,6 @@ pub enum Mode {
     CargoKani,
     CargoKaniTest, // `cargo kani --tests`. This is temporary and should be removed when s2n-quic moves --tests to `Cargo.toml`.
     Expected,
-    Stub,
 }
 
 impl FromStr for Mode {
@@ -32,7 +31,6 @@ fn from_str(s: &str) -> Result<Mode, ()> {
             "cargo-kani" => Ok(CargoKani),
             "cargo-kani-test" => Ok(CargoKaniTest),
             "expected" => Ok(Expected),
-            "stub-tests" => Ok(Stub),
             _ => Err(()),
         }
     }
@@ -46,7 +44,6 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             CargoKani => "cargo-kani",
             CargoKaniTest => "cargo-kani-test",
             Expected => "expected",
-            Stub => "stub-tests",
         };
         fmt::Display::fmt(s, f)
     }
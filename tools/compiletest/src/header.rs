@@ -192,7 +192,7 @@ pub fn make_test_description<R: Read>(
     let mut should_fail = false;
     let mut ignore_message = None;
 
-    if config.mode == Mode::Kani || config.mode == Mode::Stub {
+    if config.mode == Mode::Kani {
         // If the path to the test contains "fixme" or "ignore", skip it.
         let file_path = path.to_str().unwrap();
         (ignore, ignore_message) = if file_path.contains("fixme") {
@@ -7,7 +7,7 @@
 
 use crate::common::KaniFailStep;
 use crate::common::{output_base_dir, output_base_name};
-use crate::common::{CargoKani, CargoKaniTest, Expected, Kani, KaniFixme, Stub};
+use crate::common::{CargoKani, CargoKaniTest, Expected, Kani, KaniFixme};
 use crate::common::{Config, TestPaths};
 use crate::header::TestProps;
 use crate::json;
@@ -66,7 +66,6 @@ fn run_revision(&self) {
             CargoKani => self.run_cargo_kani_test(false),
             CargoKaniTest => self.run_cargo_kani_test(true),
             Expected => self.run_expected_test(),
-            Stub => self.run_stub_test(),
         }
     }
 
@@ -357,20 +356,6 @@ fn run_expected_test(&self) {
         self.verify_output(&proc_res, &expected);
     }
 
-    /// Runs Kani with stub implementations of various data structures.
-    /// Currently, it only runs tests for the Vec module with the (Kani)Vec
-    /// abstraction. At a later stage, it should be possible to add command-line
-    /// arguments to test specific abstractions and modules.
-    fn run_stub_test(&self) {
-        let proc_res = self.run_kani();
-        if !proc_res.status.success() {
-            self.fatal_proc_rec(
-                "test failed: expected verification success, got failure",
-                &proc_res,
-            );
-        }
-    }
-
     /// Print an error if the verification output does not contain the expected
     /// lines.
     fn verify_output(&self, proc_res: &ProcRes, expected: &str) {